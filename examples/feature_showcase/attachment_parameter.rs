@@ -17,10 +17,16 @@ pub async fn file_details(
     Ok(())
 }
 
-#[poise::command(prefix_command)]
+/// Sums up the sizes of all attached files
+///
+/// For slash commands, `Vec<Attachment>` is turned into multiple optional attachment options,
+/// here `files`, `files_2` and `files_3`
+#[poise::command(prefix_command, slash_command)]
 pub async fn totalsize(
     ctx: Context<'_>,
-    #[description = "File to rename"] files: Vec<serenity::Attachment>,
+    #[description = "Files to examine"]
+    #[max_attachments = 3]
+    files: Vec<serenity::Attachment>,
 ) -> Result<(), Error> {
     let total = files.iter().map(|f| f.size as u64).sum::<u64>();
 
@@ -33,3 +39,24 @@ pub async fn totalsize(
 
     Ok(())
 }
+
+/// Shows the dimensions of an image
+///
+/// Demonstrates `#[content_type]` and `#[max_size]`
+#[poise::command(prefix_command, slash_command)]
+pub async fn image_info(
+    ctx: Context<'_>,
+    #[description = "Image to examine"]
+    #[content_type = "image/*"]
+    #[max_size = "8MB"]
+    image: serenity::Attachment,
+) -> Result<(), Error> {
+    ctx.say(format!(
+        "**{}** is {}x{} pixels",
+        image.filename,
+        image.width.unwrap_or(0),
+        image.height.unwrap_or(0)
+    ))
+    .await?;
+    Ok(())
+}
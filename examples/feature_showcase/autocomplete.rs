@@ -24,6 +24,9 @@ use crate::{Context, Error};
 // - `-> impl Iterator<String>`
 // - `-> impl Iterator<&str>`
 // - `-> impl Iterator<serenity::AutocompleteChoice>
+//
+// For trivial cases, the autocomplete function can also be an inline closure, which receives a
+// Context and the partial input.
 
 async fn autocomplete_name<'a>(
     _ctx: Context<'_>,
@@ -57,8 +60,15 @@ pub async fn greet(
     #[description = "A number... idk I wanted to test number autocomplete"]
     #[autocomplete = "autocomplete_number"]
     number: Option<u32>,
+    #[description = "How to greet"]
+    #[autocomplete = |_ctx, partial| async move {
+        ["Hello", "Hi", "Good morning"]
+            .into_iter()
+            .filter(move |greeting| greeting.starts_with(partial))
+    }]
+    greeting: Option<String>,
 ) -> Result<(), Error> {
-    let mut response = format!("Hello {}", name);
+    let mut response = format!("{} {}", greeting.as_deref().unwrap_or("Hello"), name);
     if let Some(number) = number {
         let _ = write!(response, "#{}", number);
     }
@@ -67,3 +77,14 @@ pub async fn greet(
     ctx.say(response).await?;
     Ok(())
 }
+
+/// Set your time zone. Some parameter types, like poise::Timezone, come with autocomplete built in
+#[poise::command(slash_command, prefix_command)]
+pub async fn set_timezone(
+    ctx: Context<'_>,
+    #[description = "Your time zone, e.g. Europe/Berlin"] timezone: poise::Timezone,
+) -> Result<(), Error> {
+    ctx.say(format!("Your time zone is now {}", timezone))
+        .await?;
+    Ok(())
+}
@@ -17,3 +17,20 @@ pub async fn oracle(
     }
     Ok(())
 }
+
+/// Pretends to delete messages
+///
+/// Demonstrates `#[flag]`: in prefix commands, `~purge 50 --bots` and `~purge --bots 50` both work
+#[poise::command(slash_command, prefix_command)]
+pub async fn purge(
+    ctx: Context<'_>,
+    #[description = "Number of messages to delete"] amount: u32,
+    #[description = "Only delete messages from bots"]
+    #[flag]
+    bots: bool,
+) -> Result<(), Error> {
+    let target = if bots { "bot messages" } else { "messages" };
+    ctx.say(format!("Would delete {} {}", amount, target))
+        .await?;
+    Ok(())
+}
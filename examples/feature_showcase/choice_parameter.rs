@@ -52,3 +52,55 @@ pub async fn inline_choice_int(
         .await?;
     Ok(())
 }
+
+// If the options aren't known at compile time, they can be supplied by a function instead. It's
+// called when the command is registered, so this could read from a config file or environment
+// variable as well.
+fn server_regions() -> Vec<poise::CommandParameterChoice> {
+    ["eu-west", "us-east", "ap-south"]
+        .into_iter()
+        .map(poise::CommandParameterChoice::new)
+        .collect()
+}
+
+#[poise::command(prefix_command, slash_command)]
+pub async fn dynamic_choice(
+    ctx: Context<'_>,
+    #[description = "Region to deploy to"]
+    #[choices_fn = "server_regions"]
+    region: String,
+) -> Result<(), Error> {
+    ctx.say(format!("Deploying to {}", region)).await?;
+    Ok(())
+}
+
+#[derive(Debug, poise::ChoiceParameter)]
+pub enum Interval {
+    // The name is what users see, the value is what Discord sends to the bot
+    #[name = "Every minute"]
+    #[value = 60]
+    Minute,
+    #[name = "Every hour"]
+    #[value = 3600]
+    Hour,
+    #[name = "Every day"]
+    #[value = 86400]
+    Day,
+}
+
+/// Choice parameter whose choices have explicit values instead of their index
+#[poise::command(prefix_command, slash_command)]
+pub async fn valued_choice(
+    ctx: Context<'_>,
+    #[description = "How often to remind you"] interval: Interval,
+) -> Result<(), Error> {
+    use poise::ChoiceParameter as _;
+
+    let seconds = match interval.value() {
+        Some(poise::CommandParameterChoiceValue::Integer(seconds)) => seconds,
+        _ => unreachable!("all variants have integer values"),
+    };
+    ctx.say(format!("Reminding you every {} seconds", seconds))
+        .await?;
+    Ok(())
+}
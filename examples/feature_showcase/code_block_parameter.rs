@@ -10,3 +10,21 @@ pub async fn code(
         .await?;
     Ok(())
 }
+
+/// Shows the language and length of a code block
+///
+/// In the slash command, the backticks can be left out
+#[poise::command(prefix_command, slash_command)]
+pub async fn inspect_code(
+    ctx: Context<'_>,
+    #[description = "Code block or plain code"] code: poise::CodeBlock,
+) -> Result<(), Error> {
+    ctx.say(format!(
+        "Language: {}, {} lines\n{}",
+        code.language.as_deref().unwrap_or("none"),
+        code.code.lines().count(),
+        code,
+    ))
+    .await?;
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use crate::{Context, Error};
+
+/// Reacts to the given message with an emoji
+///
+/// Both unicode emoji like 👍 and custom emoji like <:ferris:1234> are accepted
+#[poise::command(prefix_command, slash_command)]
+pub async fn react(
+    ctx: Context<'_>,
+    #[description = "Message to react to"] msg: poise::serenity_prelude::Message,
+    #[description = "Emoji to react with"] emoji: poise::AnyEmoji,
+) -> Result<(), Error> {
+    msg.react(ctx, emoji.clone()).await?;
+    ctx.say(format!("Reacted with {}", emoji)).await?;
+    Ok(())
+}
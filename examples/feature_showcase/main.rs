@@ -7,8 +7,10 @@ mod choice_parameter;
 mod code_block_parameter;
 mod collector;
 mod context_menu;
+mod emoji_parameter;
 mod inherit_checks;
 mod localization;
+mod message_link_parameter;
 mod modal;
 mod paginate;
 mod panic_handler;
@@ -35,8 +37,11 @@ async fn main() {
             commands: vec![
                 attachment_parameter::file_details(),
                 attachment_parameter::totalsize(),
+                attachment_parameter::image_info(),
                 autocomplete::greet(),
+                autocomplete::set_timezone(),
                 bool_parameter::oracle(),
+                bool_parameter::purge(),
                 #[cfg(feature = "cache")]
                 builtins::servers(),
                 builtins::help(),
@@ -54,13 +59,22 @@ async fn main() {
                 choice_parameter::choice(),
                 choice_parameter::inline_choice(),
                 choice_parameter::inline_choice_int(),
+                choice_parameter::dynamic_choice(),
+                choice_parameter::valued_choice(),
                 code_block_parameter::code(),
+                code_block_parameter::inspect_code(),
                 collector::boop(),
                 context_menu::user_info(),
                 context_menu::echo(),
+                emoji_parameter::react(),
                 inherit_checks::parent_checks(),
                 localization::welcome(),
+                message_link_parameter::quote(),
                 modal::modal(),
+                modal::modal_with_timeout(),
+                modal::modal_wizard(),
+                modal::modal_anywhere(),
+                modal::validated_modal(),
                 modal::component_modal(),
                 paginate::paginate(),
                 panic_handler::div(),
@@ -69,9 +83,14 @@ async fn main() {
                 parameter_attributes::say(),
                 parameter_attributes::punish(),
                 parameter_attributes::stringlen(),
+                parameter_attributes::crate_info(),
+                parameter_attributes::create_event(),
+                parameter_attributes::cookie(),
+                parameter_attributes::average(),
                 raw_identifiers::r#move(),
                 response_with_reply::reply(),
                 subcommands::parent(),
+                subcommands::group(),
                 subcommand_required::parent_subcommand_required(),
                 track_edits::test_reuse_response(),
                 track_edits::add(),
@@ -86,6 +105,13 @@ async fn main() {
                 }),
                 ..Default::default()
             },
+            post_reply_callback: Some(|ctx, reply| {
+                Box::pin(async move {
+                    if let Ok(message) = reply.message().await {
+                        println!("{} replied with message {}", ctx.command().name, message.id);
+                    }
+                })
+            }),
             on_error: |error| {
                 Box::pin(async move {
                     println!("what the hell");
@@ -105,7 +131,7 @@ async fn main() {
         })
         .setup(move |ctx, _ready, framework| {
             Box::pin(async move {
-                poise::builtins::register_globally(ctx, &framework.options().commands).await?;
+                poise::builtins::register_globally(ctx, &framework.commands()).await?;
                 Ok(Data {})
             })
         })
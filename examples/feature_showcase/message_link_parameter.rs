@@ -0,0 +1,19 @@
+use crate::{Context, Error};
+
+/// Quotes a message from this server
+///
+/// Accepts a message link or a `channel_id/message_id` pair
+#[poise::command(prefix_command, slash_command)]
+pub async fn quote(
+    ctx: Context<'_>,
+    #[description = "Link to the message"] link: poise::MessageLink,
+) -> Result<(), Error> {
+    ctx.say(format!(
+        "> {}\n— {} in <#{}>",
+        link.content.replace('\n', "\n> "),
+        link.author.name,
+        link.channel_id,
+    ))
+    .await?;
+    Ok(())
+}
@@ -17,6 +17,105 @@ pub async fn modal(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<()
     Ok(())
 }
 
+/// Gives up on the modal after a minute and tells the user
+#[poise::command(slash_command)]
+pub async fn modal_with_timeout(
+    ctx: poise::ApplicationContext<'_, Data, Error>,
+) -> Result<(), Error> {
+    let options = poise::ModalOptions {
+        timeout: std::time::Duration::from_secs(60),
+        on_timeout: Some(|ctx, interaction| {
+            Box::pin(async move {
+                if let serenity::Interaction::Command(interaction) = interaction {
+                    let message = serenity::CreateInteractionResponseFollowup::new()
+                        .content("The form timed out, run the command again to retry")
+                        .ephemeral(true);
+                    interaction.create_followup(ctx, message).await?;
+                }
+                Ok(())
+            })
+        }),
+        ..Default::default()
+    };
+
+    match poise::execute_modal_with_options::<_, _, MyModal>(ctx, None, options).await {
+        Ok(data) => println!("Got data: {:?}", data),
+        Err(poise::ModalError::Timeout) => println!("Modal timed out"),
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, poise::Modal)]
+#[name = "Shipping address"]
+#[allow(dead_code)] // fields only used for Debug print
+struct AddressModal {
+    street: String,
+    city: String,
+    #[name = "Postal code"]
+    #[validate = "check_postal_code"]
+    postal_code: String,
+}
+
+fn check_postal_code(input: &str) -> Result<(), &'static str> {
+    if input.len() == 5 && input.chars().all(|c| c.is_ascii_digit()) {
+        Ok(())
+    } else {
+        Err("must be five digits")
+    }
+}
+
+#[derive(Debug, poise::Modal)]
+#[name = "Delivery notes"]
+#[allow(dead_code)] // fields only used for Debug print
+struct NotesModal {
+    #[paragraph]
+    notes: Option<String>,
+}
+
+/// Collects a form spread over multiple modals
+#[poise::command(slash_command)]
+pub async fn modal_wizard(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    match poise::execute_modal_wizard::<_, _, (MyModal, AddressModal, NotesModal)>(
+        ctx,
+        Default::default(),
+    )
+    .await
+    {
+        Ok((data, address, notes)) => println!("Got data: {:?} {:?} {:?}", data, address, notes),
+        Err(poise::ModalError::Timeout | poise::ModalError::Cancelled) => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Re-opens the modal until the postal code is valid
+#[poise::command(slash_command)]
+pub async fn validated_modal(ctx: poise::ApplicationContext<'_, Data, Error>) -> Result<(), Error> {
+    match poise::execute_validated_modal::<_, _, AddressModal>(ctx, None, Default::default()).await
+    {
+        Ok(address) => println!("Got data: {:?}", address),
+        Err(poise::ModalError::Timeout | poise::ModalError::Cancelled) => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// Opens the modal directly in slash commands, and via an "Open form" button in prefix commands
+#[poise::command(prefix_command, slash_command)]
+pub async fn modal_anywhere(ctx: crate::Context<'_>) -> Result<(), Error> {
+    match poise::execute_modal_in_context::<_, _, MyModal>(ctx, None, Default::default()).await {
+        Ok(data) => println!("Got data: {:?}", data),
+        Err(poise::ModalError::Timeout) => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
 /// Tests the Modal trait with component interactions.
 ///
 /// Should be both prefix and slash to make sure it works without any slash command interaction
@@ -96,3 +96,95 @@ pub async fn stringlen(
     ctx.say(format!("you wrote: {}", s)).await?;
     Ok(())
 }
+
+/// Looks up a crate by name
+///
+/// Demonstrates `#[regex]`
+#[poise::command(prefix_command, slash_command)]
+pub async fn crate_info(
+    ctx: Context<'_>,
+    #[description = "Name of the crate"]
+    #[regex = "^[a-zA-Z0-9_-]{1,64}$"]
+    name: String,
+) -> Result<(), Error> {
+    ctx.say(format!("https://crates.io/crates/{}", name))
+        .await?;
+    Ok(())
+}
+
+/// Creates a fake event
+///
+/// Demonstrates `named_arguments`: `~create_event title="Game night" when=2024-05-01 private=yes`
+/// works just like the positional `~create_event "Game night" 2024-05-01 yes`
+#[poise::command(prefix_command, slash_command, named_arguments)]
+pub async fn create_event(
+    ctx: Context<'_>,
+    #[description = "Name of the event"] title: String,
+    #[description = "Date of the event"] when: serenity::Timestamp,
+    #[description = "Whether the event is invite-only"] private: Option<bool>,
+) -> Result<(), Error> {
+    ctx.say(format!(
+        "Created {} event **{}** at <t:{}>",
+        if private == Some(true) {
+            "private"
+        } else {
+            "public"
+        },
+        title,
+        when.unix_timestamp(),
+    ))
+    .await?;
+    Ok(())
+}
+
+async fn validate_not_self(ctx: Context<'_>, user: &serenity::User) -> Result<(), String> {
+    if user.id == ctx.author().id {
+        return Err("You can't give a cookie to yourself".into());
+    }
+    if user.bot {
+        return Err(format!("{} is a bot and can't eat cookies", user.name));
+    }
+    Ok(())
+}
+
+/// Gives someone a cookie
+///
+/// Demonstrates `#[validate]`
+#[poise::command(prefix_command, slash_command)]
+pub async fn cookie(
+    ctx: Context<'_>,
+    #[description = "Who to give the cookie to"]
+    #[validate = "validate_not_self"]
+    user: serenity::User,
+) -> Result<(), Error> {
+    ctx.say(format!("{} received a cookie 🍪", user.name))
+        .await?;
+    Ok(())
+}
+
+/// Calculates the average of a list of scores
+///
+/// Demonstrates `#[list]` for `Vec<T>` in slash commands, e.g. `/average scores: 7, 8.5, 10`,
+/// where `#[min]` and `#[max]` apply to each element
+#[poise::command(prefix_command, slash_command)]
+pub async fn average(
+    ctx: Context<'_>,
+    #[description = "Scores between 0 and 10"]
+    #[min = 0]
+    #[max = 10]
+    #[list]
+    scores: Vec<f64>,
+) -> Result<(), Error> {
+    if scores.is_empty() {
+        ctx.say("No scores given").await?;
+        return Ok(());
+    }
+    let average = scores.iter().sum::<f64>() / scores.len() as f64;
+    ctx.say(format!(
+        "Average of {} scores: {:.2}",
+        scores.len(),
+        average
+    ))
+    .await?;
+    Ok(())
+}
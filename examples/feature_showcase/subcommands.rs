@@ -23,3 +23,28 @@ pub async fn child2(ctx: Context<'_>) -> Result<(), Error> {
     ctx.say("You invoked the second child command!").await?;
     Ok(())
 }
+
+/// A command group with the commands of this module as subcommands
+///
+/// Unlike `parent`, this needs no stub parent function. The category and cooldown of the group
+/// apply to both subcommands.
+#[poise::command_group(prefix_command, slash_command, category = "Groups", user_cooldown = 3)]
+pub mod group {
+    use crate::{Context, Error};
+
+    /// A subcommand of `group`
+    #[poise::command(prefix_command, slash_command)]
+    pub async fn first(ctx: Context<'_>) -> Result<(), Error> {
+        ctx.say("You invoked the first command of the group!")
+            .await?;
+        Ok(())
+    }
+
+    /// Another subcommand of `group`
+    #[poise::command(prefix_command, slash_command)]
+    pub async fn second(ctx: Context<'_>) -> Result<(), Error> {
+        ctx.say("You invoked the second command of the group!")
+            .await?;
+        Ok(())
+    }
+}
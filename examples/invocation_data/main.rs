@@ -56,8 +56,7 @@ async fn main() {
     let framework = poise::Framework::builder()
         .setup(move |ctx, _, framework| {
             Box::pin(async move {
-                let commands =
-                    poise::builtins::create_application_commands(&framework.options().commands);
+                let commands = poise::builtins::create_application_commands(&framework.commands());
 
                 serenity::GuildId::new(703332075914264606)
                     .set_commands(ctx, commands)
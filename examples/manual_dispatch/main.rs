@@ -16,6 +16,7 @@ async fn ping(ctx: poise::Context<'_, (), Error>) -> Result<(), Error> {
 
 struct Handler {
     options: poise::FrameworkOptions<(), Error>,
+    commands: poise::CommandRegistry<(), Error>,
     shard_manager: std::sync::Mutex<Option<std::sync::Arc<serenity::ShardManager>>>,
 }
 #[serenity::async_trait]
@@ -23,12 +24,16 @@ impl serenity::EventHandler for Handler {
     async fn message(&self, ctx: serenity::Context, new_message: serenity::Message) {
         // FrameworkContext contains all data that poise::Framework usually manages
         let shard_manager = (*self.shard_manager.lock().unwrap()).clone().unwrap();
-        let framework_data = poise::FrameworkContext {
-            bot_id: serenity::UserId::new(846453852164587620),
-            options: &self.options,
-            user_data: &(),
-            shard_manager: &shard_manager,
-        };
+        let commands = self.commands.load();
+        // Graceful shutdown is managed by poise::Framework, so there's no shutdown handle here
+        let framework_data = poise::FrameworkContext::new(
+            serenity::UserId::new(846453852164587620),
+            &self.options,
+            &commands,
+            &self.commands,
+            &(),
+            &shard_manager,
+        );
 
         let event = serenity::FullEvent::Message { new_message };
         poise::dispatch_event(framework_data, &ctx, event).await;
@@ -41,14 +46,16 @@ impl serenity::EventHandler for Handler {
 async fn main() -> Result<(), Error> {
     let token = std::env::var("DISCORD_TOKEN").expect("missing DISCORD_TOKEN");
     let intents = serenity::GatewayIntents::non_privileged();
-    let mut handler = Handler {
-        options: poise::FrameworkOptions {
-            commands: vec![ping()],
-            ..Default::default()
-        },
+    let mut options = poise::FrameworkOptions {
+        commands: vec![ping()],
+        ..Default::default()
+    };
+    let handler = Handler {
+        // Takes the commands out of the options and prepares them, like poise::Framework does
+        commands: poise::CommandRegistry::new(&mut options),
+        options,
         shard_manager: std::sync::Mutex::new(None),
     };
-    poise::set_qualified_names(&mut handler.options.commands); // some setup
 
     let handler = std::sync::Arc::new(handler);
     let mut client = serenity::Client::builder(token, intents)
@@ -12,9 +12,90 @@ struct VariantAttribute {
     name: Vec<String>,
     #[darling(multiple)]
     name_localized: Vec<crate::util::Tuple2<String>>,
+    value: Option<syn::Lit>,
+}
+
+/// Representation of the enum attribute arguments
+#[derive(Debug, darling::FromMeta)]
+#[darling(allow_unknown_fields)]
+struct EnumAttribute {
+    // Note to self: when adding an attribute here, add it to #[proc_macro_derive]!
+    rename_all: Option<syn::LitStr>,
+}
+
+/// Splits a variant identifier like `HTTPError2Retry` into words: `HTTP`, `Error2`, `Retry`
+fn split_words(ident: &str) -> Vec<String> {
+    let chars = ident.chars().collect::<Vec<_>>();
+    let mut words = Vec::new();
+    let mut word = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if c == '_' {
+            words.extend((!word.is_empty()).then(|| std::mem::take(&mut word)));
+            continue;
+        }
+        let previous = i.checked_sub(1).map(|i| chars[i]);
+        let next = chars.get(i + 1);
+        let starts_word = c.is_uppercase()
+            && previous.is_some_and(|p| {
+                p.is_lowercase()
+                    || p.is_ascii_digit()
+                    || (p.is_uppercase() && next.is_some_and(|n| n.is_lowercase()))
+            });
+        if starts_word && !word.is_empty() {
+            words.push(std::mem::take(&mut word));
+        }
+        word.push(c);
+    }
+    words.extend((!word.is_empty()).then_some(word));
+    words
+}
+
+/// Applies a `#[rename_all]` convention to a variant identifier
+fn rename(ident: &str, convention: &syn::LitStr) -> Result<String, syn::Error> {
+    let words = split_words(ident);
+    let lower = || words.iter().map(|w| w.to_lowercase());
+    let upper = || words.iter().map(|w| w.to_uppercase());
+    let capitalized = || {
+        words.iter().map(|w| {
+            let mut chars = w.chars();
+            let first = chars.next().map(|c| c.to_uppercase().to_string());
+            first.unwrap_or_default() + &chars.as_str().to_lowercase()
+        })
+    };
+    Ok(match &*convention.value() {
+        "lowercase" => lower().collect(),
+        "UPPERCASE" => upper().collect(),
+        "PascalCase" => capitalized().collect(),
+        "camelCase" => {
+            let mut words = capitalized();
+            let first = words.next().map(|w| w.to_lowercase()).unwrap_or_default();
+            first + &words.collect::<String>()
+        }
+        "snake_case" => lower().collect::<Vec<_>>().join("_"),
+        "SCREAMING_SNAKE_CASE" => upper().collect::<Vec<_>>().join("_"),
+        "kebab-case" => lower().collect::<Vec<_>>().join("-"),
+        "SCREAMING-KEBAB-CASE" => upper().collect::<Vec<_>>().join("-"),
+        "Title Case" => capitalized().collect::<Vec<_>>().join(" "),
+        "lower case" => lower().collect::<Vec<_>>().join(" "),
+        _ => {
+            return Err(syn::Error::new(
+                convention.span(),
+                "Unknown rename_all convention. Expected one of \"lowercase\", \"UPPERCASE\", \
+                \"PascalCase\", \"camelCase\", \"snake_case\", \"SCREAMING_SNAKE_CASE\", \
+                \"kebab-case\", \"SCREAMING-KEBAB-CASE\", \"Title Case\", \"lower case\"",
+            ))
+        }
+    })
 }
 
 pub fn choice_parameter(input: syn::DeriveInput) -> Result<TokenStream, darling::Error> {
+    let enum_attrs: Vec<_> = input
+        .attrs
+        .iter()
+        .map(|attr| darling::ast::NestedMeta::Meta(attr.meta.clone()))
+        .collect();
+    let enum_attrs = <EnumAttribute as darling::FromMeta>::from_list(&enum_attrs)?;
+
     let enum_ = match input.data {
         syn::Data::Enum(x) => x,
         _ => {
@@ -31,6 +112,8 @@ pub fn choice_parameter(input: syn::DeriveInput) -> Result<TokenStream, darling:
     let mut alternative_names = Vec::new();
     let mut locales: Vec<Vec<String>> = Vec::new();
     let mut localized_names: Vec<Vec<String>> = Vec::new();
+    let mut values: Vec<Option<syn::Lit>> = Vec::new();
+    let enum_span = input.ident.span();
 
     for variant in enum_.variants {
         if !matches!(&variant.fields, syn::Fields::Unit) {
@@ -48,10 +131,12 @@ pub fn choice_parameter(input: syn::DeriveInput) -> Result<TokenStream, darling:
             .collect();
         let mut attrs = <VariantAttribute as darling::FromMeta>::from_list(&attrs)?;
 
-        let main_name = if attrs.name.is_empty() {
-            variant.ident.to_string()
-        } else {
+        let main_name = if !attrs.name.is_empty() {
             attrs.name.remove(0)
+        } else if let Some(convention) = &enum_attrs.rename_all {
+            rename(&variant.ident.to_string(), convention)?
+        } else {
+            variant.ident.to_string()
         };
 
         variant_idents.push(variant.ident);
@@ -61,8 +146,77 @@ pub fn choice_parameter(input: syn::DeriveInput) -> Result<TokenStream, darling:
         let (a, b) = attrs.name_localized.into_iter().map(|x| (x.0, x.1)).unzip();
         locales.push(a);
         localized_names.push(b);
+        values.push(attrs.value);
     }
 
+    // Either all variants have a value or none, and they're either all integers or all numbers
+    let values = if values.iter().all(Option::is_none) {
+        values
+            .iter()
+            .map(|_| quote::quote! { None })
+            .collect::<Vec<_>>()
+    } else {
+        let values = values
+            .into_iter()
+            .map(|value| {
+                value.ok_or_else(|| {
+                    syn::Error::new(
+                        enum_span,
+                        "If one variant has a #[value], all variants need one",
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let is_number = values.iter().any(|v| matches!(v, syn::Lit::Float(_)));
+        values
+            .iter()
+            .map(|value| match value {
+                syn::Lit::Int(int) if is_number => {
+                    let number = int.base10_parse::<f64>()?;
+                    Ok(quote::quote! { Some(poise::CommandParameterChoiceValue::Number(#number)) })
+                }
+                syn::Lit::Int(int) => {
+                    let int = int.base10_parse::<i32>()?;
+                    Ok(quote::quote! { Some(poise::CommandParameterChoiceValue::Integer(#int)) })
+                }
+                syn::Lit::Float(float) => {
+                    let number = float.base10_parse::<f64>()?;
+                    Ok(quote::quote! { Some(poise::CommandParameterChoiceValue::Number(#number)) })
+                }
+                other => Err(syn::Error::new(
+                    other.span(),
+                    "Choice values must be integer or float literals",
+                )),
+            })
+            .collect::<Result<Vec<_>, syn::Error>>()?
+    };
+
+    // Prefix commands accept localized names too, so that users can type the name they see in
+    // slash commands. Duplicates are skipped, which would be unreachable match arms otherwise
+    let mut seen_localized_names = std::collections::HashSet::new();
+    let (localized_variants, lowercase_localized_names): (Vec<_>, Vec<_>) = variant_idents
+        .iter()
+        .zip(&localized_names)
+        .filter_map(|(variant, names)| {
+            let names = names
+                .iter()
+                .map(|name| name.to_lowercase())
+                .filter(|name| seen_localized_names.insert(name.clone()))
+                .collect::<Vec<_>>();
+            (!names.is_empty()).then_some((variant, names))
+        })
+        .unzip();
+
+    let localized_name_match = match localized_variants.is_empty() {
+        true => quote::quote! { None },
+        false => quote::quote! {
+            match name.to_lowercase().as_str() {
+                #( #( #lowercase_localized_names )|* => Some(Self::#localized_variants), )*
+                _ => None,
+            }
+        },
+    };
+
     let enum_ident = &input.ident;
     let indices = 0..variant_idents.len();
     Ok(quote::quote! {
@@ -74,6 +228,7 @@ pub fn choice_parameter(input: syn::DeriveInput) -> Result<TokenStream, darling:
                     localizations: std::collections::HashMap::from([
                         #( (#locales.to_string(), #localized_names.to_string()) ),*
                     ]),
+                    value: #values,
                 }, )* ]
             }
 
@@ -90,7 +245,7 @@ pub fn choice_parameter(input: syn::DeriveInput) -> Result<TokenStream, darling:
                 {
                     return Some(Self::#variant_idents);
                 } )*
-                None
+                #localized_name_match
             }
 
             fn name(&self) -> &'static str {
@@ -1,5 +1,7 @@
+mod permissions;
 mod prefix;
 mod slash;
+mod validation;
 
 use crate::util::{
     iter_tuple_2_to_hash_map, wrap_option, wrap_option_and_map, wrap_option_to_string,
@@ -25,22 +27,29 @@ pub struct CommandArgs {
     reuse_response: bool,
     track_deletion: bool,
     track_edits: bool,
+    // In seconds
+    edit_tracking_window: Option<u64>,
     broadcast_typing: bool,
     help_text_fn: Option<syn::Path>,
     #[darling(multiple)]
-    check: Vec<syn::Path>,
+    check: Vec<Check>,
     on_error: Option<syn::Path>,
+    on_parse_error: Option<syn::Path>,
+    on_check_failure: Option<syn::Path>,
     rename: Option<String>,
     #[darling(multiple)]
     name_localized: Vec<crate::util::Tuple2<String>>,
     #[darling(multiple)]
     description_localized: Vec<crate::util::Tuple2<String>>,
     discard_spare_arguments: bool,
+    named_arguments: bool,
     hide_in_help: bool,
     ephemeral: bool,
-    default_member_permissions: Option<syn::punctuated::Punctuated<syn::Ident, syn::Token![|]>>,
-    required_permissions: Option<syn::punctuated::Punctuated<syn::Ident, syn::Token![|]>>,
-    required_bot_permissions: Option<syn::punctuated::Punctuated<syn::Ident, syn::Token![|]>>,
+    // In milliseconds
+    auto_defer: Option<u64>,
+    default_member_permissions: Option<permissions::Permissions>,
+    required_permissions: Option<permissions::Permissions>,
+    required_bot_permissions: Option<permissions::Permissions>,
     owners_only: bool,
     guild_only: bool,
     dm_only: bool,
@@ -55,6 +64,66 @@ pub struct CommandArgs {
     guild_cooldown: Option<u64>,
     channel_cooldown: Option<u64>,
     member_cooldown: Option<u64>,
+
+    max_concurrent: Option<usize>,
+    user_max_concurrent: Option<usize>,
+    guild_max_concurrent: Option<usize>,
+}
+
+/// A `check` argument: path to the check function and an optional message that's shown to the user
+/// when the check denies access. Written as `check = "path"`, `check = "path: message"` or
+/// `check(path, "message")`
+#[derive(Debug)]
+struct Check {
+    path: syn::Path,
+    message: Option<String>,
+}
+
+impl darling::FromMeta for Check {
+    fn from_string(value: &str) -> darling::Result<Self> {
+        // The separator is the first colon that isn't part of a `::` path separator
+        let bytes = value.as_bytes();
+        let separator = (0..bytes.len()).find(|&i| {
+            bytes[i] == b':' && bytes.get(i + 1) != Some(&b':') && (i == 0 || bytes[i - 1] != b':')
+        });
+        let (path, message) = match separator {
+            Some(i) => (&value[..i], Some(value[i + 1..].trim().to_owned())),
+            None => (value, None),
+        };
+        Ok(Self {
+            path: syn::parse_str(path.trim())
+                .map_err(|_| darling::Error::unknown_value(path.trim()))?,
+            message,
+        })
+    }
+
+    fn from_expr(expr: &syn::Expr) -> darling::Result<Self> {
+        match expr {
+            syn::Expr::Path(path) => Ok(Self {
+                path: path.path.clone(),
+                message: None,
+            }),
+            syn::Expr::Lit(lit) => Self::from_value(&lit.lit),
+            _ => Err(darling::Error::unexpected_expr_type(expr)),
+        }
+    }
+
+    fn from_list(items: &[darling::ast::NestedMeta]) -> darling::Result<Self> {
+        match items {
+            [path, message] => Ok(Self {
+                path: match path {
+                    darling::ast::NestedMeta::Meta(syn::Meta::Path(path)) => path.clone(),
+                    _ => Self::from_nested_meta(path)?.path,
+                },
+                message: Some(String::from_nested_meta(message)?),
+            }),
+            _ => Err(syn::Error::new(
+                proc_macro2::Span::call_site(),
+                "expected a check function and a message `(check, \"message\")`",
+            )
+            .into()),
+        }
+    }
 }
 
 /// Representation of the function parameter attribute arguments
@@ -68,13 +137,21 @@ struct ParamArgs {
     name_localized: Vec<crate::util::Tuple2<String>>,
     #[darling(multiple)]
     description_localized: Vec<crate::util::Tuple2<String>>,
-    autocomplete: Option<syn::Path>,
+    autocomplete: Option<syn::Expr>,
     channel_types: Option<crate::util::List<syn::Ident>>,
     choices: Option<crate::util::List<syn::Lit>>,
+    choices_fn: Option<syn::Path>,
     min: Option<syn::Lit>,
     max: Option<syn::Lit>,
     min_length: Option<syn::Lit>,
     max_length: Option<syn::Lit>,
+    regex: Option<syn::LitStr>,
+    validate: Option<syn::Path>,
+    content_type: Option<syn::LitStr>,
+    max_size: Option<syn::LitStr>,
+    max_attachments: Option<usize>,
+    type_autocomplete: bool,
+    list: bool,
     lazy: bool,
     flag: bool,
     rest: bool,
@@ -190,6 +267,14 @@ pub fn command(
             let message = "#[rename = \"...\"] must be specified for pattern parameters";
             return Err(syn::Error::new(pattern.pat.span(), message).into());
         };
+        if attrs.choices.is_some() && attrs.choices_fn.is_some() {
+            let message = "#[choices] and #[choices_fn] cannot be used together";
+            return Err(syn::Error::new(span, message).into());
+        }
+        if attrs.list && !crate::util::is_slash_list(&pattern.ty) {
+            let message = "#[list] can only be used on Vec parameters other than Vec<Attachment>";
+            return Err(syn::Error::new(span, message).into());
+        }
         parameters.push(CommandParameter {
             name,
             type_: (*pattern.ty).clone(),
@@ -201,12 +286,10 @@ pub fn command(
     // Extract the command descriptions from the function doc comments
     let (description, help_text) = extract_help_from_doc_comments(&function.attrs);
 
-    fn permissions_to_tokens(
-        perms: &Option<syn::punctuated::Punctuated<syn::Ident, syn::Token![|]>>,
-    ) -> syn::Expr {
+    fn permissions_to_tokens(perms: &Option<permissions::Permissions>) -> syn::Expr {
         match perms {
             Some(perms) => {
-                let perms = perms.iter();
+                let perms = perms.0.iter();
                 syn::parse_quote! { #(poise::serenity_prelude::Permissions::#perms)|* }
             }
             None => syn::parse_quote! { poise::serenity_prelude::Permissions::empty() },
@@ -281,6 +364,9 @@ fn generate_command(mut inv: Invocation) -> Result<proc_macro2::TokenStream, dar
     let category = wrap_option_to_string(inv.args.category.as_ref());
 
     let cooldown_config = generate_cooldown_config(&inv.args);
+    let max_concurrent = wrap_option(inv.args.max_concurrent);
+    let user_max_concurrent = wrap_option(inv.args.user_max_concurrent);
+    let guild_max_concurrent = wrap_option(inv.args.guild_max_concurrent);
 
     let default_member_permissions = &inv.default_member_permissions;
     let required_permissions = &inv.required_permissions;
@@ -299,22 +385,37 @@ fn generate_command(mut inv: Invocation) -> Result<proc_macro2::TokenStream, dar
         },
     };
 
-    let checks = &inv.args.check;
+    let checks = inv.args.check.iter().map(|check| &check.path);
+    let check_failure_messages = inv.args.check.iter().map(|check| match &check.message {
+        Some(message) => quote::quote! { Some(#message.to_string()) },
+        None => quote::quote! { None },
+    });
     // Box::pin the callback in order to store it in a struct
-    let on_error = match &inv.args.on_error {
-        Some(on_error) => quote::quote! { Some(|err| Box::pin(#on_error(err))) },
+    let wrap_error_handler = |handler: &Option<syn::Path>| match handler {
+        Some(handler) => quote::quote! { Some(|err| Box::pin(#handler(err))) },
         None => quote::quote! { None },
     };
+    let on_error = wrap_error_handler(&inv.args.on_error);
+    let on_parse_error = wrap_error_handler(&inv.args.on_parse_error);
+    let on_check_failure = wrap_error_handler(&inv.args.on_check_failure);
 
     let invoke_on_edit = inv.args.invoke_on_edit || inv.args.track_edits;
     let reuse_response = inv.args.reuse_response || inv.args.track_edits;
     let track_deletion = inv.args.track_deletion || inv.args.track_edits;
+    let edit_tracking_window = match inv.args.edit_tracking_window {
+        Some(secs) => quote::quote! { Some(std::time::Duration::from_secs(#secs)) },
+        None => quote::quote! { None },
+    };
     let broadcast_typing = inv.args.broadcast_typing;
     let aliases = &inv.args.aliases.0;
     let subcommands = &inv.args.subcommands.0;
 
     let parameters = slash::generate_parameters(&inv)?;
     let ephemeral = inv.args.ephemeral;
+    let auto_defer = match inv.args.auto_defer {
+        Some(millis) => quote::quote! { Some(std::time::Duration::from_millis(#millis)) },
+        None => quote::quote! { None },
+    };
     let custom_data = match &inv.args.custom_data {
         Some(custom_data) => quote::quote! { Box::new(#custom_data) },
         None => quote::quote! { Box::new(()) },
@@ -356,6 +457,13 @@ fn generate_command(mut inv: Invocation) -> Result<proc_macro2::TokenStream, dar
                 hide_in_help: #hide_in_help,
                 cooldowns: std::sync::Mutex::new(::poise::Cooldowns::new()),
                 cooldown_config: #cooldown_config,
+                max_concurrent: ::poise::ConcurrencyLimits {
+                    global: #max_concurrent,
+                    user: #user_max_concurrent,
+                    guild: #guild_max_concurrent,
+                    __non_exhaustive: (),
+                },
+                concurrency: std::sync::Mutex::new(::poise::ConcurrencyTracker::new()),
                 reuse_response: #reuse_response,
                 default_member_permissions: #default_member_permissions,
                 required_permissions: #required_permissions,
@@ -364,18 +472,25 @@ fn generate_command(mut inv: Invocation) -> Result<proc_macro2::TokenStream, dar
                 guild_only: #guild_only,
                 dm_only: #dm_only,
                 nsfw_only: #nsfw_only,
+                enabled: std::sync::atomic::AtomicBool::new(true),
                 checks: vec![ #( |ctx| Box::pin(#checks(ctx)) ),* ],
+                check_failure_messages: vec![ #( #check_failure_messages ),* ],
                 on_error: #on_error,
+                on_parse_error: #on_parse_error,
+                on_check_failure: #on_check_failure,
                 parameters: vec![ #( #parameters ),* ],
                 custom_data: #custom_data,
 
                 aliases: vec![ #( #aliases.to_string(), )* ],
                 invoke_on_edit: #invoke_on_edit,
                 track_deletion: #track_deletion,
+                edit_tracking_window: #edit_tracking_window,
                 broadcast_typing: #broadcast_typing,
 
                 context_menu_name: #context_menu_name,
                 ephemeral: #ephemeral,
+                auto_defer: #auto_defer,
+                application_command_id: std::sync::Mutex::new(None),
 
                 __non_exhaustive: (),
             }
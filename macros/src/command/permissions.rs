@@ -0,0 +1,124 @@
+//! Parses the permission arguments of `#[poise::command]`, like
+//! `required_permissions = "MANAGE_MESSAGES | KICK_MEMBERS"`
+
+/// Permission names of serenity's `Permissions` bitflags. Names outside this list are passed on
+/// unchanged, so permissions added to serenity later still work, but typos close to a known name
+/// are reported with a suggestion
+const KNOWN_PERMISSIONS: &[&str] = &[
+    "CREATE_INSTANT_INVITE",
+    "KICK_MEMBERS",
+    "BAN_MEMBERS",
+    "ADMINISTRATOR",
+    "MANAGE_CHANNELS",
+    "MANAGE_GUILD",
+    "ADD_REACTIONS",
+    "VIEW_AUDIT_LOG",
+    "PRIORITY_SPEAKER",
+    "STREAM",
+    "VIEW_CHANNEL",
+    "SEND_MESSAGES",
+    "SEND_TTS_MESSAGES",
+    "MANAGE_MESSAGES",
+    "EMBED_LINKS",
+    "ATTACH_FILES",
+    "READ_MESSAGE_HISTORY",
+    "MENTION_EVERYONE",
+    "USE_EXTERNAL_EMOJIS",
+    "VIEW_GUILD_INSIGHTS",
+    "CONNECT",
+    "SPEAK",
+    "MUTE_MEMBERS",
+    "DEAFEN_MEMBERS",
+    "MOVE_MEMBERS",
+    "USE_VAD",
+    "CHANGE_NICKNAME",
+    "MANAGE_NICKNAMES",
+    "MANAGE_ROLES",
+    "MANAGE_WEBHOOKS",
+    "MANAGE_GUILD_EXPRESSIONS",
+    "MANAGE_EMOJIS_AND_STICKERS",
+    "USE_APPLICATION_COMMANDS",
+    "REQUEST_TO_SPEAK",
+    "MANAGE_EVENTS",
+    "MANAGE_THREADS",
+    "CREATE_PUBLIC_THREADS",
+    "CREATE_PRIVATE_THREADS",
+    "USE_EXTERNAL_STICKERS",
+    "SEND_MESSAGES_IN_THREADS",
+    "USE_EMBEDDED_ACTIVITIES",
+    "MODERATE_MEMBERS",
+    "VIEW_CREATOR_MONETIZATION_ANALYTICS",
+    "USE_SOUNDBOARD",
+    "CREATE_GUILD_EXPRESSIONS",
+    "CREATE_EVENTS",
+    "USE_EXTERNAL_SOUNDS",
+    "SEND_VOICE_MESSAGES",
+    "SET_VOICE_CHANNEL_STATUS",
+    "SEND_POLLS",
+    "USE_EXTERNAL_APPS",
+];
+
+/// Permission names separated by `|`, e.g. `"MANAGE_MESSAGES | KICK_MEMBERS"`. Names are
+/// case-insensitive and may use spaces instead of underscores, e.g. `"manage messages"`
+#[derive(Debug)]
+pub struct Permissions(pub Vec<syn::Ident>);
+
+impl darling::FromMeta for Permissions {
+    fn from_value(value: &syn::Lit) -> darling::Result<Self> {
+        let syn::Lit::Str(string) = value else {
+            return Err(darling::Error::unexpected_lit_type(value));
+        };
+        string
+            .value()
+            .split('|')
+            .map(|name| parse_permission(name, string.span()))
+            .collect::<darling::Result<Vec<_>>>()
+            .map(Self)
+            .map_err(|e| e.with_span(value))
+    }
+}
+
+/// Normalizes a single permission name to the name of the serenity constant and checks it
+fn parse_permission(name: &str, span: proc_macro2::Span) -> darling::Result<syn::Ident> {
+    let name = name
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("_")
+        .to_uppercase();
+    if name.is_empty() {
+        return Err(darling::Error::custom("empty permission name"));
+    }
+    if !KNOWN_PERMISSIONS.contains(&&*name) {
+        let closest = KNOWN_PERMISSIONS
+            .iter()
+            .map(|known| (edit_distance(&name, known), known))
+            .min();
+        if let Some((distance, known)) = closest {
+            if distance <= 2 {
+                return Err(darling::Error::custom(format!(
+                    "unknown permission `{}`, did you mean `{}`?",
+                    name, known
+                )));
+            }
+        }
+    }
+    syn::parse_str::<syn::Ident>(&name)
+        .map(|ident| syn::Ident::new(&ident.to_string(), span))
+        .map_err(|_| darling::Error::custom(format!("invalid permission name `{}`", name)))
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut previous_diagonal = row[0];
+        row[0] = i + 1;
+        for (j, &b_char) in b.iter().enumerate() {
+            let substitution = previous_diagonal + usize::from(a_char != b_char);
+            previous_diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(row[j + 1] + 1);
+        }
+    }
+    row[b.len()]
+}
@@ -37,6 +37,79 @@ fn quote_parameter(p: &super::CommandParameter) -> Result<proc_macro2::TokenStre
     })
 }
 
+/// Wraps the positional argument parsing code such that, if the user supplied any `name=value`
+/// arguments, each parameter is parsed from its named value instead
+fn generate_named_args_parsing(
+    inv: &Invocation,
+    param_idents: &[syn::Ident],
+    args: &proc_macro2::TokenStream,
+    positional_parsing: proc_macro2::TokenStream,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let param_names = inv.parameters.iter().map(|p| &p.name);
+    let mut named_parses = Vec::new();
+    for (i, p) in inv.parameters.iter().enumerate() {
+        let name = &p.name;
+        let parse = |spec: proc_macro2::TokenStream, value: proc_macro2::TokenStream| {
+            quote::quote! {
+                ::poise::parse_prefix_args!(ctx.serenity_context, ctx.msg, #value, 0 => #spec)
+                    .await
+                    .map_err(|(error, input)| poise::FrameworkError::new_argument_parse_for(
+                        ctx.into(),
+                        input,
+                        error,
+                        Some(#name),
+                    ))?
+                    .0
+            }
+        };
+        named_parses.push(if p.args.flag {
+            let parse = parse(quote::quote! { (bool) }, quote::quote! { value });
+            quote::quote! { match poise_values[#i] {
+                Some(value) => #parse,
+                None => false,
+            } }
+        } else if p.args.rest {
+            // Parsing a #[rest] parameter doesn't strip quotes by itself
+            let value = quote::quote! { poise_values[#i].unwrap_or("").trim_matches('"') };
+            parse(quote_parameter(p)?, value)
+        } else {
+            parse(
+                quote_parameter(p)?,
+                quote::quote! { poise_values[#i].unwrap_or("") },
+            )
+        });
+    }
+
+    let spare_arguments_check = match inv.args.discard_spare_arguments {
+        true => quote::quote! {},
+        false => quote::quote! {
+            if !poise_rest.trim().is_empty() {
+                return Err(poise::FrameworkError::new_argument_parse(
+                    ctx.into(),
+                    Some(poise_rest.trim().to_owned()),
+                    Box::new(::poise::TooManyArguments::default()),
+                ));
+            }
+        },
+    };
+
+    Ok(quote::quote! {
+        let ( #( #param_idents, )* ) = match ::poise::extract_named_args(
+            #args,
+            &[ #( #param_names ),* ],
+        ) {
+            Some((poise_rest, poise_values)) => {
+                #spare_arguments_check
+                ( #( #named_parses, )* )
+            }
+            None => {
+                #positional_parsing
+                ( #( #param_idents, )* )
+            }
+        };
+    })
+}
+
 pub fn generate_prefix_action(inv: &Invocation) -> Result<proc_macro2::TokenStream, syn::Error> {
     let param_idents = (0..inv.parameters.len())
         .map(|i| format_ident!("poise_param_{i}"))
@@ -50,18 +123,51 @@ pub fn generate_prefix_action(inv: &Invocation) -> Result<proc_macro2::TokenStre
         true => Some(quote::quote! { #[rest] (Option<String>), }),
         false => None,
     };
+    let param_checks = super::validation::generate_parameter_checks(inv, &param_idents, true)?;
+
+    // `--flag` style flags may appear anywhere in the message, so they're extracted beforehand
+    let (flag_names, flag_idents): (Vec<_>, Vec<_>) = inv
+        .parameters
+        .iter()
+        .zip(&param_idents)
+        .filter(|(p, _)| p.args.flag)
+        .map(|(p, ident)| (&p.name, ident))
+        .unzip();
+    let flag_indices = (0..flag_names.len()).map(syn::Index::from);
+    let (extract_flags, args) = match flag_names.is_empty() {
+        true => (quote::quote! {}, quote::quote! { ctx.args }),
+        false => (
+            quote::quote! {
+                let (poise_args, poise_flags) =
+                    ::poise::extract_flags(ctx.args, &[ #( #flag_names ),* ]);
+            },
+            quote::quote! { &poise_args },
+        ),
+    };
+
+    let param_names = inv.parameters.iter().map(|p| &p.name);
+    let mut parse_args = quote::quote! {
+        let ( #( #param_idents, )* .. ) = ::poise::parse_prefix_args!(
+            @with_position ctx.serenity_context, ctx.msg, #args, 0 =>
+            #( #param_specs, )*
+            #wildcard_arg
+        ).await.map_err(|(error, input, position)| poise::FrameworkError::new_argument_parse_for(
+            ctx.into(),
+            input,
+            error,
+            position.and_then(|i| <[&str]>::get(&[ #( #param_names ),* ], i).copied()),
+        ))?;
+    };
+    if inv.args.named_arguments {
+        parse_args = generate_named_args_parsing(inv, &param_idents, &args, parse_args)?;
+    }
 
     Ok(quote::quote! {
         |ctx| Box::pin(async move {
-            let ( #( #param_idents, )* .. ) = ::poise::parse_prefix_args!(
-                ctx.serenity_context, ctx.msg, ctx.args, 0 =>
-                #( #param_specs, )*
-                #wildcard_arg
-            ).await.map_err(|(error, input)| poise::FrameworkError::new_argument_parse(
-                ctx.into(),
-                input,
-                error,
-            ))?;
+            #extract_flags
+            #parse_args
+            #( let #flag_idents = #flag_idents || poise_flags[#flag_indices]; )*
+            #param_checks
 
             if !ctx.framework.options.manual_cooldowns {
                 ctx.command.cooldowns.lock().unwrap().start_cooldown(ctx.cooldown_context());
@@ -1,16 +1,96 @@
 use super::Invocation;
 use crate::util::{
-    extract_type_parameter, iter_tuple_2_to_hash_map, tuple_2_iter_deref, wrap_option_to_string,
+    extract_type_parameter, has_builtin_autocomplete, is_attachment_vec, iter_tuple_2_to_hash_map,
+    tuple_2_iter_deref, wrap_option_to_string,
 };
 use quote::format_ident;
 use syn::spanned::Spanned as _;
 
+/// Number of attachment options that a `Vec<Attachment>` parameter is expanded into, unless set
+/// with `#[max_attachments]`
+const DEFAULT_SLASH_ATTACHMENTS: usize = 5;
+
+/// Discord's limit of options per slash command
+const MAX_SLASH_OPTIONS: usize = 25;
+
+/// A slash command option that a parameter maps to
+pub struct SlashOptionSlot {
+    /// Appended to the parameter name and its localizations
+    pub name_suffix: String,
+    /// Appended to the parameter description and its localizations
+    pub description_suffix: String,
+}
+
+/// Returns the slash command options that a parameter maps to. That's a single option named like
+/// the parameter, except for `Vec<Attachment>` parameters: slash commands can't take a variable
+/// number of attachments, so those are expanded into multiple optional attachment options. For a
+/// parameter `files`, they're named `files`, `files_2`, `files_3` and so on, and the descriptions
+/// of all but the first option get a ` (2)`, ` (3)`, ... suffix, in every locale.
+pub fn slash_option_slots(
+    param: &super::CommandParameter,
+) -> Result<Vec<SlashOptionSlot>, syn::Error> {
+    if !is_attachment_vec(&param.type_) {
+        if param.args.max_attachments.is_some() {
+            return Err(syn::Error::new(
+                param.span,
+                "#[max_attachments] can only be used on Vec<Attachment> parameters",
+            ));
+        }
+        return Ok(vec![SlashOptionSlot {
+            name_suffix: String::new(),
+            description_suffix: String::new(),
+        }]);
+    }
+
+    let count = param
+        .args
+        .max_attachments
+        .unwrap_or(DEFAULT_SLASH_ATTACHMENTS);
+    if !(1..=MAX_SLASH_OPTIONS).contains(&count) {
+        return Err(syn::Error::new(
+            param.span,
+            format!(
+                "#[max_attachments] must be between 1 and {}",
+                MAX_SLASH_OPTIONS
+            ),
+        ));
+    }
+    Ok((1..=count)
+        .map(|i| match i {
+            1 => SlashOptionSlot {
+                name_suffix: String::new(),
+                description_suffix: String::new(),
+            },
+            i => SlashOptionSlot {
+                name_suffix: format!("_{}", i),
+                description_suffix: format!(" ({})", i),
+            },
+        })
+        .collect())
+}
+
+/// Appends a suffix to all values of the given localizations map expression
+fn suffix_localizations(
+    localizations: &proc_macro2::TokenStream,
+    suffix: &str,
+) -> proc_macro2::TokenStream {
+    if suffix.is_empty() {
+        return localizations.clone();
+    }
+    quote::quote! { {
+        let mut localizations: std::collections::HashMap<String, String> = #localizations;
+        for value in localizations.values_mut() {
+            value.push_str(#suffix);
+        }
+        localizations
+    } }
+}
+
 pub fn generate_parameters(inv: &Invocation) -> Result<Vec<proc_macro2::TokenStream>, syn::Error> {
     let mut parameter_structs = Vec::new();
     for param in &inv.parameters {
         // no #[description] check here even if slash_command set, so users can programatically
         // supply descriptions later (e.g. via translation framework like fluent)
-        let description = wrap_option_to_string(param.args.description.as_ref());
 
         let (mut required, type_) = match extract_type_parameter("Option", &param.type_)
             .or_else(|| extract_type_parameter("Vec", &param.type_))
@@ -32,6 +112,18 @@ pub fn generate_parameters(inv: &Invocation) -> Result<Vec<proc_macro2::TokenStr
 
         let autocomplete_callback = match &param.args.autocomplete {
             Some(autocomplete_fn) => {
+                // Paths are called directly, so the function can take either context type.
+                // Closures need the context type up front to type-check their body
+                let autocomplete_call = match autocomplete_fn {
+                    syn::Expr::Path(_) => quote::quote! { #autocomplete_fn(ctx.into(), partial) },
+                    _ => quote::quote! {
+                        ::poise::call_autocomplete_closure(
+                            poise::Context::Application(ctx),
+                            partial,
+                            #autocomplete_fn,
+                        )
+                    },
+                };
                 quote::quote! { Some(|
                     ctx: poise::ApplicationContext<'_, _, _>,
                     partial: &str,
@@ -39,7 +131,7 @@ pub fn generate_parameters(inv: &Invocation) -> Result<Vec<proc_macro2::TokenStr
                     use ::poise::futures_util::{Stream, StreamExt};
 
                     let choices_stream = ::poise::into_stream!(
-                        #autocomplete_fn(ctx.into(), partial).await
+                        #autocomplete_call.await
                     );
                     let choices_vec = choices_stream
                         .take(25)
@@ -52,6 +144,30 @@ pub fn generate_parameters(inv: &Invocation) -> Result<Vec<proc_macro2::TokenStr
                     Ok(response.set_choices(choices_vec))
                 })) }
             }
+            // Types may come with their own autocomplete suggestions (e.g. `poise::Timezone`).
+            // Only generated for those types, to not bloat every parameter with a dead match
+            None if inv.args.slash_command
+                && param.args.choices.is_none()
+                && !param.args.list
+                && (param.args.type_autocomplete || has_builtin_autocomplete(&param.type_)) =>
+            {
+                quote::quote! {
+                match poise::slash_argument_autocomplete!(#type_) {
+                    Some(_) => Some(|
+                        _: poise::ApplicationContext<'_, _, _>,
+                        partial: &str,
+                    | Box::pin(async move {
+                        let choices = match poise::slash_argument_autocomplete!(#type_) {
+                            Some(autocomplete) => autocomplete(partial),
+                            None => Vec::new(),
+                        };
+                        let response = poise::serenity_prelude::CreateAutocompleteResponse::default();
+                        Ok(response.set_choices(choices))
+                    })),
+                    None => None,
+                }
+                }
+            }
             None => quote::quote! { None },
         };
 
@@ -78,6 +194,9 @@ pub fn generate_parameters(inv: &Invocation) -> Result<Vec<proc_macro2::TokenStr
             true => {
                 if let Some(_choices) = &param.args.choices {
                     quote::quote! { Some(|o| o.kind(::poise::serenity_prelude::CommandOptionType::Integer)) }
+                } else if param.args.list {
+                    // Bounds apply to the individual elements, so they're checked after parsing
+                    quote::quote! { Some(|o| o.kind(::poise::serenity_prelude::CommandOptionType::String)) }
                 } else {
                     quote::quote! { Some(|o| {
                         poise::create_slash_argument!(#type_, o)
@@ -92,11 +211,15 @@ pub fn generate_parameters(inv: &Invocation) -> Result<Vec<proc_macro2::TokenStr
         // TODO: move this to poise::CommandParameter::choices (is there a reason not to?)
         let choices = match inv.args.slash_command {
             true => {
-                if let Some(choices) = &param.args.choices {
+                if param.args.list {
+                    // Elements are typed in freely, so there's nothing to pick from
+                    quote::quote! { vec![] }
+                } else if let Some(choices) = &param.args.choices {
                     let choices = &choices.0;
                     quote::quote! { vec![#( ::poise::CommandParameterChoice {
                         name: ToString::to_string(&#choices),
                         localizations: Default::default(),
+                        value: None,
                         __non_exhaustive: (),
                     } ),*] }
                 } else {
@@ -106,6 +229,11 @@ pub fn generate_parameters(inv: &Invocation) -> Result<Vec<proc_macro2::TokenStr
             false => quote::quote! { vec![] },
         };
 
+        let dynamic_choices = match &param.args.choices_fn {
+            Some(choices_fn) if !param.args.list => quote::quote! { Some(#choices_fn) },
+            _ => quote::quote! { None },
+        };
+
         let channel_types = match &param.args.channel_types {
             Some(crate::util::List(channel_types)) => quote::quote! { Some(
                 vec![ #( poise::serenity_prelude::ChannelType::#channel_types ),* ]
@@ -113,23 +241,37 @@ pub fn generate_parameters(inv: &Invocation) -> Result<Vec<proc_macro2::TokenStr
             None => quote::quote! { None },
         };
 
-        parameter_structs.push((
-            quote::quote! {
-                ::poise::CommandParameter {
-                    name: #param_name.to_string(),
-                    name_localizations: #name_localizations,
-                    description: #description,
-                    description_localizations: #desc_localizations,
-                    required: #required,
-                    channel_types: #channel_types,
-                    type_setter: #type_setter,
-                    choices: #choices,
-                    autocomplete_callback: #autocomplete_callback,
-                    __non_exhaustive: (),
-                }
-            },
-            required,
-        ));
+        for slot in slash_option_slots(param)? {
+            let option_name = format!("{}{}", param_name, slot.name_suffix);
+            let name_localizations = suffix_localizations(&name_localizations, &slot.name_suffix);
+            let description = param
+                .args
+                .description
+                .as_ref()
+                .map(|description| format!("{}{}", description, slot.description_suffix));
+            let description = wrap_option_to_string(description.as_ref());
+            let desc_localizations =
+                suffix_localizations(&desc_localizations, &slot.description_suffix);
+
+            parameter_structs.push((
+                quote::quote! {
+                    ::poise::CommandParameter {
+                        name: #option_name.to_string(),
+                        name_localizations: #name_localizations,
+                        description: #description,
+                        description_localizations: #desc_localizations,
+                        required: #required,
+                        channel_types: #channel_types,
+                        type_setter: #type_setter,
+                        choices: #choices,
+                        dynamic_choices: #dynamic_choices,
+                        autocomplete_callback: #autocomplete_callback,
+                        __non_exhaustive: (),
+                    }
+                },
+                required,
+            ));
+        }
     }
     // Sort the parameters so that optional parameters come last - Discord requires this order
     parameter_structs.sort_by_key(|(_, required)| !required);
@@ -140,39 +282,38 @@ pub fn generate_parameters(inv: &Invocation) -> Result<Vec<proc_macro2::TokenStr
 }
 
 pub fn generate_slash_action(inv: &Invocation) -> Result<proc_macro2::TokenStream, syn::Error> {
-    if let Some(desc) = &inv.description {
-        if desc.len() > 100 {
-            return Err(syn::Error::new(
-                inv.function.span(),
-                format!(
-                    "slash command description too long ({} chars, must be max 100)",
-                    desc.len()
-                ),
-            ));
-        }
-    }
+    super::validation::validate_slash_names(inv)?;
 
     let param_identifiers = (0..inv.parameters.len())
         .map(|i| format_ident!("poise_param_{i}"))
         .collect::<Vec<_>>();
     let param_names = inv.parameters.iter().map(|p| &p.name).collect::<Vec<_>>();
+    let param_checks =
+        super::validation::generate_parameter_checks(inv, &param_identifiers, false)?;
 
     let param_types = inv
         .parameters
         .iter()
         .map(|p| {
             let t = &p.type_;
-            if p.args.flag {
+            Ok(if p.args.flag {
                 quote::quote! { FLAG }
+            } else if is_attachment_vec(t) {
+                let option_names = slash_option_slots(p)?
+                    .into_iter()
+                    .map(|slot| format!("{}{}", p.name, slot.name_suffix));
+                quote::quote! { ATTACHMENTS [#(#option_names),*] }
+            } else if p.args.list {
+                quote::quote! { LIST #t }
             } else if let Some(choices) = &p.args.choices {
                 let choice_indices = (0..choices.0.len()).map(syn::Index::from);
                 let choice_vals = &choices.0;
                 quote::quote! { INLINE_CHOICE #t [#(#choice_indices: #choice_vals),*] }
             } else {
                 quote::quote! { #t }
-            }
+            })
         })
-        .collect::<Vec<_>>();
+        .collect::<Result<Vec<_>, syn::Error>>()?;
 
     Ok(quote::quote! {
         |ctx| Box::pin(async move {
@@ -184,6 +325,7 @@ pub fn generate_slash_action(inv: &Invocation) -> Result<proc_macro2::TokenStrea
                 ctx.serenity_context, ctx.interaction, ctx.args =>
                 #( (#param_names: #param_types), )*
             ).await.map_err(|error| error.to_framework_error(ctx))?;
+            #param_checks
 
             if !ctx.framework.options.manual_cooldowns {
                 ctx.command.cooldowns.lock().unwrap().start_cooldown(ctx.cooldown_context());
@@ -0,0 +1,273 @@
+use super::Invocation;
+use crate::util::extract_type_parameter;
+
+/// Wraps a check on `value` so that it runs on the inner value(s) of `Option` and `Vec` parameters
+fn for_each_value(
+    param: &super::CommandParameter,
+    param_ident: &syn::Ident,
+    check: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    if extract_type_parameter("Option", &param.type_).is_some() {
+        quote::quote! { if let Some(value) = &#param_ident { #check } }
+    } else if extract_type_parameter("Vec", &param.type_).is_some() {
+        quote::quote! { for value in &#param_ident { #check } }
+    } else {
+        quote::quote! { { let value = &#param_ident; #check } }
+    }
+}
+
+/// Wraps an optional attribute literal into an `Option` expression cast to the given type
+fn wrap_bound(
+    bound: Option<&syn::Lit>,
+    type_: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    match bound {
+        Some(bound) => quote::quote! { Some(#bound as #type_) },
+        None => quote::quote! { None },
+    }
+}
+
+/// Wraps an optional `#[min]` or `#[max]` literal into an `Option<poise::NumberBound>` expression.
+/// Integer literals stay integers, so that they're compared exactly with integer parameters
+fn wrap_number_bound(bound: Option<&syn::Lit>) -> proc_macro2::TokenStream {
+    match bound {
+        Some(bound @ syn::Lit::Int(_)) => {
+            quote::quote! { Some(::poise::NumberBound::Int(#bound as i128)) }
+        }
+        Some(bound) => quote::quote! { Some(::poise::NumberBound::Float(#bound as f64)) },
+        None => quote::quote! { None },
+    }
+}
+
+/// Parses a file size like `8MB`, `512 KB` or `1.5GiB` into bytes. Units are binary, i.e. `1KB` is
+/// 1024 bytes, like in Discord's upload limits.
+fn parse_size(size: &syn::LitStr) -> Result<u64, syn::Error> {
+    let value = size.value();
+    let number_end = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(number_end);
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "kb" | "kib" => 1 << 10,
+        "mb" | "mib" => 1 << 20,
+        "gb" | "gib" => 1 << 30,
+        _ => {
+            return Err(syn::Error::new(
+                size.span(),
+                "unknown size unit, expected B, KB, MB or GB",
+            ))
+        }
+    };
+    match number.parse::<f64>() {
+        Ok(number) if number >= 0.0 => Ok((number * multiplier as f64) as u64),
+        _ => Err(syn::Error::new(
+            size.span(),
+            "expected a size like \"8MB\" or \"512KB\"",
+        )),
+    }
+}
+
+/// Returns whether the string is a valid slash command or option name: 1-32 letters, digits, `-`
+/// or `_`, without uppercase letters. Mirrors the runtime check in `poise::builtins`
+fn is_valid_slash_name(name: &str) -> bool {
+    (1..=32).contains(&name.chars().count())
+        && name
+            .chars()
+            .all(|c| (c.is_alphanumeric() && !c.is_uppercase()) || c == '-' || c == '_')
+}
+
+/// Checks a slash command or option name against Discord's rules
+fn check_slash_name(name: &str, what: &str, span: proc_macro2::Span) -> Result<(), syn::Error> {
+    if is_valid_slash_name(name) {
+        return Ok(());
+    }
+    Err(syn::Error::new(
+        span,
+        format!(
+            "invalid slash {} name `{}`: must be 1-32 lowercase letters, digits, `-` or `_`",
+            what, name
+        ),
+    ))
+}
+
+/// Checks a slash command or option description against Discord's length limit
+fn check_description(
+    description: &str,
+    what: &str,
+    span: proc_macro2::Span,
+) -> Result<(), syn::Error> {
+    let length = description.chars().count();
+    if (1..=100).contains(&length) {
+        return Ok(());
+    }
+    Err(syn::Error::new(
+        span,
+        format!(
+            "slash {} description must be 1-100 characters, got {}",
+            what, length
+        ),
+    ))
+}
+
+/// Checks the names and descriptions of a slash command and its options, including their
+/// localizations, against Discord's rules. Without this, violations only show up as a failed
+/// command registration at runtime, without pointing to the offending command
+pub fn validate_slash_names(inv: &Invocation) -> Result<(), syn::Error> {
+    let span = inv.function.sig.ident.span();
+    let function_name = inv.function.sig.ident.to_string();
+    let command_name = inv
+        .args
+        .rename
+        .clone()
+        .unwrap_or_else(|| function_name.trim_start_matches("r#").to_owned());
+    check_slash_name(&command_name, "command", span)?;
+    for crate::util::Tuple2(_, name) in &inv.args.name_localized {
+        check_slash_name(name, "command", span)?;
+    }
+    if let Some(description) = &inv.description {
+        check_description(description, "command", span)?;
+    }
+    for crate::util::Tuple2(_, description) in &inv.args.description_localized {
+        check_description(description, "command", span)?;
+    }
+
+    for param in &inv.parameters {
+        for slot in super::slash::slash_option_slots(param)? {
+            let names =
+                std::iter::once(&param.name).chain(param.args.name_localized.iter().map(|x| &x.1));
+            for name in names {
+                check_slash_name(
+                    &format!("{}{}", name, slot.name_suffix),
+                    "option",
+                    param.span,
+                )?;
+            }
+            let descriptions = param
+                .args
+                .description
+                .iter()
+                .chain(param.args.description_localized.iter().map(|x| &x.1));
+            for description in descriptions {
+                let description = format!("{}{}", description, slot.description_suffix);
+                check_description(&description, "option", param.span)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Generates the statement that validates the already parsed parameters against their attributes
+/// (like `#[regex]` or `#[validate]`). Returns an empty token stream if there's nothing to validate.
+///
+/// `#[min]`, `#[max]`, `#[min_length]`, `#[max_length]` and `#[choices_fn]` are only checked if
+/// `enforce_bounds` is set or for `Vec<T>` parameters, because Discord already enforces them for
+/// other slash command parameters.
+pub fn generate_parameter_checks(
+    inv: &Invocation,
+    param_idents: &[syn::Ident],
+    enforce_bounds: bool,
+) -> Result<proc_macro2::TokenStream, syn::Error> {
+    let mut checks = Vec::new();
+    for (param, param_ident) in inv.parameters.iter().zip(param_idents) {
+        // Discord can't check the elements of lists, which are a single string option in slash
+        // commands
+        let enforce_bounds = enforce_bounds || param.args.list;
+        if enforce_bounds && (param.args.min.is_some() || param.args.max.is_some()) {
+            let min = wrap_number_bound(param.args.min.as_ref());
+            let max = wrap_number_bound(param.args.max.as_ref());
+            checks.push(for_each_value(
+                param,
+                param_ident,
+                quote::quote! {
+                    ::poise::validate_number_range(*value, #min, #max)?;
+                },
+            ));
+        }
+        if enforce_bounds && (param.args.min_length.is_some() || param.args.max_length.is_some()) {
+            let min_length = wrap_bound(param.args.min_length.as_ref(), quote::quote! { usize });
+            let max_length = wrap_bound(param.args.max_length.as_ref(), quote::quote! { usize });
+            checks.push(for_each_value(
+                param,
+                param_ident,
+                quote::quote! {
+                    ::poise::validate_length_range(
+                        ::std::convert::AsRef::<str>::as_ref(value),
+                        #min_length,
+                        #max_length,
+                    )?;
+                },
+            ));
+        }
+        if let Some(choices_fn) = param.args.choices_fn.as_ref().filter(|_| enforce_bounds) {
+            checks.push(for_each_value(
+                param,
+                param_ident,
+                quote::quote! {
+                    ::poise::validate_dynamic_choice(
+                        ::std::convert::AsRef::<str>::as_ref(value),
+                        #choices_fn,
+                    )?;
+                },
+            ));
+        }
+        if let Some(pattern) = &param.args.regex {
+            if let Err(e) = regex::Regex::new(&pattern.value()) {
+                return Err(syn::Error::new(pattern.span(), e));
+            }
+            let check = for_each_value(
+                param,
+                param_ident,
+                quote::quote! {
+                    POISE_PATTERN.validate(::std::convert::AsRef::<str>::as_ref(value))?;
+                },
+            );
+            checks.push(quote::quote! { {
+                static POISE_PATTERN: ::poise::LazyPattern = ::poise::LazyPattern::new(#pattern);
+                #check
+            } });
+        }
+        if let Some(content_type) = &param.args.content_type {
+            checks.push(for_each_value(
+                param,
+                param_ident,
+                quote::quote! {
+                    ::poise::validate_content_type(value, #content_type)?;
+                },
+            ));
+        }
+        if let Some(max_size) = &param.args.max_size {
+            let max_size = parse_size(max_size)?;
+            checks.push(for_each_value(
+                param,
+                param_ident,
+                quote::quote! {
+                    ::poise::validate_attachment_size(value, #max_size)?;
+                },
+            ));
+        }
+        if let Some(validate) = &param.args.validate {
+            checks.push(for_each_value(
+                param,
+                param_ident,
+                quote::quote! {
+                    ::poise::run_validator(#validate(ctx.into(), value)).await?;
+                },
+            ));
+        }
+    }
+
+    if checks.is_empty() {
+        return Ok(quote::quote! {});
+    }
+    Ok(quote::quote! {
+        async {
+            #( #checks )*
+            Ok::<_, (Box<dyn std::error::Error + Send + Sync>, Option<String>)>(())
+        }.await.map_err(|(error, input)| poise::FrameworkError::new_argument_parse(
+            ctx.into(),
+            input,
+            error,
+        ))?;
+    })
+}
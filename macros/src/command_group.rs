@@ -0,0 +1,104 @@
+//! Implements the `#[poise::command_group]` attribute macro, which turns a module of commands into
+//! a parent command with the module's commands as subcommands
+
+use syn::spanned::Spanned as _;
+
+/// Returns whether the attribute is `#[name]` or a path ending in it, like `#[poise::name]`
+fn is_attribute(attr: &syn::Attribute, name: &str) -> bool {
+    attr.path()
+        .segments
+        .last()
+        .is_some_and(|segment| segment.ident == name)
+}
+
+pub fn command_group(
+    args: Vec<darling::ast::NestedMeta>,
+    mut module: syn::ItemMod,
+) -> Result<proc_macro2::TokenStream, darling::Error> {
+    if let Some(arg) = args.iter().find(|arg| match arg {
+        darling::ast::NestedMeta::Meta(meta) => meta.path().is_ident("subcommands"),
+        _ => false,
+    }) {
+        let err_msg = "the subcommands of a command group are the commands in its module";
+        return Err(syn::Error::new(arg.span(), err_msg).into());
+    }
+
+    let module_span = module.span();
+    let Some((_, items)) = &mut module.content else {
+        let err_msg = "command group must be an inline module: `mod name { ... }`";
+        return Err(syn::Error::new(module_span, err_msg).into());
+    };
+
+    // Commands and nested command groups in source order. The first command provides the Context
+    // and return type for the parent command
+    let mut subcommands = Vec::new();
+    let mut signature = None;
+    for item in items.iter() {
+        match item {
+            syn::Item::Fn(function)
+                if function
+                    .attrs
+                    .iter()
+                    .any(|attr| is_attribute(attr, "command")) =>
+            {
+                if signature.is_none() {
+                    signature = Some(function.sig.clone());
+                }
+                subcommands.push(function.sig.ident.to_string());
+            }
+            syn::Item::Mod(module)
+                if module
+                    .attrs
+                    .iter()
+                    .any(|attr| is_attribute(attr, "command_group")) =>
+            {
+                subcommands.push(module.ident.to_string());
+            }
+            _ => {}
+        }
+    }
+    let Some(signature) = signature else {
+        let err_msg = "command group must contain at least one `#[poise::command]` function";
+        return Err(syn::Error::new(module_span, err_msg).into());
+    };
+    let ctx_type = match signature.inputs.first() {
+        Some(syn::FnArg::Typed(syn::PatType { ty, .. })) => (**ty).clone(),
+        _ => return Err(syn::Error::new(signature.span(), "expected a Context parameter").into()),
+    };
+    let ctx_type_with_static =
+        syn::fold::fold_type(&mut crate::util::AllLifetimesToStatic, ctx_type.clone());
+    let output = &signature.output;
+
+    // The module's doc comments become the description and help text of the parent command
+    let docs = module
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"));
+    let ident = &module.ident;
+    let parent: syn::ItemFn = syn::parse_quote! {
+        #( #docs )*
+        #[poise::command( #( #args, )* subcommands( #( #subcommands ),* ) )]
+        pub async fn #ident(_: #ctx_type) #output {
+            Ok(())
+        }
+    };
+    let constructor: syn::ItemFn = syn::parse_quote! {
+        #[doc(hidden)]
+        pub fn __command_group() -> ::poise::Command<
+            <#ctx_type_with_static as poise::_GetGenerics>::U,
+            <#ctx_type_with_static as poise::_GetGenerics>::E,
+        > {
+            let mut command = #ident();
+            command.share_with_subcommands();
+            command
+        }
+    };
+    items.push(parent.into());
+    items.push(constructor.into());
+
+    let vis = &module.vis;
+    Ok(quote::quote! {
+        #module
+        #vis use #ident::__command_group as #ident;
+    })
+}
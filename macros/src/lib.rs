@@ -4,6 +4,7 @@ Procedural macros used in poise, like [`macro@command`]
 
 mod choice_parameter;
 mod command;
+mod command_group;
 mod modal;
 mod util;
 
@@ -24,6 +25,7 @@ for example for command-specific help (i.e. `~help command_name`). Escape newlin
 
 - `prefix_command`: Generate a prefix command
 - `slash_command`: Generate a slash command
+    - Command and parameter names and descriptions, including localizations, are checked against Discord's rules at compile time
 - `context_menu_command`: Generate a context menu command
 
 ## Meta properties
@@ -41,6 +43,7 @@ for example for command-specific help (i.e. `~help command_name`). Escape newlin
 ## Checks
 
 - `required_permissions`: Permissions which the command caller needs to have
+    - Permissions are given by name, separated by `|`: `required_permissions = "MANAGE_MESSAGES | KICK_MEMBERS"`. Names are case-insensitive and may use spaces instead of underscores (`"manage messages"`). Misspelled names are reported at compile time. The same syntax applies to `required_bot_permissions` and `default_member_permissions`
 - `required_bot_permissions`: Permissions which the bot is known to need
 - `default_member_permissions`: Like `required_permissions`, but checked server-side (slash only)
     - Due to being checked server-side, users without the required permissions are prevented from executing the command in the first place, which is a better experience
@@ -52,6 +55,7 @@ for example for command-specific help (i.e. `~help command_name`). Escape newlin
 - `nsfw_only`: Restricts command callers to only run on a NSFW channel
 - `subcommand_required`: Requires a subcommand to be specified (prefix only)
 - `check`: Path to a function which is invoked for every invocation. If the function returns false, the command is not executed (can be used multiple times)
+    - A message for users who fail the check can be added with `check = "is_mod: You must be a moderator to use this"` or `check(is_mod, "You must be a moderator to use this")`. The default error handler responds with it
 
 ## Help-related arguments
 
@@ -63,8 +67,13 @@ for example for command-specific help (i.e. `~help command_name`). Escape newlin
 
 - `track_edits`: Shorthand for `invoke_on_edit`, `track_deletion`, and `reuse_response` (prefix only)
 - `invoke_on_edit`: Reruns the command if an existing invocation message is edited (prefix only)
-- `track_deletion`: Deletes the bot response to a command if the command message is deleted (prefix only)
+- `track_deletion`: Deletes the bot responses to a command if the command message is deleted (prefix only)
 - `reuse_response`: After the first response, post subsequent responses as edits to the initial message (prefix only)
+- `edit_tracking_window`: Duration in seconds after sending the command message in which edits rerun the command, if shorter than the edit tracker's duration (prefix only)
+
+These can be combined to pick a policy per command. For example, `invoke_on_edit, reuse_response`
+tracks edits but leaves the response when the command message is deleted, and `invoke_on_edit`
+alone sends a fresh response for every edit instead of editing the previous one.
 
 ## Cooldown
 
@@ -73,14 +82,23 @@ for example for command-specific help (i.e. `~help command_name`). Escape newlin
 - `guild_cooldown`: Minimum duration in seconds between invocations, per guild
 - `channel_cooldown`: Minimum duration in seconds between invocations, per channel
 - `member_cooldown`: Minimum duration in seconds between invocations, per guild member
+- `max_concurrent`: Maximum number of invocations that may run at the same time, globally
+- `user_max_concurrent`: Maximum number of invocations that may run at the same time, per user
+- `guild_max_concurrent`: Maximum number of invocations that may run at the same time, per guild
 
 ## Other
 
 - `on_error`: Error handling function
+- `on_parse_error`: Error handling function for argument parse errors, takes precedence over `on_error`
+- `on_check_failure`: Error handling function for failed `check`s, takes precedence over `on_error`
 - `broadcast_typing`: Trigger a typing indicator while command runs (prefix only)
 - `discard_spare_arguments`: Don't throw an error if the user supplies too many arguments (prefix only)
+- `named_arguments`: Additionally accept arguments as `name=value` or `name="multi word value"` in any order (prefix only)
+    - If the invocation contains at least one such argument, every parameter is read from its named value, and parameters that weren't named are treated as missing. Otherwise, arguments are parsed positionally as usual
 - `ephemeral`: Make bot responses ephemeral if possible (slash only)
     - Only poise's functions, like `poise::send_reply`, respect this preference
+- `auto_defer`: Defer the response automatically if the command hasn't responded after this many milliseconds, e.g. `auto_defer = 2000` (slash only)
+    - Overrides `FrameworkOptions::auto_defer`. The deferred response is ephemeral if `ephemeral` is set
 
 # Function parameters
 
@@ -89,30 +107,60 @@ SlashContext, which contain a variety of context data each. Context provides som
 access data present in both PrefixContext and SlashContext, like `author()` or `created_at()`.
 
 All following parameters are inputs to the command. You can use all types that implement `poise::PopArgument`, `serenity::ArgumentConvert` or `std::str::FromStr`.
-You can also wrap types in `Option` or `Vec` to make them optional or variadic. In addition, there
-are multiple attributes you can use on parameters:
+You can also wrap types in `Option` or `Vec` to make them optional or variadic. Slash commands
+don't support variadic options, so there, a `Vec<T>` parameter is a single optional `T` option
+unless it's marked with `#[list]` (see below). `Vec<Attachment>` parameters collect all attachments of the message in
+prefix commands; in slash commands, they're turned into five optional attachment options (see
+`#[max_attachments]`). For a parameter named `files`, the options are named `files`, `files_2`,
+`files_3` and so on, and their descriptions get a ` (2)`, ` (3)`, ... suffix. Localized names and
+descriptions get the same suffixes; with `poise::i18n`, each option can also be translated
+individually under its own name.
+In addition, there are multiple attributes you can use on parameters:
 
 ## Meta properties
 
 - `#[description = ""]`: Sets description of the parameter (slash-only)
 - `#[description_localized("locale", "Description")]`: Adds localized description of the parameter (slash-only)
 - `#[name_localized("locale", "new_name")]`: Adds localized name of the parameter (slash-only)
-- `#[autocomplete = "callback()"]`: Sets the autocomplete callback (slash-only)
+- `#[autocomplete = "callback()"]`: Sets the autocomplete callback (slash-only). Some parameter types, like `poise::Timezone`, have autocomplete built in, which this overrides. Takes a path to a function, which may be an associated function like `Fruit::autocomplete`, or an inline closure that receives a `poise::Context` and the partial input: `#[autocomplete = |_ctx, partial| async move { FRUITS.iter().filter(move |f| f.starts_with(partial)) }]`
+- `#[type_autocomplete]`: Uses the autocomplete suggestions of the parameter type, i.e. `poise::SlashArgument::autocomplete` (slash-only). Only needed for your own types; poise's types with built-in autocomplete, like `poise::Timezone`, are detected automatically
+- `#[choices_fn = "callback"]`: Path to a `fn() -> Vec<poise::CommandParameterChoice>` which supplies the choices for a string parameter when the command is registered, e.g. from a config file. Prefix commands reject input that isn't one of the choice names
 - `#[rename = "new_name"]`: Changes the user-facing name of the parameter (slash-only)
 
-## Input filter (slash only)
+## Input filter
 
 - `#[channel_types("", "")]`: For channel parameters, restricts allowed channel types (slash-only)
-- `#[min = 0]`: Minimum value for this number parameter (slash-only)
-- `#[max = 0]`: Maximum value for this number parameter (slash-only)
-- `#[min_length = 0]`: Minimum length for this string parameter (slash-only)
-- `#[max_length = 1]`: Maximum length for this string parameter (slash-only)
+- `#[min = 0]`: Minimum value for this number parameter
+- `#[max = 0]`: Maximum value for this number parameter
+- `#[min_length = 0]`: Minimum length for this string parameter
+- `#[max_length = 1]`: Maximum length for this string parameter
+- `#[list]`: Turns a `Vec<T>` parameter into an optional string option in slash commands, into which users type a list separated by commas or spaces (`1, 2, 3` or `1 2 3`). Each element is parsed as `T`, and `#[min]`, `#[max]` etc. are checked for each element (slash-only)
+- `#[max_attachments = 5]`: Number of attachment options that a `Vec<Attachment>` parameter is turned into in slash commands, between 1 and 25 (slash-only)
+
+For slash commands, the bounds are enforced by Discord. For prefix commands, poise checks them after
+parsing and raises `FrameworkError::ArgumentParse` with a `poise::NumberOutOfRange` or
+`poise::LengthOutOfRange` error.
+
+## Validation
+
+- `#[regex = "^[a-z0-9_]+$"]`: Rejects string parameters that don't match the regular expression
+    - The pattern is checked for validity at compile time. It's not anchored implicitly, so use `^` and `$` to match the whole input
+    - Unicode-aware `\d`, `\w`, `\s` and case-insensitive matching (`(?i)`) are supported. Other Unicode classes like `\p{Greek}` aren't, because poise enables only the `unicode-perl` and `unicode-case` features of the `regex` crate
+    - On mismatch, a `FrameworkError::ArgumentParse` with a `poise::PatternMismatch` error is raised
+- `#[content_type = "image/png"]`: Rejects attachments whose MIME type doesn't match. Accepts exact types (`image/png`), wildcard subtypes (image/&#42; for all images) and comma-separated lists of them
+- `#[max_size = "8MB"]`: Rejects attachments larger than the given size. Units are B, KB, MB and GB, counted in powers of 1024 like Discord's upload limit
+    - On mismatch, a `FrameworkError::ArgumentParse` with a `poise::InvalidContentType` or `poise::AttachmentTooLarge` error is raised
+- `#[validate = "callback"]`: Path to an async function `(Context<'_, U, E>, &T) -> Result<(), M>` that checks the parsed value, where `M` is a user-facing error message (anything `Display`)
+    - Runs for both prefix and slash invocations, with `T` being the inner type for `Option<T>` and `Vec<T>` parameters
+    - On `Err`, a `FrameworkError::ArgumentParse` with a `poise::ValidationFailed` error is raised
 
 ## Parser settings (prefix only)
 - `#[rest]`: Use the entire rest of the message for this parameter (prefix-only)
 - `#[lazy]`: Can be used on Option and Vec parameters and is equivalent to regular expressions' laziness (prefix-only)
-- `#[flag]`: Can be used on a bool parameter to set the bool to true if the user typed the parameter name literally (prefix-only)
+- `#[flag]`: Can be used on a bool parameter to set the bool to true if the user typed the parameter name literally, or `--name` anywhere in the message (prefix-only)
     - For example with `async fn my_command(ctx: Context<'_>, #[flag] my_flag: bool)`, `~my_command` would set my_flag to false, and `~my_command my_flag` would set my_flag to true
+    - `--my_flag` (or `--my-flag`) may appear at any position, e.g. `~purge 50 --bots` for `async fn purge(ctx: Context<'_>, amount: u32, #[flag] bots: bool)`
+    - In slash commands, flags are optional bool options that default to false
 
 # Help text
 
@@ -201,6 +249,72 @@ pub fn command(args: TokenStream, function: TokenStream) -> TokenStream {
         Err(e) => e.write_errors().into(),
     }
 }
+/**
+Turns a module of commands into a command group: a parent command whose subcommands are the
+`#[poise::command]` functions in the module, without writing a stub parent function.
+
+The macro arguments are the same as for [`macro@command`], except for `subcommands`. They configure
+the parent command, whose name is the module name and whose description comes from the module's
+doc comments. The parent's `category` and cooldowns are copied to all subcommands that don't set
+their own, and the parent's checks (`check`, `required_permissions`, `owners_only`, ...) run before
+every subcommand. Command groups can be nested.
+
+The parent command is available as a function with the module's name, next to the module:
+
+```rust
+# type Error = Box<dyn std::error::Error + Send + Sync>;
+# type Context<'a> = poise::Context<'a, (), Error>;
+# async fn is_moderator(ctx: Context<'_>) -> Result<bool, Error> { Ok(true) }
+/// Moderation commands
+#[poise::command_group(
+    slash_command,
+    prefix_command,
+    category = "Moderation",
+    check = "is_moderator",
+    user_cooldown = 5
+)]
+mod moderation {
+    use super::*;
+
+    /// Warns a user
+    #[poise::command(slash_command, prefix_command)]
+    pub async fn warn(ctx: Context<'_>, user: poise::serenity_prelude::User) -> Result<(), Error> {
+        ctx.say(format!("{} has been warned", user.name)).await?;
+        Ok(())
+    }
+
+    /// Locks the channel
+    #[poise::command(slash_command, prefix_command)]
+    pub async fn lock(ctx: Context<'_>) -> Result<(), Error> {
+        ctx.say("Locked").await?;
+        Ok(())
+    }
+}
+
+let command = moderation();
+assert_eq!(command.name, "moderation");
+assert_eq!(command.subcommands[0].category.as_deref(), Some("Moderation"));
+# let _: poise::Command<(), Error> = command;
+```
+
+The module must be inline and contain at least one command, whose `Context` and return type are
+used for the parent command.
+*/
+#[proc_macro_attribute]
+pub fn command_group(args: TokenStream, module: TokenStream) -> TokenStream {
+    let args = match darling::ast::NestedMeta::parse_meta_list(args.into()) {
+        Ok(x) => x,
+        Err(e) => return e.into_compile_error().into(),
+    };
+
+    let module = syn::parse_macro_input!(module as syn::ItemMod);
+
+    match command_group::command_group(args, module) {
+        Ok(x) => x.into(),
+        Err(e) => e.write_errors().into(),
+    }
+}
+
 /**
 Use this derive macro on an enum to easily generate a choice parameter type. A choice parameter
 is mainly useful in slash commands. It allows you to constrain input to a fixed set of choices.
@@ -224,6 +338,24 @@ Example invocations:
 - `~yourcommand ChoiceB`
 - `~yourcommand cHoIcEb` - names are case-insensitive
 
+Instead of naming every variant, you can derive the names from the variant names with
+`#[rename_all = "..."]` on the enum. Supported conventions are `lowercase`, `UPPERCASE`,
+`PascalCase`, `camelCase`, `snake_case`, `SCREAMING_SNAKE_CASE`, `kebab-case`,
+`SCREAMING-KEBAB-CASE`, `Title Case` and `lower case`. A `#[name]` on a variant takes precedence.
+
+```rust
+#[derive(poise::ChoiceParameter)]
+#[rename_all = "kebab-case"]
+pub enum Sorting {
+    // Shown as "most-recent"
+    MostRecent,
+    // Shown as "top-rated"
+    TopRated,
+    #[name = "random order"]
+    Random,
+}
+```
+
 # Localization
 
 In slash commands, you can take advantage of Discord's localization.
@@ -243,11 +375,36 @@ pub enum Food {
 }
 ```
 
-When invoking your slash command, users will be shown the name matching their locale.
+When invoking your slash command, users will be shown the name matching their locale. Prefix
+commands accept the localized names as well, so `~yourcommand eier` works just like
+`~yourcommand eggs`.
 
 You can also set localized choice names programmatically; see `CommandParameter::choices`
+
+# Choice values
+
+By default, Discord sends the index of the picked choice to the bot, which poise translates back to
+the enum variant. With `#[value = ...]`, you can set the value that's sent instead. The value
+doesn't affect the name shown in Discord, which is still set with `#[name]` and
+`#[name_localized]`.
+
+```rust
+#[derive(poise::ChoiceParameter)]
+pub enum Duration {
+    #[name = "One minute"]
+    #[value = 60]
+    Minute,
+    #[name = "One hour"]
+    #[value = 3600]
+    Hour,
+}
+```
+
+If one variant has a value, all variants need one. With integer values, the parameter is registered
+as integer option; if any value is a float, it's registered as number option. The value of a
+variant can be retrieved with `ChoiceParameter::value()`.
 */
-#[proc_macro_derive(ChoiceParameter, attributes(name, name_localized))]
+#[proc_macro_derive(ChoiceParameter, attributes(name, name_localized, value, rename_all))]
 pub fn choice_parameter(input: TokenStream) -> TokenStream {
     let enum_ = syn::parse_macro_input!(input as syn::DeriveInput);
 
@@ -267,7 +424,7 @@ pub fn slash_choice_parameter(input: TokenStream) -> TokenStream {
 /// See `Modal` trait documentation
 #[proc_macro_derive(
     Modal,
-    attributes(name, placeholder, min_length, max_length, paragraph)
+    attributes(name, placeholder, min_length, max_length, paragraph, validate)
 )]
 pub fn modal(input: TokenStream) -> TokenStream {
     let struct_ = syn::parse_macro_input!(input as syn::DeriveInput);
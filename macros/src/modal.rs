@@ -18,6 +18,7 @@ struct FieldAttributes {
     min_length: Option<u16>,
     max_length: Option<u16>,
     paragraph: Option<()>,
+    validate: Option<syn::Path>,
 }
 
 pub fn modal(input: syn::DeriveInput) -> Result<TokenStream, darling::Error> {
@@ -44,6 +45,7 @@ pub fn modal(input: syn::DeriveInput) -> Result<TokenStream, darling::Error> {
 
     let mut builders = Vec::new();
     let mut parsers = Vec::new();
+    let mut validators = Vec::new();
     for field in fields {
         // Extract data from syn::Field
         let field_attrs: Vec<_> = field
@@ -93,8 +95,37 @@ pub fn modal(input: syn::DeriveInput) -> Result<TokenStream, darling::Error> {
         parsers.push(quote::quote! {
             #field_ident: poise::find_modal_text(&mut data, stringify!(#field_ident)) #ok_or,
         });
+
+        // Create validation code for this field
+        if let Some(validate) = field_attrs.validate {
+            validators.push(quote::quote! {
+                // Only validate optional fields if they were filled in
+                if let Some(value) = Option::<&String>::from(&self.#field_ident) {
+                    if let Err(error) = #validate(value) {
+                        errors.push(format!("{}: {}", #label, error));
+                    }
+                }
+            });
+        }
     }
 
+    // Keep the trait's default implementation if there's nothing to validate
+    let validate_fn = if validators.is_empty() {
+        None
+    } else {
+        Some(quote::quote! {
+            fn validate(&self) -> ::std::result::Result<(), String> {
+                let mut errors = Vec::<String>::new();
+                #( #validators )*
+                if errors.is_empty() {
+                    Ok(())
+                } else {
+                    Err(errors.join("\n"))
+                }
+            }
+        })
+    };
+
     let modal_title = struct_attrs.name.unwrap_or(input.ident.to_string());
     let struct_ident = input.ident;
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
@@ -110,6 +141,8 @@ pub fn modal(input: syn::DeriveInput) -> Result<TokenStream, darling::Error> {
             fn parse(mut data: serenity::ModalInteractionData) -> ::std::result::Result<Self, &'static str> {
                 Ok(Self { #( #parsers )* })
             }
+
+            #validate_fn
         }
     }; }
     .into())
@@ -18,6 +18,38 @@ pub fn extract_type_parameter<'a>(outer_type: &str, t: &'a syn::Type) -> Option<
     None
 }
 
+/// Checks whether the type is of form `Vec<Attachment>` or `Vec<path::to::Attachment>`
+pub fn is_attachment_vec(t: &syn::Type) -> bool {
+    match extract_type_parameter("Vec", t) {
+        Some(syn::Type::Path(path)) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Attachment"),
+        _ => false,
+    }
+}
+
+/// Checks whether the type is a `Vec<T>` that slash commands can take as a single string option
+/// containing a list with `#[list]`, i.e. anything but `Vec<Attachment>`
+pub fn is_slash_list(t: &syn::Type) -> bool {
+    extract_type_parameter("Vec", t).is_some() && !is_attachment_vec(t)
+}
+
+/// Whether the type is one of poise's parameter types with built-in autocomplete, optionally
+/// wrapped in `Option`
+pub fn has_builtin_autocomplete(t: &syn::Type) -> bool {
+    let t = extract_type_parameter("Option", t).unwrap_or(t);
+    match t {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Timezone"),
+        _ => false,
+    }
+}
+
 /// Converts None => `None` and Some(x) => `Some(#x)`
 pub fn wrap_option<T: quote::ToTokens>(literal: Option<T>) -> syn::Expr {
     match literal {
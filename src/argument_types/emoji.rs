@@ -0,0 +1,250 @@
+//! Parsing code for emoji parameters that may be either unicode or custom emoji
+
+use crate::serenity_prelude as serenity;
+use crate::{PopArgument, SlashArgError, SlashArgument};
+
+/// Error thrown when an [`AnyEmoji`] parameter can't be parsed
+#[derive(Default, Debug, Clone)]
+pub struct EmojiParseError {
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+impl std::fmt::Display for EmojiParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Expected a unicode emoji like 👍, or a custom emoji of this server")
+    }
+}
+impl std::error::Error for EmojiParseError {}
+
+/// Command parameter type that accepts both unicode emoji and custom emoji, for example for
+/// reaction role or emoji management commands.
+///
+/// Accepted inputs are:
+/// - unicode emoji like `👍`, `👍🏽`, `🇩🇪` or `🏳️‍🌈`
+/// - custom emoji markup like `<:ferris:1234>` or `<a:ferris_dance:1234>`, which is what the
+///   Discord client inserts when picking a custom emoji. Emoji from other servers are accepted too
+/// - the name or ID of one of the server's custom emoji, like `ferris` or `:ferris:` (only with the
+///   `cache` feature)
+///
+/// Convert it into a [`serenity::ReactionType`] to react with it.
+#[derive(Debug, Clone)]
+pub enum AnyEmoji {
+    /// A unicode emoji, possibly consisting of multiple code points (skin tone modifiers, flags,
+    /// ZWJ sequences)
+    Unicode(String),
+    /// A custom emoji
+    #[non_exhaustive]
+    Custom {
+        /// ID of the custom emoji
+        id: serenity::EmojiId,
+        /// Name of the custom emoji
+        name: String,
+        /// Whether the custom emoji is animated
+        animated: bool,
+        /// Full emoji data, if the emoji belongs to the guild the command was invoked in and the
+        /// guild was found in the cache
+        guild_emoji: Option<Box<serenity::Emoji>>,
+    },
+}
+
+impl AnyEmoji {
+    /// Returns the custom emoji ID, or `None` if this is a unicode emoji
+    pub fn custom_id(&self) -> Option<serenity::EmojiId> {
+        match self {
+            Self::Unicode(_) => None,
+            Self::Custom { id, .. } => Some(*id),
+        }
+    }
+}
+
+impl PartialEq for AnyEmoji {
+    /// Unicode emoji are compared by their text, custom emoji by their ID
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Unicode(a), Self::Unicode(b)) => a == b,
+            (Self::Custom { id: a, .. }, Self::Custom { id: b, .. }) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for AnyEmoji {
+    /// Formats the emoji such that it renders as emoji in a Discord message
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Unicode(emoji) => f.write_str(emoji),
+            Self::Custom {
+                id, name, animated, ..
+            } => {
+                let prefix = if *animated { "a" } else { "" };
+                write!(f, "<{}:{}:{}>", prefix, name, id)
+            }
+        }
+    }
+}
+
+impl From<AnyEmoji> for serenity::ReactionType {
+    fn from(emoji: AnyEmoji) -> Self {
+        match emoji {
+            AnyEmoji::Unicode(emoji) => serenity::ReactionType::Unicode(emoji),
+            AnyEmoji::Custom {
+                id, name, animated, ..
+            } => serenity::ReactionType::Custom {
+                animated,
+                id,
+                name: Some(name),
+            },
+        }
+    }
+}
+
+/// Whether the code point is a pictographic character that can stand on its own as emoji
+fn is_pictographic(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x1F000..=0x1FAFF // Most emoji, including regional indicators and skin tone modifiers
+            | 0x2600..=0x27BF // Miscellaneous symbols and dingbats
+            | 0x2300..=0x23FF // Miscellaneous technical (⌚, ⏰)
+            | 0x2B00..=0x2BFF // Arrows and shapes (⬆, ⭐)
+            | 0x2190..=0x21FF // Arrows
+            | 0x2900..=0x297F // Supplemental arrows
+            | 0x25A0..=0x25FF // Geometric shapes (▶, ◻)
+            | 0x3030 | 0x303D | 0x3297 | 0x3299
+            | 0x00A9 | 0x00AE | 0x203C | 0x2049 | 0x2122 | 0x2139 | 0x24C2
+    )
+}
+
+/// Checks whether the input consists solely of a single unicode emoji (sequence). This is a
+/// heuristic based on code point ranges rather than a full implementation of the emoji spec, so it
+/// errs on the side of accepting.
+fn is_unicode_emoji(input: &str) -> bool {
+    let is_keycap = input.ends_with('\u{20E3}');
+    let mut has_pictographic = is_keycap;
+    let all_valid = input.chars().all(|c| {
+        if is_pictographic(c) {
+            has_pictographic = true;
+            return true;
+        }
+        match c {
+            // Zero width joiner and variation selectors
+            '\u{200D}' | '\u{FE0E}' | '\u{FE0F}' => true,
+            // Tag sequences, used for subdivision flags like 🏴󠁧󠁢󠁥󠁮󠁧󠁿
+            '\u{E0020}'..='\u{E007F}' => true,
+            // Keycaps like 1️⃣
+            '\u{20E3}' => true,
+            '0'..='9' | '#' | '*' => is_keycap,
+            _ => false,
+        }
+    });
+    all_valid && has_pictographic && input.chars().count() <= 16
+}
+
+/// Parses an emoji from user input, looking up custom emoji in the given guild's cache
+fn parse_emoji(
+    input: &str,
+    ctx: &serenity::Context,
+    guild_id: Option<serenity::GuildId>,
+) -> Result<AnyEmoji, EmojiParseError> {
+    let input = input.trim();
+    if is_unicode_emoji(input) {
+        return Ok(AnyEmoji::Unicode(input.to_owned()));
+    }
+
+    #[cfg(feature = "cache")]
+    let guild_emoji = |check: &dyn Fn(&serenity::Emoji) -> bool| {
+        let guild = ctx.cache.guild(guild_id?)?;
+        let emoji = guild.emojis.values().find(|&emoji| check(emoji))?;
+        Some(Box::new(emoji.clone()))
+    };
+    #[cfg(not(feature = "cache"))]
+    let guild_emoji = |_: &dyn Fn(&serenity::Emoji) -> bool| {
+        let _ = (ctx, guild_id);
+        None::<Box<serenity::Emoji>>
+    };
+
+    if let Some(emoji) = serenity::parse_emoji(input) {
+        return Ok(AnyEmoji::Custom {
+            guild_emoji: guild_emoji(&|e| e.id == emoji.id),
+            id: emoji.id,
+            name: emoji.name,
+            animated: emoji.animated,
+        });
+    }
+
+    // Bare name or ID of one of the guild's emoji
+    let name = input.trim_matches(':');
+    let id = input.parse::<serenity::EmojiId>().ok();
+    let emoji = guild_emoji(&|e| Some(e.id) == id || e.name.eq_ignore_ascii_case(name))
+        .ok_or_else(EmojiParseError::default)?;
+    Ok(AnyEmoji::Custom {
+        id: emoji.id,
+        name: emoji.name.clone(),
+        animated: emoji.animated,
+        guild_emoji: Some(emoji),
+    })
+}
+
+#[async_trait::async_trait]
+impl<'a> PopArgument<'a> for AnyEmoji {
+    async fn pop_from(
+        args: &'a str,
+        attachment_index: usize,
+        ctx: &serenity::Context,
+        msg: &serenity::Message,
+    ) -> Result<(&'a str, usize, Self), (Box<dyn std::error::Error + Send + Sync>, Option<String>)>
+    {
+        let (args, input) = crate::prefix_argument::pop_string(args)
+            .map_err(|_| (crate::TooFewArguments::default().into(), None))?;
+        match parse_emoji(&input, ctx, msg.guild_id) {
+            Ok(emoji) => Ok((args.trim_start(), attachment_index, emoji)),
+            Err(e) => Err((e.into(), Some(input.into_owned()))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SlashArgument for AnyEmoji {
+    async fn extract(
+        ctx: &serenity::Context,
+        interaction: &serenity::CommandInteraction,
+        value: &serenity::ResolvedValue<'_>,
+    ) -> Result<Self, SlashArgError> {
+        match *value {
+            serenity::ResolvedValue::String(input) => parse_emoji(input, ctx, interaction.guild_id)
+                .map_err(|e| SlashArgError::Parse {
+                    error: e.into(),
+                    input: input.into(),
+                }),
+            _ => Err(SlashArgError::CommandStructureMismatch {
+                description: "expected string",
+            }),
+        }
+    }
+
+    fn create(builder: serenity::CreateCommandOption) -> serenity::CreateCommandOption {
+        builder
+            .kind(serenity::CommandOptionType::String)
+            .max_length(100)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_is_unicode_emoji() {
+    for emoji in ["👍", "👍🏽", "🇩🇪", "🏳️‍🌈", "👨‍👩‍👧", "❤️", "⭐", "1️⃣", "#️⃣", "🏴󠁧󠁢󠁥󠁮󠁧󠁿"]
+    {
+        assert!(is_unicode_emoji(emoji), "{emoji}");
+    }
+    for not_emoji in [
+        "",
+        "a",
+        "1",
+        "ferris",
+        ":ferris:",
+        "👍 x",
+        "<:ferris:1234>",
+        "\u{200D}",
+    ] {
+        assert!(!is_unicode_emoji(not_emoji), "{not_emoji}");
+    }
+}
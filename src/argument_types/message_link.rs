@@ -0,0 +1,242 @@
+//! Parsing code for links to Discord messages, which are resolved to the linked message
+
+use crate::serenity_prelude as serenity;
+use crate::{PopArgument, SlashArgError, SlashArgument};
+
+/// Error thrown when a [`MessageLink`] parameter can't be resolved. Its [`Display`] implementation
+/// explains what went wrong, so it can be shown to the user as-is.
+///
+/// [`Display`]: std::fmt::Display
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum MessageLinkError {
+    /// The input is neither a message link nor a channel and message ID pair
+    Malformed,
+    /// The message is in a different server than the one the command was invoked in, or in a
+    /// private channel other than the current one
+    Inaccessible,
+    /// Fetching the channel or message from Discord failed, e.g. because it doesn't exist or the
+    /// bot can't see it
+    Fetch(serenity::Error),
+}
+impl std::fmt::Display for MessageLinkError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Malformed => f.write_str(
+                "Expected a message link like `https://discord.com/channels/123/456/789`, or a \
+                channel and message ID like `456/789`",
+            ),
+            Self::Inaccessible => {
+                f.write_str("The linked message is in another server or a private channel")
+            }
+            Self::Fetch(error) => write!(
+                f,
+                "Couldn't fetch the linked message (does it exist, and can the bot see the \
+                channel?): {error}"
+            ),
+        }
+    }
+}
+impl std::error::Error for MessageLinkError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Fetch(error) => Some(error),
+            Self::Malformed | Self::Inaccessible => None,
+        }
+    }
+}
+
+/// Command parameter type for commands that operate on another message, like quote, report or pin
+/// commands. The message is fetched during argument parsing.
+///
+/// Accepted inputs are message links (`https://discord.com/channels/123/456/789`, as copied with
+/// "Copy Message Link") and channel and message ID pairs (`456/789` or `456-789`, as copied by
+/// shift-clicking "Copy Message ID").
+///
+/// For privacy, only messages from the server the command was invoked in are accepted. In DMs,
+/// only messages from the same DM channel are accepted.
+#[derive(Debug, Clone)]
+pub struct MessageLink {
+    /// The linked message
+    ///
+    /// Unlike messages received over the gateway, messages fetched over HTTP don't carry their guild
+    /// ID, so poise fills in [`serenity::Message::guild_id`] itself.
+    pub message: serenity::Message,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl std::ops::Deref for MessageLink {
+    type Target = serenity::Message;
+
+    fn deref(&self) -> &Self::Target {
+        &self.message
+    }
+}
+
+/// Splits a message link or channel and message ID pair into its IDs. The guild ID is only known
+/// for links to guild messages.
+fn parse_message_link(
+    input: &str,
+) -> Option<(
+    Option<serenity::GuildId>,
+    serenity::ChannelId,
+    serenity::MessageId,
+)> {
+    let input = input.trim().trim_start_matches('<').trim_end_matches('>');
+    if let Some(path) = input
+        .strip_prefix("https://")
+        .or_else(|| input.strip_prefix("http://"))
+    {
+        let (host, path) = path.split_once('/')?;
+        let is_discord_host = ["discord.com", "discordapp.com"]
+            .iter()
+            .any(|domain| host == *domain || host.ends_with(&format!(".{domain}")));
+        if !is_discord_host {
+            return None;
+        }
+        // Discord links may have a trailing slash or query string
+        let path = path.split(['?', '#']).next()?.trim_end_matches('/');
+        let [guild, channel, message] = path
+            .strip_prefix("channels/")?
+            .split('/')
+            .collect::<Vec<_>>()[..]
+        else {
+            return None;
+        };
+        let guild_id = match guild {
+            "@me" => None,
+            guild => Some(guild.parse().ok()?),
+        };
+        return Some((guild_id, channel.parse().ok()?, message.parse().ok()?));
+    }
+
+    let (channel, message) = input.split_once(['/', '-'])?;
+    Some((None, channel.parse().ok()?, message.parse().ok()?))
+}
+
+/// Parses the input and fetches the linked message, checking that it's accessible from where the
+/// command was invoked
+async fn resolve_message_link(
+    ctx: &serenity::Context,
+    invocation_guild_id: Option<serenity::GuildId>,
+    invocation_channel_id: serenity::ChannelId,
+    input: &str,
+) -> Result<MessageLink, MessageLinkError> {
+    let (link_guild_id, channel_id, message_id) =
+        parse_message_link(input).ok_or(MessageLinkError::Malformed)?;
+
+    // The guild ID in the link can't be trusted, so look up where the channel really is
+    let channel = channel_id
+        .to_channel(ctx)
+        .await
+        .map_err(MessageLinkError::Fetch)?;
+    let guild_id = channel.guild().map(|channel| channel.guild_id);
+    let accessible = match guild_id {
+        Some(guild_id) => Some(guild_id) == invocation_guild_id,
+        None => channel_id == invocation_channel_id,
+    };
+    if !accessible || (link_guild_id.is_some() && link_guild_id != guild_id) {
+        return Err(MessageLinkError::Inaccessible);
+    }
+
+    let mut message = channel_id
+        .message(ctx, message_id)
+        .await
+        .map_err(MessageLinkError::Fetch)?;
+    message.guild_id = guild_id;
+    Ok(MessageLink {
+        message,
+        __non_exhaustive: (),
+    })
+}
+
+#[async_trait::async_trait]
+impl<'a> PopArgument<'a> for MessageLink {
+    async fn pop_from(
+        args: &'a str,
+        attachment_index: usize,
+        ctx: &serenity::Context,
+        msg: &serenity::Message,
+    ) -> Result<(&'a str, usize, Self), (Box<dyn std::error::Error + Send + Sync>, Option<String>)>
+    {
+        let (args, input) = crate::prefix_argument::pop_string(args)
+            .map_err(|_| (crate::TooFewArguments::default().into(), None))?;
+        match resolve_message_link(ctx, msg.guild_id, msg.channel_id, &input).await {
+            Ok(link) => Ok((args.trim_start(), attachment_index, link)),
+            Err(e) => Err((e.into(), Some(input.into_owned()))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl SlashArgument for MessageLink {
+    async fn extract(
+        ctx: &serenity::Context,
+        interaction: &serenity::CommandInteraction,
+        value: &serenity::ResolvedValue<'_>,
+    ) -> Result<Self, SlashArgError> {
+        match *value {
+            serenity::ResolvedValue::String(input) => {
+                resolve_message_link(ctx, interaction.guild_id, interaction.channel_id, input)
+                    .await
+                    .map_err(|e| SlashArgError::Parse {
+                        error: e.into(),
+                        input: input.into(),
+                    })
+            }
+            _ => Err(SlashArgError::CommandStructureMismatch {
+                description: "expected string",
+            }),
+        }
+    }
+
+    fn create(builder: serenity::CreateCommandOption) -> serenity::CreateCommandOption {
+        builder
+            .kind(serenity::CommandOptionType::String)
+            .max_length(200)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse_message_link() {
+    let ids = |guild: Option<u64>, channel: u64, message: u64| {
+        Some((
+            guild.map(serenity::GuildId::new),
+            serenity::ChannelId::new(channel),
+            serenity::MessageId::new(message),
+        ))
+    };
+    for &(input, expected) in &[
+        ("https://discord.com/channels/1/2/3", ids(Some(1), 2, 3)),
+        (
+            "https://ptb.discord.com/channels/1/2/3/",
+            ids(Some(1), 2, 3),
+        ),
+        (
+            "<https://discordapp.com/channels/1/2/3>",
+            ids(Some(1), 2, 3),
+        ),
+        (
+            "https://canary.discord.com/channels/@me/2/3",
+            ids(None, 2, 3),
+        ),
+        ("https://discord.com/channels/1/2/3?foo", ids(Some(1), 2, 3)),
+        ("2/3", ids(None, 2, 3)),
+        ("2-3", ids(None, 2, 3)),
+    ] {
+        assert_eq!(parse_message_link(input), expected, "{input}");
+    }
+    for input in [
+        "",
+        "3",
+        "2/3/4",
+        "https://discord.com/channels/1/2",
+        "https://evil.com/channels/1/2/3",
+        "https://notdiscord.com/channels/1/2/3",
+        "https://discord.com/channels/x/2/3",
+    ] {
+        assert_eq!(parse_message_link(input), None, "{input}");
+    }
+}
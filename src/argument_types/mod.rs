@@ -0,0 +1,14 @@
+//! Parameter types that work in both prefix and application commands and that need more
+//! involved parsing than a plain [`std::str::FromStr`] implementation
+
+mod emoji;
+pub use emoji::*;
+
+mod message_link;
+pub use message_link::*;
+
+mod timestamp;
+pub use timestamp::*;
+
+mod timezone;
+pub use timezone::*;
@@ -0,0 +1,278 @@
+//! Parsing code for absolute points in time, usable as [`serenity::Timestamp`] command parameter
+
+use crate::serenity_prelude as serenity;
+use crate::{PopArgument, SlashArgError, SlashArgument};
+
+/// Error thrown when a [`serenity::Timestamp`] parameter can't be parsed. Its [`Display`]
+/// implementation lists the accepted input formats, so it can be shown to the user as-is.
+///
+/// [`Display`]: std::fmt::Display
+#[derive(Default, Debug, Clone)]
+pub struct TimestampParseError {
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+impl std::fmt::Display for TimestampParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "Couldn't understand that date. Accepted formats are `2024-05-01`, \
+            `2024-05-01 14:30`, `2024-05-01T14:30:00+02:00`, `01.05.2024 14:30`, \
+            `1 May 2024 14:30`, `May 1, 2024`, Unix timestamps like `1714573800` and Discord \
+            timestamps like `<t:1714573800:R>`. Times without a UTC offset are treated as UTC",
+        )
+    }
+}
+impl std::error::Error for TimestampParseError {}
+
+/// Maps an English month name or its three-letter abbreviation to the month number
+fn parse_month_name(word: &str) -> Option<u32> {
+    const MONTHS: [&str; 12] = [
+        "january",
+        "february",
+        "march",
+        "april",
+        "may",
+        "june",
+        "july",
+        "august",
+        "september",
+        "october",
+        "november",
+        "december",
+    ];
+
+    let word = word.to_ascii_lowercase();
+    let word = word.trim_end_matches('.');
+    MONTHS
+        .iter()
+        .position(|&month| word == month || (word.len() == 3 && month.starts_with(word)))
+        .map(|i| i as u32 + 1)
+}
+
+/// Parses `2024-05-01`, `2024/05/01` or `01.05.2024` into (year, month, day)
+fn parse_numeric_date(word: &str) -> Option<(i32, u32, u32)> {
+    let (parts, year_first): (Vec<&str>, bool) = if word.contains('-') {
+        (word.split('-').collect(), true)
+    } else if word.contains('/') {
+        (word.split('/').collect(), true)
+    } else {
+        (word.split('.').collect(), false)
+    };
+    let [a, b, c] = parts[..] else {
+        return None;
+    };
+    let (year, month, day) = if year_first { (a, b, c) } else { (c, b, a) };
+    if year.len() != 4 {
+        return None;
+    }
+    Some((year.parse().ok()?, month.parse().ok()?, day.parse().ok()?))
+}
+
+/// Parses `14:30` or `14:30:05` into (hour, minute, second)
+fn parse_time(word: &str) -> Option<(u32, u32, u32)> {
+    let mut parts = word.split(':');
+    let hour = parts.next()?.parse().ok()?;
+    let minute = parts.next()?.parse().ok()?;
+    let second = match parts.next() {
+        Some(second) => second.parse().ok()?,
+        None => 0,
+    };
+    if parts.next().is_some() {
+        return None;
+    }
+    Some((hour, minute, second))
+}
+
+/// Bare numbers with fewer digits aren't accepted as Unix timestamps. Nine digits start at
+/// 1973-03-03, so any plausible date can still be entered
+const MIN_UNIX_TIMESTAMP_DIGITS: usize = 9;
+
+/// Parses an absolute point in time from a variety of formats. See [`TimestampParseError`] for the
+/// list of accepted formats.
+pub fn parse_timestamp(input: &str) -> Result<serenity::Timestamp, TimestampParseError> {
+    let input = input.trim();
+
+    // Discord timestamp markup: <t:1714573800> or <t:1714573800:R>
+    if let Some(inner) = input.strip_prefix("<t:").and_then(|x| x.strip_suffix('>')) {
+        let secs = inner.split(':').next().unwrap_or(inner);
+        return secs
+            .parse()
+            .ok()
+            .and_then(|secs| serenity::Timestamp::from_unix_timestamp(secs).ok())
+            .ok_or_else(TimestampParseError::default);
+    }
+    // Unix timestamp in seconds. Shorter numbers are more likely a year or a typo than a date in
+    // the early 1970s, so they're rejected instead of silently resolving to such a date. Those
+    // dates can still be given as `<t:...>`
+    if !input.is_empty() && input.bytes().all(|b| b.is_ascii_digit()) {
+        if input.len() < MIN_UNIX_TIMESTAMP_DIGITS {
+            return Err(TimestampParseError::default());
+        }
+        return input
+            .parse()
+            .ok()
+            .and_then(|secs| serenity::Timestamp::from_unix_timestamp(secs).ok())
+            .ok_or_else(TimestampParseError::default);
+    }
+    // Full RFC 3339, which may carry its own UTC offset
+    if let Ok(timestamp) = serenity::Timestamp::parse(input) {
+        return Ok(timestamp);
+    }
+
+    let normalized = input.replace(',', " ");
+    let mut words = normalized.split_whitespace().collect::<Vec<_>>();
+    // ISO 8601 without offset: 2024-05-01T14:30
+    if let [word] = words[..] {
+        if let Some((date, time)) = word.split_once(['T', 't']) {
+            words = vec![date, time];
+        }
+    }
+    // Explicit UTC markers are redundant because UTC is the default anyway
+    if let Some(last) = words.last_mut() {
+        if last.eq_ignore_ascii_case("utc") || *last == "Z" {
+            words.pop();
+        } else if let Some(time) = last.strip_suffix('Z') {
+            *last = time;
+        }
+    }
+
+    let (date, rest) = if let Some(date) = words.first().and_then(|w| parse_numeric_date(w)) {
+        (date, &words[1..])
+    } else {
+        let date = match words[..] {
+            // 1 May 2024
+            [day, month, year, ..] if day.starts_with(|c: char| c.is_ascii_digit()) => {
+                (year, parse_month_name(month), day)
+            }
+            // May 1 2024
+            [month, day, year, ..] => (year, parse_month_name(month), day),
+            _ => return Err(TimestampParseError::default()),
+        };
+        let day = date
+            .2
+            .trim_end_matches(&['.', 's', 't', 'n', 'd', 'r', 'h'][..]);
+        match (date.0.parse(), date.1, day.parse()) {
+            (Ok(year), Some(month), Ok(day)) => ((year, month, day), &words[3..]),
+            _ => return Err(TimestampParseError::default()),
+        }
+    };
+    let (hour, minute, second) = match rest {
+        [] => (0, 0, 0),
+        [time] => parse_time(time).ok_or_else(TimestampParseError::default)?,
+        _ => return Err(TimestampParseError::default()),
+    };
+
+    // Let serenity's parser do the calendar validation (e.g. reject February 30th)
+    let (year, month, day) = date;
+    serenity::Timestamp::parse(&format!(
+        "{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z"
+    ))
+    .map_err(|_| TimestampParseError::default())
+}
+
+#[async_trait::async_trait]
+impl<'a> PopArgument<'a> for serenity::Timestamp {
+    /// Parses a date and optionally a time. Formats that contain spaces, like `2024-05-01 14:30`
+    /// or `1 May 2024`, don't need to be quoted; the longest sequence of words that forms a valid
+    /// date is consumed.
+    async fn pop_from(
+        args: &'a str,
+        attachment_index: usize,
+        _: &serenity::Context,
+        _: &serenity::Message,
+    ) -> Result<(&'a str, usize, Self), (Box<dyn std::error::Error + Send + Sync>, Option<String>)>
+    {
+        // A date is at most four words long (`1 May 2024 14:30`)
+        let mut candidates = Vec::with_capacity(4);
+        let mut rest = args;
+        let mut input = String::new();
+        while candidates.len() < 4 {
+            let Ok((new_rest, word)) = crate::prefix_argument::pop_string(rest) else {
+                break;
+            };
+            if !input.is_empty() {
+                input.push(' ');
+            }
+            input += &word;
+            rest = new_rest;
+            candidates.push((rest, input.clone()));
+        }
+
+        let first_word = match candidates.first() {
+            Some((_, word)) => word.clone(),
+            None => return Err((crate::TooFewArguments::default().into(), None)),
+        };
+        for (rest, input) in candidates.into_iter().rev() {
+            if let Ok(timestamp) = parse_timestamp(&input) {
+                return Ok((rest.trim_start(), attachment_index, timestamp));
+            }
+        }
+        Err((TimestampParseError::default().into(), Some(first_word)))
+    }
+}
+
+#[async_trait::async_trait]
+impl SlashArgument for serenity::Timestamp {
+    async fn extract(
+        _: &serenity::Context,
+        _: &serenity::CommandInteraction,
+        value: &serenity::ResolvedValue<'_>,
+    ) -> Result<Self, SlashArgError> {
+        match *value {
+            serenity::ResolvedValue::String(input) => {
+                parse_timestamp(input).map_err(|e| SlashArgError::Parse {
+                    error: e.into(),
+                    input: input.into(),
+                })
+            }
+            _ => Err(SlashArgError::CommandStructureMismatch {
+                description: "expected string",
+            }),
+        }
+    }
+
+    fn create(builder: serenity::CreateCommandOption) -> serenity::CreateCommandOption {
+        builder
+            .kind(serenity::CommandOptionType::String)
+            .max_length(64)
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse_timestamp() {
+    for &(input, expected) in &[
+        ("2024-05-01", "2024-05-01T00:00:00Z"),
+        ("2024/05/01", "2024-05-01T00:00:00Z"),
+        ("01.05.2024", "2024-05-01T00:00:00Z"),
+        ("2024-05-01 14:30", "2024-05-01T14:30:00Z"),
+        ("2024-05-01T14:30:05", "2024-05-01T14:30:05Z"),
+        ("2024-05-01T14:30:05Z", "2024-05-01T14:30:05Z"),
+        ("2024-05-01T16:30:05+02:00", "2024-05-01T14:30:05Z"),
+        ("01.05.2024 14:30 UTC", "2024-05-01T14:30:00Z"),
+        ("1 May 2024", "2024-05-01T00:00:00Z"),
+        ("1st May 2024 14:30", "2024-05-01T14:30:00Z"),
+        ("May 1, 2024", "2024-05-01T00:00:00Z"),
+        ("sep 30 2024 23:59:59", "2024-09-30T23:59:59Z"),
+        ("1714573800", "2024-05-01T14:30:00Z"),
+        ("<t:1714573800:R>", "2024-05-01T14:30:00Z"),
+    ] {
+        assert_eq!(
+            parse_timestamp(input).unwrap().unix_timestamp(),
+            serenity::Timestamp::parse(expected)
+                .unwrap()
+                .unix_timestamp(),
+            "{input}",
+        );
+    }
+
+    assert!(parse_timestamp("").is_err());
+    assert!(parse_timestamp("2024").is_err());
+    assert!(parse_timestamp("-1714573800").is_err());
+    assert!(parse_timestamp("<t:2024>").is_ok());
+    assert!(parse_timestamp("tomorrow").is_err());
+    assert!(parse_timestamp("2024-02-30").is_err());
+    assert!(parse_timestamp("2024-05-01 25:00").is_err());
+    assert!(parse_timestamp("24-05-01").is_err());
+    assert!(parse_timestamp("Mayo 1 2024").is_err());
+}
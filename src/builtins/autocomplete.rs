@@ -0,0 +1,167 @@
+//! Fuzzy matching helper for autocomplete callbacks
+
+use crate::serenity_prelude as serenity;
+
+/// Discord rejects autocomplete responses with more choices than this
+const MAX_CHOICES: usize = 25;
+/// Discord rejects choice names and string values longer than this (in characters)
+const MAX_CHOICE_LENGTH: usize = 100;
+
+/// Cuts off a string after [`MAX_CHOICE_LENGTH`] characters
+fn truncate(text: &str) -> &str {
+    match text.char_indices().nth(MAX_CHOICE_LENGTH) {
+        Some((byte_index, _)) => &text[..byte_index],
+        None => text,
+    }
+}
+
+/// Checks whether two characters are equal, ignoring case
+fn chars_match(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// Scores the match of `partial` against `candidate`, starting the search at character index
+/// `start`. All characters of `partial` must appear in `candidate` in order.
+fn score_from(candidate: &[char], partial: &[char], start: usize) -> Option<i64> {
+    let mut score = 0;
+    let mut previous_match: Option<usize> = None;
+    let mut position = start;
+    for &wanted in partial {
+        let offset = candidate[position..]
+            .iter()
+            .position(|&c| chars_match(c, wanted))?;
+        let index = position + offset;
+
+        score += 1;
+        if previous_match.is_some_and(|previous| previous + 1 == index) {
+            // Runs of consecutive characters are what makes a match look right to humans
+            score += 6;
+        } else if let Some(previous) = previous_match {
+            score -= (index - previous - 1).min(5) as i64;
+        }
+        let at_word_start = match index.checked_sub(1).map(|i| candidate[i]) {
+            None => true,
+            Some(before) => {
+                !before.is_alphanumeric()
+                    || (before.is_lowercase() && candidate[index].is_uppercase())
+            }
+        };
+        if at_word_start {
+            score += 8;
+        }
+
+        previous_match = Some(index);
+        position = index + 1;
+    }
+
+    // Prefer matches close to the start of shorter candidates
+    score -= (start.min(10) / 2) as i64;
+    score -= ((candidate.len() - partial.len()) / 10) as i64;
+    Some(score)
+}
+
+/// Scores how well the user's `partial` input matches `candidate`, case-insensitively. Higher is
+/// better. Returns `None` if the characters of `partial` don't all appear in `candidate` in order.
+///
+/// Consecutive matched characters and matches at the start of words (`sn` in `Snow Leopard`) are
+/// rewarded; gaps between matched characters are penalized.
+fn fuzzy_score(candidate: &str, partial: &str) -> Option<i64> {
+    let candidate = candidate.chars().collect::<Vec<_>>();
+    let partial = partial.trim().chars().collect::<Vec<_>>();
+    let Some(&first) = partial.first() else {
+        return Some(0);
+    };
+
+    // Greedy matching could lock onto an early stray occurrence of the first character, so try
+    // every possible starting point and keep the best
+    let best = (0..candidate.len())
+        .filter(|&start| chars_match(candidate[start], first))
+        .filter_map(|start| score_from(&candidate, &partial, start))
+        .max()?;
+
+    let bonus = if candidate.len() == partial.len() {
+        // Exact match (modulo case)
+        100
+    } else if candidate
+        .iter()
+        .zip(&partial)
+        .all(|(&a, &b)| chars_match(a, b))
+    {
+        20
+    } else {
+        0
+    };
+    Some(best + bonus)
+}
+
+/// Ranks `candidates` by how well they fuzzy-match what the user has typed so far and returns the
+/// best ones as autocomplete choices, ready to be returned from an autocomplete callback.
+///
+/// Unlike a plain `starts_with` filter, this finds `Snow Leopard` for `leo` or `snlp`, and ranks
+/// exact and prefix matches first. Candidates that match equally well keep their original order,
+/// and with empty input, the first candidates are returned as-is.
+///
+/// At most `limit` choices are returned, and never more than the 25 that Discord accepts. Choice
+/// names and values are truncated to Discord's limit of 100 characters.
+///
+/// ```rust
+/// # use poise::serenity_prelude as serenity;
+/// async fn autocomplete_animal(
+///     _ctx: poise::Context<'_, (), serenity::Error>,
+///     partial: &str,
+/// ) -> Vec<serenity::AutocompleteChoice> {
+///     let animals = ["Cat", "Dog", "Snow Leopard", "Leopard Seal"];
+///     poise::builtins::autocomplete_fuzzy(animals, partial, 25)
+/// }
+/// ```
+pub fn autocomplete_fuzzy<I, S>(
+    candidates: I,
+    partial: &str,
+    limit: usize,
+) -> Vec<serenity::AutocompleteChoice>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut matches = candidates
+        .into_iter()
+        .filter_map(|candidate| {
+            let score = fuzzy_score(candidate.as_ref(), partial)?;
+            Some((score, candidate))
+        })
+        .collect::<Vec<_>>();
+    // Stable sort, so ties stay in the caller's order
+    matches.sort_by_key(|&(score, _)| std::cmp::Reverse(score));
+
+    matches
+        .into_iter()
+        .take(limit.min(MAX_CHOICES))
+        .map(|(_, candidate)| {
+            let text = truncate(candidate.as_ref());
+            serenity::AutocompleteChoice::new(text, text)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[test]
+fn test_fuzzy_score() {
+    assert_eq!(fuzzy_score("anything", ""), Some(0));
+    assert_eq!(fuzzy_score("Snow Leopard", "xyz"), None);
+    assert_eq!(fuzzy_score("abc", "cba"), None);
+    assert!(fuzzy_score("Snow Leopard", "snlp").is_some());
+    assert!(fuzzy_score("Snow Leopard", "LEO").is_some());
+
+    let ranked = |partial| {
+        let mut candidates = vec!["Leopard Seal", "Snow Leopard", "Elephant", "leo"];
+        candidates.retain(|c| fuzzy_score(c, partial).is_some());
+        candidates.sort_by_key(|c| std::cmp::Reverse(fuzzy_score(c, partial)));
+        candidates
+    };
+    assert_eq!(ranked("leo"), ["leo", "Leopard Seal", "Snow Leopard"]);
+    assert_eq!(ranked("sl"), ["Snow Leopard", "Leopard Seal"]);
+    assert_eq!(ranked("ep"), ["Elephant", "Leopard Seal", "Snow Leopard"]);
+
+    assert_eq!(truncate(&"ä".repeat(150)), "ä".repeat(100));
+    assert_eq!(truncate("short"), "short");
+}
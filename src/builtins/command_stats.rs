@@ -0,0 +1,59 @@
+//! Contains a command that displays command usage statistics, see [`command_stats`]
+
+use crate::{serenity_prelude as serenity, CreateReply};
+
+/// Formats a ranking of commands as lines like `1. `ping`: 42`
+fn format_ranking(ranking: &[(String, u64)]) -> String {
+    ranking
+        .iter()
+        .enumerate()
+        .map(|(i, (command, invocations))| format!("{}. `{}`: {}", i + 1, command, invocations))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Responds with the most used commands overall and, in guilds, in the current guild, as counted
+/// by [`crate::FrameworkOptions::command_usage`]. If usage counting isn't enabled, the user is
+/// told so.
+///
+/// ```rust,no_run
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// # type Context<'a> = poise::Context<'a, (), Error>;
+/// #[poise::command(slash_command, prefix_command)]
+/// pub async fn command_stats(ctx: Context<'_>) -> Result<(), Error> {
+///     poise::builtins::command_stats(ctx).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn command_stats<U, E>(ctx: crate::Context<'_, U, E>) -> Result<(), serenity::Error> {
+    let Some(command_usage) = &ctx.framework().options().command_usage else {
+        ctx.say("Command usage statistics are not enabled").await?;
+        return Ok(());
+    };
+
+    let snapshot = command_usage.snapshot();
+    let total_invocations = snapshot
+        .values()
+        .map(|stats| stats.invocations)
+        .sum::<u64>();
+    let total_failures = snapshot.values().map(|stats| stats.failures).sum::<u64>();
+
+    let top = command_usage.top(10);
+    let mut embed = serenity::CreateEmbed::new()
+        .title("Command usage")
+        .description(match top.is_empty() {
+            true => "No commands have been used yet".to_owned(),
+            false => format_ranking(&top),
+        })
+        .field("Invocations", total_invocations.to_string(), true)
+        .field("Failed", total_failures.to_string(), true);
+    if let Some(guild_id) = ctx.guild_id() {
+        let top_in_guild = command_usage.top_in_guild(guild_id, 5);
+        if !top_in_guild.is_empty() {
+            embed = embed.field("In this server", format_ranking(&top_in_guild), false);
+        }
+    }
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
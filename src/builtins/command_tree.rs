@@ -0,0 +1,284 @@
+//! Contains a command that dumps the command tree for debugging, see [`command_tree`]
+
+use std::fmt::Write as _;
+
+use crate::{serenity_prelude as serenity, CreateReply};
+
+/// Returns the application command option type of a parameter, like `Integer`, or how the
+/// parameter is parsed if it has no type
+pub(super) fn parameter_type<U, E>(parameter: &crate::CommandParameter<U, E>) -> String {
+    use ::serenity::json::*; // as_u64() access via trait for simd-json
+
+    if let Some(type_setter) = parameter.type_setter {
+        let option = type_setter(serenity::CreateCommandOption::new(
+            serenity::CommandOptionType::String,
+            "",
+            "",
+        ));
+        if let Some(kind) = to_value(option)
+            .ok()
+            .and_then(|option| option.get("type").and_then(|kind| kind.as_u64()))
+        {
+            return format!("{:?}", serenity::CommandOptionType::from(kind as u8));
+        }
+    }
+    if parameter.dynamic_choices.is_some() {
+        "dynamic choice".to_owned()
+    } else if !parameter.choices.is_empty() {
+        "choice".to_owned()
+    } else {
+        "prefix only".to_owned()
+    }
+}
+
+/// Returns the checks that restrict who can run the command, like `owners_only` or `2 custom`
+fn checks<U, E>(command: &crate::Command<U, E>) -> Vec<String> {
+    let mut checks = Vec::new();
+    for (enabled, name) in [
+        (command.owners_only, "owners_only"),
+        (command.guild_only, "guild_only"),
+        (command.dm_only, "dm_only"),
+        (command.nsfw_only, "nsfw_only"),
+    ] {
+        if enabled {
+            checks.push(name.to_owned());
+        }
+    }
+    if !command.required_permissions.is_empty() {
+        checks.push(format!("user needs {}", command.required_permissions));
+    }
+    if !command.required_bot_permissions.is_empty() {
+        checks.push(format!("bot needs {}", command.required_bot_permissions));
+    }
+    if !command.checks.is_empty() {
+        checks.push(format!("{} custom", command.checks.len()));
+    }
+    checks
+}
+
+/// Returns the configured cooldowns of the command, like `user: 5s`
+fn cooldowns<U, E>(command: &crate::Command<U, E>) -> Vec<String> {
+    let config = command.cooldown_config.read().unwrap();
+    [
+        ("global", config.global),
+        ("user", config.user),
+        ("guild", config.guild),
+        ("channel", config.channel),
+        ("member", config.member),
+    ]
+    .into_iter()
+    .filter_map(|(scope, duration)| Some(format!("{}: {:?}", scope, duration?)))
+    .collect()
+}
+
+/// Returns how the command can be invoked, like `["prefix", "slash"]`
+fn kinds<U, E>(command: &crate::Command<U, E>) -> Vec<&'static str> {
+    let mut kinds = Vec::new();
+    if command.prefix_action.is_some() {
+        kinds.push("prefix");
+    }
+    if command.slash_action.is_some() {
+        kinds.push("slash");
+    }
+    if command.context_menu_action.is_some() {
+        kinds.push("context menu");
+    }
+    kinds
+}
+
+/// Returns whether the top-level command of this command is registered on Discord, as far as
+/// [`crate::Command::application_command_id`] tells
+fn registration<U, E>(command: &crate::Command<U, E>) -> String {
+    match *command.application_command_id.lock().unwrap() {
+        Some(id) => format!("registered ({})", id),
+        None => "no known registration".to_owned(),
+    }
+}
+
+/// Appends a human-readable description of the command and its subcommands to the text
+fn write_command<U, E>(text: &mut String, command: &crate::Command<U, E>, depth: usize) {
+    let indent = "  ".repeat(depth);
+    let _ = writeln!(
+        text,
+        "{}{} [{}]",
+        indent,
+        command.name,
+        kinds(command).join(", ")
+    );
+    if !command.aliases.is_empty() {
+        let _ = writeln!(text, "{}  aliases: {}", indent, command.aliases.join(", "));
+    }
+    if let Some(category) = &command.category {
+        let _ = writeln!(text, "{}  category: {}", indent, category);
+    }
+    for parameter in &command.parameters {
+        let _ = writeln!(
+            text,
+            "{}  param {}: {}{}",
+            indent,
+            parameter.name,
+            parameter_type(parameter),
+            if parameter.required {
+                ""
+            } else {
+                " (optional)"
+            },
+        );
+    }
+    let checks = checks(command);
+    if !checks.is_empty() {
+        let _ = writeln!(text, "{}  checks: {}", indent, checks.join(", "));
+    }
+    let cooldowns = cooldowns(command);
+    if !cooldowns.is_empty() {
+        let _ = writeln!(text, "{}  cooldowns: {}", indent, cooldowns.join(", "));
+    }
+    if command.hide_in_help {
+        let _ = writeln!(text, "{}  hidden in help", indent);
+    }
+    if !command.is_enabled() {
+        let _ = writeln!(text, "{}  disabled", indent);
+    }
+    if depth == 0 && (command.slash_action.is_some() || command.context_menu_action.is_some()) {
+        let _ = writeln!(text, "{}  {}", indent, registration(command));
+    }
+    for subcommand in &command.subcommands {
+        write_command(text, subcommand, depth + 1);
+    }
+}
+
+/// Describes the command and its subcommands as JSON
+fn command_to_json<U, E>(command: &crate::Command<U, E>) -> ::serenity::json::Value {
+    let parameters = command
+        .parameters
+        .iter()
+        .map(|parameter| {
+            ::serenity::json::json!({
+                "name": parameter.name,
+                "type": parameter_type(parameter),
+                "required": parameter.required,
+            })
+        })
+        .collect::<Vec<_>>();
+    let subcommands = command
+        .subcommands
+        .iter()
+        .map(command_to_json)
+        .collect::<Vec<_>>();
+    let application_command_id = command
+        .application_command_id
+        .lock()
+        .unwrap()
+        .map(|id| id.to_string());
+
+    ::serenity::json::json!({
+        "name": command.name,
+        "qualified_name": command.qualified_name,
+        "kinds": kinds(command),
+        "aliases": command.aliases,
+        "category": command.category,
+        "hide_in_help": command.hide_in_help,
+        "enabled": command.is_enabled(),
+        "parameters": parameters,
+        "checks": checks(command),
+        "cooldowns": cooldowns(command),
+        "application_command_id": application_command_id,
+        "subcommands": subcommands,
+    })
+}
+
+/// Dumps the full command tree for debugging, e.g. why a subcommand doesn't show up: names,
+/// aliases, categories, parameter types, checks, cooldowns and whether the command is known to
+/// be registered on Discord. Only bot owners may use this; the response is ephemeral.
+///
+/// If `as_json` is true, the tree is attached as a JSON file. Otherwise it's shown as code
+/// blocks, split into pages with navigation buttons (see [`super::paginate`]) if it's too long.
+/// Without the `chrono` feature, only the first page is shown.
+///
+/// ```rust,no_run
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// # type Context<'a> = poise::Context<'a, (), Error>;
+/// #[poise::command(prefix_command, owners_only, hide_in_help)]
+/// pub async fn command_tree(ctx: Context<'_>, #[flag] json: bool) -> Result<(), Error> {
+///     poise::builtins::command_tree(ctx, json).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn command_tree<U, E>(
+    ctx: crate::Context<'_, U, E>,
+    as_json: bool,
+) -> Result<(), serenity::Error> {
+    if !super::ensure_owner(ctx).await? {
+        return Ok(());
+    }
+
+    let commands = ctx.framework().commands();
+
+    if as_json {
+        let tree = commands
+            .iter()
+            .map(AsRef::as_ref)
+            .map(command_to_json)
+            .collect::<Vec<_>>();
+        let json = ::serenity::json::to_string_pretty(&tree)?;
+        let attachment = serenity::CreateAttachment::bytes(json, "commands.json");
+        let reply = CreateReply::default()
+            .attachment(attachment)
+            .ephemeral(true);
+        ctx.send(reply).await?;
+        return Ok(());
+    }
+
+    // Split at line boundaries into pages that fit into an embed with code block markers
+    let mut pages = vec![String::new()];
+    for command in commands {
+        let mut text = String::new();
+        write_command(&mut text, command, 0);
+        for line in text.lines() {
+            let page = pages.last_mut().expect("there's at least one page");
+            if !page.is_empty() && page.len() + line.len() > 3900 {
+                pages.push(String::new());
+            }
+            let page = pages.last_mut().expect("there's at least one page");
+            page.push_str(line);
+            page.push('\n');
+        }
+    }
+    let pages = pages
+        .into_iter()
+        .map(|page| format!("```\n{}```", page))
+        .collect::<Vec<_>>();
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    if pages.len() > 1 {
+        let pages = pages.iter().map(|page| &**page).collect::<Vec<_>>();
+        return super::paginate_with_visibility(ctx, &pages, true).await;
+    }
+
+    let reply = CreateReply::default()
+        .embed(serenity::CreateEmbed::new().description(&pages[0]))
+        .ephemeral(true);
+    ctx.send(reply).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_parameter_type() {
+    let mut parameter = crate::CommandParameter::<(), ()> {
+        name: "amount".into(),
+        name_localizations: Default::default(),
+        description: None,
+        description_localizations: Default::default(),
+        required: true,
+        channel_types: None,
+        choices: Vec::new(),
+        dynamic_choices: None,
+        type_setter: Some(|b| b.kind(serenity::CommandOptionType::Integer)),
+        autocomplete_callback: None,
+        __non_exhaustive: (),
+    };
+    assert_eq!(parameter_type(&parameter), "Integer");
+    parameter.type_setter = None;
+    assert_eq!(parameter_type(&parameter), "prefix only");
+}
@@ -0,0 +1,215 @@
+//! Generates Markdown documentation of the bot's commands, see [`markdown_docs`]
+
+use std::fmt::Write as _;
+
+/// Optional configuration for the documentation generated by [`markdown_docs`]
+#[derive(Debug, Clone)]
+pub struct DocsOptions {
+    /// Heading at the top of the document. `"Commands"` by default
+    pub title: String,
+    /// Prefix shown in the usage of prefix commands. `"~"` by default
+    pub prefix: String,
+    /// Whether to include commands with [`crate::Command::hide_in_help`]. `false` by default
+    pub include_hidden: bool,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for DocsOptions {
+    fn default() -> Self {
+        Self {
+            title: "Commands".to_owned(),
+            prefix: "~".to_owned(),
+            include_hidden: false,
+            __non_exhaustive: (),
+        }
+    }
+}
+
+/// Escapes text for use in a Markdown table cell
+fn table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Returns the checks that restrict who can run the command, like `servers only`
+fn restrictions<U, E>(command: &crate::Command<U, E>) -> Vec<String> {
+    let mut restrictions = Vec::new();
+    for (enabled, name) in [
+        (command.owners_only, "bot owners only"),
+        (command.guild_only, "servers only"),
+        (command.dm_only, "DMs only"),
+        (command.nsfw_only, "NSFW channels only"),
+    ] {
+        if enabled {
+            restrictions.push(name.to_owned());
+        }
+    }
+    if !command.required_permissions.is_empty() {
+        restrictions.push(format!("requires {}", command.required_permissions));
+    }
+    restrictions
+}
+
+/// Appends the documentation of the command and its subcommands to the text
+fn write_command<U, E>(
+    text: &mut String,
+    command: &crate::Command<U, E>,
+    qualified_name: &str,
+    options: &DocsOptions,
+) {
+    let _ = writeln!(text, "### `{}`\n", qualified_name);
+    if let Some(description) = &command.description {
+        let _ = writeln!(text, "{}\n", description);
+    }
+    if let Some(help_text) = &command.help_text {
+        let _ = writeln!(text, "{}\n", help_text);
+    }
+
+    let mut usage = qualified_name.to_owned();
+    for parameter in &command.parameters {
+        usage += " ";
+        usage += &crate::Command::format_parameter(parameter);
+    }
+    if command.slash_action.is_some() {
+        let _ = writeln!(text, "- Slash command: `/{}`", usage);
+    }
+    if command.prefix_action.is_some() {
+        let _ = writeln!(text, "- Prefix command: `{}{}`", options.prefix, usage);
+    }
+    if let Some(context_menu_name) = &command.context_menu_name {
+        let _ = writeln!(text, "- Context menu: {}", context_menu_name);
+    }
+    if !command.aliases.is_empty() {
+        let aliases = command
+            .aliases
+            .iter()
+            .map(|alias| format!("`{}`", alias))
+            .collect::<Vec<_>>();
+        let _ = writeln!(text, "- Aliases: {}", aliases.join(", "));
+    }
+    let restrictions = restrictions(command);
+    if !restrictions.is_empty() {
+        let _ = writeln!(text, "- Restrictions: {}", restrictions.join(", "));
+    }
+    text.push('\n');
+
+    if !command.parameters.is_empty() {
+        text.push_str("| Parameter | Type | Required | Description |\n");
+        text.push_str("| --- | --- | --- | --- |\n");
+        for parameter in &command.parameters {
+            let _ = writeln!(
+                text,
+                "| `{}` | {} | {} | {} |",
+                parameter.name,
+                super::command_tree::parameter_type(parameter),
+                if parameter.required { "yes" } else { "no" },
+                table_cell(parameter.description.as_deref().unwrap_or("")),
+            );
+        }
+        text.push('\n');
+    }
+
+    for subcommand in &command.subcommands {
+        if options.include_hidden || !subcommand.hide_in_help {
+            let qualified_name = format!("{} {}", qualified_name, subcommand.name);
+            write_command(text, subcommand, &qualified_name, options);
+        }
+    }
+}
+
+/// Generates Markdown documentation of the given commands, e.g. the commands you pass to
+/// [`crate::FrameworkOptions::commands`] or those of a running framework from
+/// [`crate::Framework::commands`], to publish an always up-to-date commands page derived
+/// from the same source as the help command.
+///
+/// Commands are grouped by [`crate::Command::category`] like in [`super::help()`]. Each command
+/// gets a section with its description and help text, usage signatures, aliases, restrictions and
+/// a table of parameters. Subcommands follow their parent command.
+///
+/// For example, to write the documentation to a file from a test or a small binary in your bot
+/// (build scripts can't call into the crate they're building):
+///
+/// ```rust,no_run
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// # fn commands() -> Vec<poise::Command<(), Error>> { vec![] }
+/// let docs = poise::builtins::markdown_docs(&commands(), &Default::default());
+/// std::fs::write("COMMANDS.md", docs).unwrap();
+/// ```
+pub fn markdown_docs<U, E>(
+    commands: &[impl AsRef<crate::Command<U, E>>],
+    options: &DocsOptions,
+) -> String {
+    let mut categories = indexmap::IndexMap::<Option<&str>, Vec<&crate::Command<U, E>>>::new();
+    for command in commands {
+        let command: &crate::Command<U, E> = command.as_ref();
+        if options.include_hidden || !command.hide_in_help {
+            categories
+                .entry(command.category.as_deref())
+                .or_default()
+                .push(command);
+        }
+    }
+
+    let mut text = format!("# {}\n\n", options.title);
+    for (category, commands) in categories {
+        let _ = writeln!(text, "## {}\n", category.unwrap_or("Commands"));
+        for command in commands {
+            write_command(&mut text, command, &command.name, options);
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+#[test]
+fn test_markdown_docs() {
+    let commands = vec![crate::Command::<(), ()> {
+        name: "config".into(),
+        category: Some("Settings".into()),
+        description: Some("Manages settings".into()),
+        prefix_action: Some(|_| Box::pin(async { Ok(()) })),
+        subcommands: vec![crate::Command {
+            name: "set".into(),
+            slash_action: Some(|_| Box::pin(async { Ok(()) })),
+            parameters: vec![crate::CommandParameter {
+                name: "key".into(),
+                name_localizations: Default::default(),
+                description: Some("Setting | key".into()),
+                description_localizations: Default::default(),
+                required: true,
+                channel_types: None,
+                choices: Vec::new(),
+                dynamic_choices: None,
+                type_setter: None,
+                autocomplete_callback: None,
+                __non_exhaustive: (),
+            }],
+            guild_only: true,
+            ..Default::default()
+        }],
+        ..Default::default()
+    }];
+    assert_eq!(
+        markdown_docs(&commands, &DocsOptions::default()),
+        "# Commands
+
+## Settings
+
+### `config`
+
+Manages settings
+
+- Prefix command: `~config`
+
+### `config set`
+
+- Slash command: `/config set <key>`
+- Restrictions: servers only
+
+| Parameter | Type | Required | Description |
+| --- | --- | --- | --- |
+| `key` | prefix only | yes | Setting \\| key |
+
+"
+    );
+}
@@ -0,0 +1,219 @@
+//! Error handler that responds with embeds, see [`on_error_embed`]
+
+use crate::{serenity_prelude as serenity, CreateReply};
+
+/// Embed color of errors in the command code
+const COLOR_FAILURE: (u8, u8, u8) = (237, 66, 69);
+/// Embed color of errors caused by invalid usage
+const COLOR_USAGE: (u8, u8, u8) = (250, 166, 26);
+/// Embed color of errors caused by missing access
+const COLOR_ACCESS: (u8, u8, u8) = (114, 137, 218);
+
+/// Optional configuration for how the error embeds from [`on_error_embed`] look
+#[derive(Debug, Clone)]
+pub struct ErrorEmbedConfiguration {
+    /// Error text is cut off after this many characters
+    pub max_length: usize,
+    /// If true, the text of command errors and panics is only shown to
+    /// [owners](crate::FrameworkOptions::owners). Others only see the error ID, which can be used
+    /// to find the error in the logs. Panic payloads are never shown to non-owners
+    pub details_for_owners_only: bool,
+    /// Whether to make the response ephemeral if possible
+    pub ephemeral: bool,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for ErrorEmbedConfiguration {
+    fn default() -> Self {
+        Self {
+            max_length: 1000,
+            details_for_owners_only: false,
+            ephemeral: true,
+            __non_exhaustive: (),
+        }
+    }
+}
+
+/// Cuts off a string after `max_length` characters, marking the cut with an ellipsis
+fn truncate(text: &str, max_length: usize) -> String {
+    match text.char_indices().nth(max_length) {
+        Some((byte_index, _)) => format!("{}…", &text[..byte_index]),
+        None => text.to_owned(),
+    }
+}
+
+/// Like [`super::on_error`], but responds to errors in commands with color-coded embeds that
+/// include the command name and an error ID. The error ID is also logged, so that reports from
+/// users can be matched with the logs.
+///
+/// Errors that aren't tied to a command invocation are passed on to [`super::on_error`].
+///
+/// ```rust,no_run
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// poise::FrameworkOptions::<(), Error> {
+///     on_error: |error| {
+///         Box::pin(async move {
+///             let config = poise::builtins::ErrorEmbedConfiguration {
+///                 details_for_owners_only: true,
+///                 ..Default::default()
+///             };
+///             if let Err(e) = poise::builtins::on_error_embed(error, &config).await {
+///                 tracing::error!("Error while handling error: {}", e);
+///             }
+///         })
+///     },
+///     ..Default::default()
+/// };
+/// ```
+pub async fn on_error_embed<U, E: std::fmt::Display + std::fmt::Debug>(
+    error: crate::FrameworkError<'_, U, E>,
+    config: &ErrorEmbedConfiguration,
+) -> Result<(), serenity::Error> {
+    let Some(ctx) = error.ctx() else {
+        return super::on_error(error).await;
+    };
+
+    let error_id = match ctx {
+        crate::Context::Application(ctx) => ctx.interaction.id.get(),
+        crate::Context::Prefix(ctx) => ctx.msg.id.get(),
+    };
+    let is_owner = ctx.framework().options().owners.contains(&ctx.author().id);
+    let show_details = is_owner || !config.details_for_owners_only;
+    let hidden_details = "An unexpected error has occurred. Please report the error ID below.";
+
+    let (title, color, text) = match &error {
+        crate::FrameworkError::Command { error, .. } => {
+            tracing::error!(
+                "error {:x} in command `{}`: {}",
+                error_id,
+                ctx.command().qualified_name,
+                error
+            );
+            let text = match show_details {
+                true => error.to_string(),
+                false => hidden_details.to_owned(),
+            };
+            ("Command failed", COLOR_FAILURE, text)
+        }
+        crate::FrameworkError::CommandPanic { payload, .. } => {
+            tracing::error!(
+                "error {:x}: panic in command `{}`: {}",
+                error_id,
+                ctx.command().qualified_name,
+                payload.as_deref().unwrap_or("<non-string payload>")
+            );
+            let text = match (is_owner, payload) {
+                (true, Some(payload)) => payload.clone(),
+                _ => hidden_details.to_owned(),
+            };
+            ("Internal error", COLOR_FAILURE, text)
+        }
+        crate::FrameworkError::ArgumentParse {
+            error, parameter, ..
+        } => {
+            let mut text = match parameter {
+                Some(parameter) => format!("Invalid value for `{}`: {}", parameter, error),
+                None => error.to_string(),
+            };
+            text += &format!("\nUsage: `{}{}`", ctx.prefix(), ctx.command().usage());
+            ("Invalid arguments", COLOR_USAGE, text)
+        }
+        crate::FrameworkError::SubcommandRequired { .. } => {
+            let subcommands = ctx
+                .command()
+                .subcommands
+                .iter()
+                .map(|s| format!("`{}`", s.name))
+                .collect::<Vec<_>>();
+            let text = format!(
+                "You must specify one of the following subcommands: {}",
+                subcommands.join(", ")
+            );
+            ("Missing subcommand", COLOR_USAGE, text)
+        }
+        crate::FrameworkError::CooldownHit {
+            remaining_cooldown, ..
+        } => {
+            let text = format!(
+                "Please wait {} seconds before retrying",
+                remaining_cooldown.as_secs()
+            );
+            ("You're too fast", COLOR_USAGE, text)
+        }
+        crate::FrameworkError::AlreadyRunning { .. } => {
+            let text = "Please wait for the running invocation to finish".to_owned();
+            ("Command already running", COLOR_USAGE, text)
+        }
+        crate::FrameworkError::MissingBotPermissions {
+            missing_permissions,
+            ..
+        } => {
+            let text = format!("The bot is lacking permissions: {}", missing_permissions);
+            ("Missing permissions", COLOR_ACCESS, text)
+        }
+        crate::FrameworkError::MissingUserPermissions {
+            missing_permissions,
+            ..
+        } => {
+            let text = match missing_permissions {
+                Some(missing_permissions) => {
+                    format!("You're lacking permissions: {}", missing_permissions)
+                }
+                None => "You may be lacking permissions. Not executing for safety".to_owned(),
+            };
+            ("Missing permissions", COLOR_ACCESS, text)
+        }
+        crate::FrameworkError::NotAnOwner { .. } => {
+            let text = "Only bot owners can call this command".to_owned();
+            ("Not allowed", COLOR_ACCESS, text)
+        }
+        crate::FrameworkError::GuildOnly { .. } => {
+            let text = "You cannot run this command in DMs".to_owned();
+            ("Not allowed here", COLOR_ACCESS, text)
+        }
+        crate::FrameworkError::DmOnly { .. } => {
+            let text = "You cannot run this command outside DMs".to_owned();
+            ("Not allowed here", COLOR_ACCESS, text)
+        }
+        crate::FrameworkError::NsfwOnly { .. } => {
+            let text = "You cannot run this command outside NSFW channels".to_owned();
+            ("Not allowed here", COLOR_ACCESS, text)
+        }
+        crate::FrameworkError::CommandDisabled { .. } => {
+            let text = "This command is currently disabled".to_owned();
+            ("Disabled", COLOR_ACCESS, text)
+        }
+        _ => return super::on_error(error).await,
+    };
+
+    let embed = serenity::CreateEmbed::default()
+        .title(title)
+        .color(color)
+        .description(truncate(&text, config.max_length))
+        .field(
+            "Command",
+            format!("`{}{}`", ctx.prefix(), ctx.command().qualified_name),
+            true,
+        )
+        .footer(serenity::CreateEmbedFooter::new(format!(
+            "Error ID: {:x}",
+            error_id
+        )));
+    ctx.send(
+        CreateReply::default()
+            .embed(embed)
+            .ephemeral(config.ephemeral),
+    )
+    .await?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_truncate() {
+    assert_eq!(truncate("short", 10), "short");
+    assert_eq!(truncate("exactly", 7), "exactly");
+    assert_eq!(truncate("äöüäöü", 3), "äöü…");
+}
@@ -16,6 +16,8 @@ pub struct HelpConfiguration<'a> {
     pub show_subcommands: bool,
     /// Whether to include [`crate::Command::description`] (above [`crate::Command::help_text`]).
     pub include_description: bool,
+    /// If set, the overview of all commands is cached in it. See [`HelpCache`]
+    pub cache: Option<&'a HelpCache>,
     #[doc(hidden)]
     pub __non_exhaustive: (),
 }
@@ -28,11 +30,90 @@ impl Default for HelpConfiguration<'_> {
             show_context_menu_commands: false,
             show_subcommands: false,
             include_description: true,
+            cache: None,
             __non_exhaustive: (),
         }
     }
 }
 
+/// Identifies an overview of all commands in the [`HelpCache`]: everything the text depends on
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct HelpCacheKey {
+    /// [`crate::CommandSet::generation`], which changes when the commands change
+    generation: u64,
+    /// Prefix shown for prefix commands
+    prefix: Option<String>,
+    /// Locale of the descriptions
+    locale: Option<String>,
+    /// [`HelpConfiguration::extra_text_at_bottom`]
+    extra_text_at_bottom: String,
+    /// [`HelpConfiguration::show_context_menu_commands`]
+    show_context_menu_commands: bool,
+    /// [`HelpConfiguration::show_subcommands`]
+    show_subcommands: bool,
+}
+
+/// Cache for the overview of all commands shown by [`help()`], so busy bots don't format it on
+/// every invocation. Set it as [`HelpConfiguration::cache`].
+///
+/// Overviews are cached per prefix, locale and configuration. The cache notices when commands
+/// are changed through [`crate::Framework`] and formats the overview again.
+///
+/// ```rust
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// # type Context<'a> = poise::Context<'a, (), Error>;
+/// static HELP_CACHE: poise::builtins::HelpCache = poise::builtins::HelpCache::new();
+///
+/// #[poise::command(prefix_command, slash_command)]
+/// pub async fn help(ctx: Context<'_>, command: Option<String>) -> Result<(), Error> {
+///     let config = poise::builtins::HelpConfiguration {
+///         cache: Some(&HELP_CACHE),
+///         ..Default::default()
+///     };
+///     poise::builtins::help(ctx, command.as_deref(), config).await?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct HelpCache {
+    /// Cached overviews. Cleared when full, see [`Self::MAX_ENTRIES`]
+    entries: std::sync::Mutex<Option<std::collections::HashMap<HelpCacheKey, String>>>,
+}
+
+impl HelpCache {
+    /// With more overviews than this (e.g. because of many dynamic prefixes), the cache starts
+    /// over
+    const MAX_ENTRIES: usize = 256;
+
+    /// Creates an empty cache
+    pub const fn new() -> Self {
+        Self {
+            entries: std::sync::Mutex::new(None),
+        }
+    }
+
+    /// Removes all cached overviews
+    pub fn clear(&self) {
+        *self.entries.lock().unwrap() = None;
+    }
+
+    /// Returns the cached overview for the key, if any
+    fn get(&self, key: &HelpCacheKey) -> Option<String> {
+        self.entries.lock().unwrap().as_ref()?.get(key).cloned()
+    }
+
+    /// Caches an overview. Entries of previous command generations are dropped
+    fn insert(&self, key: HelpCacheKey, overview: String) {
+        let mut entries = self.entries.lock().unwrap();
+        let entries = entries.get_or_insert_with(Default::default);
+        if entries.len() >= Self::MAX_ENTRIES {
+            entries.clear();
+        }
+        entries.retain(|cached_key, _| cached_key.generation == key.generation);
+        entries.insert(key, overview);
+    }
+}
+
 /// Convenience function to align descriptions behind commands
 struct TwoColumnList(Vec<(String, Option<String>)>);
 
@@ -119,15 +200,51 @@ fn format_context_menu_name<U, E>(command: &crate::Command<U, E>) -> Option<Stri
     ))
 }
 
+/// Returns the description in the given locale (or its language without region), falling back
+/// to the default description
+fn localized_description<'a>(
+    description: Option<&'a str>,
+    localizations: &'a std::collections::HashMap<String, String>,
+    locale: Option<&str>,
+) -> &'a str {
+    locale
+        .and_then(|locale| {
+            let language = locale.split('-').next().unwrap_or(locale);
+            localizations
+                .get(locale)
+                .or_else(|| localizations.get(language))
+        })
+        .map(|description| &**description)
+        .or(description)
+        .unwrap_or("")
+}
+
+/// Formats the prefix invocations of a command through its aliases, like `` `~config s` `` for
+/// `config set` with alias `s`
+pub(super) fn alias_invocations<U, E>(command: &crate::Command<U, E>, prefix: &str) -> Vec<String> {
+    // Aliases replace the last word of the qualified name
+    let parent_names = match command.qualified_name.rsplit_once(' ') {
+        Some((parent_names, _)) => format!("{} ", parent_names),
+        None => String::new(),
+    };
+    command
+        .aliases
+        .iter()
+        .map(|alias| format!("`{}{}{}`", prefix, parent_names, alias))
+        .collect()
+}
+
 /// Code for printing help of a specific command (e.g. `~help my_command`)
 async fn help_single_command<U, E>(
     ctx: crate::Context<'_, U, E>,
     command_name: &str,
     config: HelpConfiguration<'_>,
 ) -> Result<(), serenity::Error> {
-    let commands = &ctx.framework().options().commands;
+    let commands = ctx.framework().commands();
+    let locale = ctx.effective_locale();
+    let locale = locale.as_deref();
     // Try interpret the command name as a context menu command first
-    let mut command = commands.iter().find(|command| {
+    let mut command = commands.iter().map(|command| &**command).find(|command| {
         if let Some(context_menu_name) = &command.context_menu_name {
             if context_menu_name.eq_ignore_ascii_case(command_name) {
                 return true;
@@ -146,8 +263,8 @@ async fn help_single_command<U, E>(
         let mut invocations = Vec::new();
         let mut subprefix = None;
         if command.slash_action.is_some() {
-            invocations.push(format!("`/{}`", command.name));
-            subprefix = Some(format!("  /{}", command.name));
+            invocations.push(format!("`/{}`", command.qualified_name));
+            subprefix = Some(format!("  /{}", command.qualified_name));
         }
         if command.prefix_action.is_some() {
             let prefix = match get_prefix_from_options(ctx).await {
@@ -157,9 +274,10 @@ async fn help_single_command<U, E>(
                 // commands. Not sure there's a better way to handle this.
                 None => String::from("<prefix>"),
             };
-            invocations.push(format!("`{}{}`", prefix, command.name));
+            invocations.push(format!("`{}{}`", prefix, command.qualified_name));
+            invocations.extend(alias_invocations(command, &prefix));
             if subprefix.is_none() {
-                subprefix = Some(format!("  {}{}", prefix, command.name));
+                subprefix = Some(format!("  {}{}", prefix, command.qualified_name));
             }
         }
         if command.context_menu_name.is_some() && command.context_menu_action.is_some() {
@@ -174,7 +292,14 @@ async fn help_single_command<U, E>(
         assert!(!invocations.is_empty());
         let invocations = invocations.join("\n");
 
-        let mut text = match (&command.description, &command.help_text) {
+        let description = command.description.as_deref().map(|description| {
+            localized_description(
+                Some(description),
+                &command.description_localizations,
+                locale,
+            )
+        });
+        let mut text = match (description, &command.help_text) {
             (Some(description), Some(help_text)) => {
                 if config.include_description {
                     format!("{}\n\n{}", description, help_text)
@@ -191,7 +316,11 @@ async fn help_single_command<U, E>(
             let mut parameterlist = TwoColumnList::new();
             for parameter in &command.parameters {
                 let name = parameter.name.clone();
-                let description = parameter.description.as_deref().unwrap_or("");
+                let description = localized_description(
+                    parameter.description.as_deref(),
+                    &parameter.description_localizations,
+                    locale,
+                );
                 let description = format!(
                     "({}) {}",
                     if parameter.required {
@@ -216,6 +345,7 @@ async fn help_single_command<U, E>(
                 &mut commandlist,
                 command,
                 &subprefix.unwrap_or_else(|| String::from("  ")),
+                locale,
             );
             text += &commandlist.into_string();
             text += "```";
@@ -238,6 +368,7 @@ fn preformat_subcommands<U, E>(
     commands: &mut TwoColumnList,
     command: &crate::Command<U, E>,
     prefix: &str,
+    locale: Option<&str>,
 ) {
     let as_context_command = command.slash_action.is_none() && command.prefix_action.is_none();
     for subcommand in &command.subcommands {
@@ -250,7 +381,12 @@ fn preformat_subcommands<U, E>(
         } else {
             format!("{} {}", prefix, subcommand.name)
         };
-        let description = subcommand.description.as_deref().unwrap_or("").to_string();
+        let description = localized_description(
+            subcommand.description.as_deref(),
+            &subcommand.description_localizations,
+            locale,
+        )
+        .to_string();
         commands.push_two_colums(command, description);
         // We could recurse here, but things can get cluttered quickly.
         // Instead, we show (using this function) subsubcommands when
@@ -265,6 +401,7 @@ fn preformat_command<U, E>(
     command: &crate::Command<U, E>,
     indent: &str,
     options_prefix: Option<&str>,
+    locale: Option<&str>,
 ) {
     let prefix = if command.slash_action.is_some() {
         String::from("/")
@@ -277,12 +414,14 @@ fn preformat_command<U, E>(
     };
 
     let prefix = format!("{}{}{}", indent, prefix, command.name);
-    commands.push_two_colums(
-        prefix.clone(),
-        command.description.as_deref().unwrap_or("").to_string(),
+    let description = localized_description(
+        command.description.as_deref(),
+        &command.description_localizations,
+        locale,
     );
+    commands.push_two_colums(prefix.clone(), description.to_string());
     if config.show_subcommands {
-        preformat_subcommands(commands, command, &prefix)
+        preformat_subcommands(commands, command, &prefix, locale)
     }
 }
 
@@ -294,7 +433,7 @@ async fn generate_all_commands<U, E>(
     config: &HelpConfiguration<'_>,
 ) -> Result<String, serenity::Error> {
     let mut categories = indexmap::IndexMap::<Option<&str>, Vec<&crate::Command<U, E>>>::new();
-    for cmd in &ctx.framework().options().commands {
+    for cmd in ctx.framework().commands().iter() {
         categories
             .entry(cmd.category.as_deref())
             .or_default()
@@ -302,6 +441,21 @@ async fn generate_all_commands<U, E>(
     }
 
     let options_prefix = get_prefix_from_options(ctx).await;
+    let locale = ctx.effective_locale();
+
+    let cache_key = config.cache.map(|_| HelpCacheKey {
+        generation: ctx.framework().commands().generation(),
+        prefix: options_prefix.clone(),
+        locale: locale.as_deref().map(str::to_owned),
+        extra_text_at_bottom: config.extra_text_at_bottom.to_owned(),
+        show_context_menu_commands: config.show_context_menu_commands,
+        show_subcommands: config.show_subcommands,
+    });
+    if let (Some(cache), Some(key)) = (config.cache, &cache_key) {
+        if let Some(menu) = cache.get(key) {
+            return Ok(menu);
+        }
+    }
 
     let mut menu = String::from("```\n");
 
@@ -324,6 +478,7 @@ async fn generate_all_commands<U, E>(
                 command,
                 "  ",
                 options_prefix.as_deref(),
+                locale.as_deref(),
             );
         }
     }
@@ -332,7 +487,7 @@ async fn generate_all_commands<U, E>(
     if config.show_context_menu_commands {
         menu += "\nContext menu commands:\n";
 
-        for command in &ctx.framework().options().commands {
+        for command in ctx.framework().commands().iter() {
             let name = format_context_menu_name(command);
             if name.is_none() {
                 continue;
@@ -345,6 +500,9 @@ async fn generate_all_commands<U, E>(
     menu += config.extra_text_at_bottom;
     menu += "\n```";
 
+    if let (Some(cache), Some(key)) = (config.cache, cache_key) {
+        cache.insert(key, menu.clone());
+    }
     Ok(menu)
 }
 
@@ -425,3 +583,26 @@ pub async fn help<U, E>(
         None => help_all_commands(ctx, config).await,
     }
 }
+
+#[cfg(test)]
+#[test]
+fn test_help_cache() {
+    let cache = HelpCache::new();
+    let key = |generation, prefix: &str| HelpCacheKey {
+        generation,
+        prefix: Some(prefix.into()),
+        locale: None,
+        extra_text_at_bottom: String::new(),
+        show_context_menu_commands: false,
+        show_subcommands: false,
+    };
+    cache.insert(key(1, "~"), "overview".into());
+    cache.insert(key(1, "!"), "other overview".into());
+    assert_eq!(cache.get(&key(1, "~")).as_deref(), Some("overview"));
+    assert_eq!(cache.get(&key(2, "~")), None);
+
+    // Changed commands replace the overviews of the old commands
+    cache.insert(key(2, "~"), "new overview".into());
+    assert_eq!(cache.get(&key(1, "!")), None);
+    assert_eq!(cache.get(&key(2, "~")).as_deref(), Some("new overview"));
+}
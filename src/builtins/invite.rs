@@ -0,0 +1,64 @@
+//! Contains a command that responds with the bot's invite link, see [`invite`]
+
+use crate::{serenity_prelude as serenity, CreateReply};
+
+/// Returns the union of [`crate::Command::required_bot_permissions`] of all given commands and
+/// their subcommands, i.e. the permissions the bot needs for all of its commands to work
+pub fn required_bot_permissions<U, E>(
+    commands: &[impl AsRef<crate::Command<U, E>>],
+) -> serenity::Permissions {
+    commands.iter().map(AsRef::as_ref).fold(
+        serenity::Permissions::empty(),
+        |permissions, command| {
+            permissions
+                | command.required_bot_permissions
+                | required_bot_permissions(&command.subcommands)
+        },
+    )
+}
+
+/// Responds with an OAuth2 URL to invite the bot to a server.
+///
+/// `scopes` defaults to [`serenity::Scope::Bot`] and [`serenity::Scope::ApplicationsCommands`]
+/// if empty. `permissions` defaults to the permissions required by all registered commands, see
+/// [`required_bot_permissions`].
+///
+/// ```rust,no_run
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// # type Context<'a> = poise::Context<'a, (), Error>;
+/// #[poise::command(slash_command, prefix_command)]
+/// pub async fn invite(ctx: Context<'_>) -> Result<(), Error> {
+///     poise::builtins::invite(ctx, &[], None).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn invite<U, E>(
+    ctx: crate::Context<'_, U, E>,
+    scopes: &[serenity::Scope],
+    permissions: Option<serenity::Permissions>,
+) -> Result<(), serenity::Error> {
+    let application_id = match ctx.http().application_id() {
+        Some(application_id) => application_id,
+        None => ctx.http().get_current_application_info().await?.id,
+    };
+    let scopes = match scopes {
+        [] => &[serenity::Scope::Bot, serenity::Scope::ApplicationsCommands],
+        scopes => scopes,
+    };
+    let permissions =
+        permissions.unwrap_or_else(|| required_bot_permissions(ctx.framework().commands()));
+
+    let url = serenity::CreateBotAuthParameters::new()
+        .client_id(application_id)
+        .scopes(scopes)
+        .permissions(permissions)
+        .build();
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!("[Invite me to your server!](<{}>)", url))
+            .ephemeral(true),
+    )
+    .await?;
+    Ok(())
+}
@@ -3,21 +3,70 @@
 //! This file provides sample commands and utility functions like help menus or error handlers to
 //! use as a starting point for the framework.
 
+mod autocomplete;
+pub use autocomplete::*;
+
+mod command_stats;
+pub use command_stats::*;
+
+mod command_tree;
+pub use command_tree::*;
+
+mod docs;
+pub use docs::*;
+
+mod error_embed;
+pub use error_embed::*;
+
 mod help;
 pub use help::*;
 
+mod invite;
+pub use invite::*;
+
+mod ping;
+pub use ping::*;
+
 mod pretty_help;
 pub use pretty_help::*;
 
+mod prefix;
+pub use prefix::*;
+
 mod register;
 pub use register::*;
 
+#[cfg(feature = "cache")]
+mod servers;
+#[cfg(feature = "cache")]
+pub use servers::*;
+
+mod shutdown;
+pub use shutdown::*;
+
+#[cfg(feature = "cache")]
+mod stats;
+#[cfg(feature = "cache")]
+pub use stats::*;
+
+mod toggle_command;
+pub use toggle_command::*;
+
+mod user_facing;
+pub use user_facing::*;
+
+mod validate;
+pub use validate::*;
+
 #[cfg(any(feature = "chrono", feature = "time"))]
 mod paginate;
 #[cfg(any(feature = "chrono", feature = "time"))]
 pub use paginate::*;
 
-use crate::{serenity::CreateAllowedMentions, serenity_prelude as serenity, CreateReply};
+use crate::{
+    serenity::CreateAllowedMentions, serenity_prelude as serenity, structs::fill_placeholders,
+    CreateReply,
+};
 
 /// An error handler that logs errors either via the [`tracing`] crate or via a Discord message. Set
 /// up a logger (e.g. `env_logger::init()`) or a tracing subscriber
@@ -26,6 +75,9 @@ use crate::{serenity::CreateAllowedMentions, serenity_prelude as serenity, Creat
 /// If the user invoked the command wrong ([`crate::FrameworkError::ArgumentParse`]), the command
 /// help is displayed and the user is directed to the help menu.
 ///
+/// See [`on_error_embed`] for a variant that responds with embeds, and [`on_error_user_facing`] to
+/// let your error type decide the response to command errors.
+///
 /// Can return an error if sending the Discord error message failed. You can decide for yourself
 /// how to handle this, for example:
 /// ```rust,no_run
@@ -38,6 +90,12 @@ use crate::{serenity::CreateAllowedMentions, serenity_prelude as serenity, Creat
 pub async fn on_error<U, E: std::fmt::Display + std::fmt::Debug>(
     error: crate::FrameworkError<'_, U, E>,
 ) -> Result<(), serenity::Error> {
+    /// Returns the error texts for the locale of the invoking user
+    fn messages<U, E>(ctx: crate::Context<'_, U, E>) -> &crate::ErrorMessageSet {
+        let error_messages = &ctx.framework().options.error_messages;
+        error_messages.get(ctx.effective_locale().as_deref())
+    }
+
     match error {
         crate::FrameworkError::Setup { error, .. } => {
             eprintln!("Error in user data setup: {}", error);
@@ -70,37 +128,78 @@ pub async fn on_error<U, E: std::fmt::Display + std::fmt::Debug>(
                 .iter()
                 .map(|s| &*s.name)
                 .collect::<Vec<_>>();
-            let response = format!(
-                "You must specify one of the following subcommands: {}",
-                subcommands.join(", ")
+            let response = fill_placeholders(
+                &messages(ctx).subcommand_required,
+                &[("subcommands", &subcommands.join(", "))],
             );
             ctx.send(CreateReply::default().content(response).ephemeral(true))
                 .await?;
         }
-        crate::FrameworkError::CommandPanic { ctx, payload: _ } => {
+        crate::FrameworkError::CommandPanic { ctx, payload } => {
+            tracing::error!(
+                "panic in command `{}`: {}",
+                ctx.command().qualified_name,
+                payload.as_deref().unwrap_or("<non-string payload>")
+            );
+
             // Not showing the payload to the user because it may contain sensitive info
             let embed = serenity::CreateEmbed::default()
                 .title("Internal error")
                 .color((255, 0, 0))
-                .description("An unexpected internal error has occurred.");
+                .description(&messages(ctx).internal_error);
 
             ctx.send(CreateReply::default().embed(embed).ephemeral(true))
                 .await?;
         }
-        crate::FrameworkError::ArgumentParse { ctx, input, error } => {
+        crate::FrameworkError::ArgumentParse {
+            ctx,
+            input,
+            parameter,
+            error,
+        } => {
             // If we caught an argument parse error, give a helpful error message with the
             // command explanation if available
             let usage = match &ctx.command().help_text {
                 Some(help_text) => &**help_text,
-                None => "Please check the help menu for usage information",
+                None => &messages(ctx).invalid_arguments_help,
             };
-            let response = if let Some(input) = input {
-                format!(
-                    "**Cannot parse `{}` as argument: {}**\n{}",
-                    input, error, usage
-                )
-            } else {
-                format!("**{}**\n{}", error, usage)
+            let parameter = parameter.and_then(|name| {
+                let command = ctx.command();
+                let param = command.parameters.iter().find(|p| p.name == name)?;
+                Some(crate::Command::format_parameter(param))
+            });
+            let error = error.to_string();
+            let response = match (input, parameter) {
+                (Some(input), Some(parameter)) => format!(
+                    "**{}**\n{}",
+                    fill_placeholders(
+                        &messages(ctx).argument_parse,
+                        &[
+                            ("parameter", &parameter),
+                            ("input", &input),
+                            ("error", &error)
+                        ],
+                    ),
+                    usage
+                ),
+                (Some(input), None) => format!(
+                    "**{}**\n{}",
+                    fill_placeholders(
+                        &messages(ctx).argument_parse_unknown_parameter,
+                        &[("input", &input), ("error", &error)],
+                    ),
+                    usage
+                ),
+                (None, Some(parameter)) => format!("**`{}`: {}**\n{}", parameter, error, usage),
+                (None, None) => format!("**{}**\n{}", error, usage),
+            };
+            // Show how the command is used in prefix invocations, where there's no Discord UI
+            // listing the parameters
+            let response = match ctx {
+                crate::Context::Prefix(_) => {
+                    format!("`{}{}`\n{}", ctx.prefix(), ctx.command().usage(), response)
+                }
+                crate::Context::Application(_) => response,
             };
 
             let mentions = CreateAllowedMentions::new()
@@ -122,7 +221,15 @@ pub async fn on_error<U, E: std::fmt::Display + std::fmt::Debug>(
                 description,
             );
         }
-        crate::FrameworkError::CommandCheckFailed { ctx, error } => {
+        crate::FrameworkError::CommandCheckFailed {
+            ctx,
+            error: None,
+            message: Some(message),
+        } => {
+            ctx.send(CreateReply::default().content(message).ephemeral(true))
+                .await?;
+        }
+        crate::FrameworkError::CommandCheckFailed { ctx, error, .. } => {
             tracing::error!(
                 "A command check failed in command {} for user {}: {:?}",
                 ctx.command().name,
@@ -134,20 +241,28 @@ pub async fn on_error<U, E: std::fmt::Display + std::fmt::Debug>(
             remaining_cooldown,
             ctx,
         } => {
-            let msg = format!(
-                "You're too fast. Please wait {} seconds before retrying",
-                remaining_cooldown.as_secs()
+            let msg = fill_placeholders(
+                &messages(ctx).cooldown_hit,
+                &[("seconds", &remaining_cooldown.as_secs().to_string())],
             );
             ctx.send(CreateReply::default().content(msg).ephemeral(true))
                 .await?;
         }
+        crate::FrameworkError::AlreadyRunning { scope, ctx } => {
+            let msg = match scope {
+                crate::ConcurrencyScope::User => &messages(ctx).already_running_user,
+                _ => &messages(ctx).already_running,
+            };
+            ctx.send(CreateReply::default().content(msg).ephemeral(true))
+                .await?;
+        }
         crate::FrameworkError::MissingBotPermissions {
             missing_permissions,
             ctx,
         } => {
-            let msg = format!(
-                "Command cannot be executed because the bot is lacking permissions: {}",
-                missing_permissions,
+            let msg = fill_placeholders(
+                &messages(ctx).missing_bot_permissions,
+                &[("permissions", &missing_permissions.to_string())],
             );
             ctx.send(CreateReply::default().content(msg).ephemeral(true))
                 .await?;
@@ -156,40 +271,46 @@ pub async fn on_error<U, E: std::fmt::Display + std::fmt::Debug>(
             missing_permissions,
             ctx,
         } => {
+            let command = format!("{}{}", ctx.prefix(), ctx.command().name);
             let response = if let Some(missing_permissions) = missing_permissions {
-                format!(
-                    "You're lacking permissions for `{}{}`: {}",
-                    ctx.prefix(),
-                    ctx.command().name,
-                    missing_permissions,
+                fill_placeholders(
+                    &messages(ctx).missing_user_permissions,
+                    &[
+                        ("command", &command),
+                        ("permissions", &missing_permissions.to_string()),
+                    ],
                 )
             } else {
-                format!(
-                    "You may be lacking permissions for `{}{}`. Not executing for safety",
-                    ctx.prefix(),
-                    ctx.command().name,
+                fill_placeholders(
+                    &messages(ctx).missing_user_permissions_unknown,
+                    &[("command", &command)],
                 )
             };
             ctx.send(CreateReply::default().content(response).ephemeral(true))
                 .await?;
         }
         crate::FrameworkError::NotAnOwner { ctx } => {
-            let response = "Only bot owners can call this command";
+            let response = &messages(ctx).not_an_owner;
             ctx.send(CreateReply::default().content(response).ephemeral(true))
                 .await?;
         }
         crate::FrameworkError::GuildOnly { ctx } => {
-            let response = "You cannot run this command in DMs.";
+            let response = &messages(ctx).guild_only;
             ctx.send(CreateReply::default().content(response).ephemeral(true))
                 .await?;
         }
         crate::FrameworkError::DmOnly { ctx } => {
-            let response = "You cannot run this command outside DMs.";
+            let response = &messages(ctx).dm_only;
             ctx.send(CreateReply::default().content(response).ephemeral(true))
                 .await?;
         }
         crate::FrameworkError::NsfwOnly { ctx } => {
-            let response = "You cannot run this command outside NSFW channels.";
+            let response = &messages(ctx).nsfw_only;
+            ctx.send(CreateReply::default().content(response).ephemeral(true))
+                .await?;
+        }
+        crate::FrameworkError::CommandDisabled { ctx } => {
+            let response = &messages(ctx).command_disabled;
             ctx.send(CreateReply::default().content(response).ephemeral(true))
                 .await?;
         }
@@ -200,6 +321,41 @@ pub async fn on_error<U, E: std::fmt::Display + std::fmt::Debug>(
                 error
             );
         }
+        crate::FrameworkError::UnknownCommand {
+            msg_content,
+            prefix,
+            suggestion: Some(suggestion),
+            ctx,
+            msg,
+            framework,
+            ..
+        } => {
+            let command_name = msg_content.split_whitespace().next().unwrap_or_default();
+            let response = fill_placeholders(
+                &framework
+                    .options
+                    .error_messages
+                    .default
+                    .unknown_command_suggestion,
+                &[
+                    ("command", &format!("{}{}", prefix, command_name)),
+                    ("suggestion", &format!("{}{}", prefix, suggestion)),
+                ],
+            );
+            let mentions = CreateAllowedMentions::new()
+                .everyone(false)
+                .all_roles(false)
+                .all_users(false);
+            msg.channel_id
+                .send_message(
+                    ctx,
+                    serenity::CreateMessage::new()
+                        .content(response)
+                        .reference_message(msg)
+                        .allowed_mentions(mentions),
+                )
+                .await?;
+        }
         crate::FrameworkError::UnknownCommand {
             msg_content,
             prefix,
@@ -217,12 +373,38 @@ pub async fn on_error<U, E: std::fmt::Display + std::fmt::Debug>(
         crate::FrameworkError::NonCommandMessage { error, .. } => {
             tracing::warn!("error in non-command message handler: {}", error);
         }
+        crate::FrameworkError::ScheduledTask {
+            error, task_name, ..
+        } => {
+            tracing::error!("error in scheduled task `{}`: {}", task_name, error);
+        }
+        crate::FrameworkError::ComponentHandler { error, ctx } => {
+            tracing::warn!(
+                "error in component handler for custom ID `{}`: {}",
+                ctx.interaction.data.custom_id,
+                error
+            );
+        }
         crate::FrameworkError::__NonExhaustive(unreachable) => match unreachable {},
     }
 
     Ok(())
 }
 
+/// Tells the user that only bot owners can use this command, unless they are one. Returns whether
+/// the user is a bot owner
+async fn ensure_owner<U, E>(ctx: crate::Context<'_, U, E>) -> Result<bool, serenity::Error> {
+    if ctx.framework().options().owners.contains(&ctx.author().id) {
+        return Ok(true);
+    }
+    let error_messages = &ctx.framework().options().error_messages;
+    let not_an_owner = &error_messages
+        .get(ctx.effective_locale().as_deref())
+        .not_an_owner;
+    ctx.say(not_an_owner.clone()).await?;
+    Ok(false)
+}
+
 /// An autocomplete function that can be used for the command parameter in your help function.
 ///
 /// See `examples/feature_showcase` for an example
@@ -232,97 +414,8 @@ pub async fn autocomplete_command<'a, U, E>(
     partial: &'a str,
 ) -> impl Iterator<Item = String> + 'a {
     ctx.framework()
-        .options()
-        .commands
+        .commands()
         .iter()
         .filter(move |cmd| cmd.name.starts_with(partial))
         .map(|cmd| cmd.name.to_string())
 }
-
-/// Lists servers of which the bot is a member of, including their member counts, sorted
-/// descendingly by member count.
-///
-/// Non-[public](https://support.discord.com/hc/en-us/articles/360030843331-Enabling-Server-Discovery)
-/// guilds are hidden to preserve privacy. When the command is invoked by the bot
-/// owner as an application command, the response will be made ephemeral and private guilds are
-/// unhidden.
-///
-/// Example:
-/// > I am currently in three servers!
-/// > - **A public server** (7123 members)
-/// > - [3 private servers with 456 members total]
-#[cfg(feature = "cache")]
-pub async fn servers<U, E>(ctx: crate::Context<'_, U, E>) -> Result<(), serenity::Error> {
-    use std::fmt::Write as _;
-
-    let show_private_guilds = ctx.framework().options().owners.contains(&ctx.author().id);
-
-    // Aggregate all guilds and sort them by size
-    let mut hidden_guilds = 0;
-    let mut hidden_guilds_members = 0;
-    let mut shown_guilds = Vec::<(String, u64)>::new();
-    for guild_id in ctx.cache().guilds() {
-        match ctx.cache().guild(guild_id) {
-            Some(guild) => {
-                let is_public = guild.features.iter().any(|x| x == "DISCOVERABLE");
-                if !is_public && !show_private_guilds {
-                    hidden_guilds += 1; // private guild whose name and size shouldn't be exposed
-                } else {
-                    shown_guilds.push((guild.name.clone(), guild.member_count))
-                }
-            }
-            None => hidden_guilds += 1, // uncached guild
-        }
-    }
-    shown_guilds.sort_by_key(|(_, member)| u64::MAX - member); // sort largest guilds first
-
-    // Iterate guilds and build up the response message line by line
-    let mut response = format!(
-        "I am currently in {} servers!\n",
-        shown_guilds.len() + hidden_guilds
-    );
-    if show_private_guilds {
-        response.insert_str(0, "_Showing private guilds because you are a bot owner_\n");
-    }
-    let mut guilds = shown_guilds.into_iter().peekable();
-    while let Some((name, member_count)) = guilds.peek() {
-        let line = format!("- **{}** ({} members)\n", name, member_count);
-
-        // Make sure we don't exceed a certain number of characters below the 2000 char limit so
-        // we have enough space for the remaining servers line
-        if response.len() + line.len() > 1940 {
-            for (_remaining_guild_name, members) in guilds {
-                hidden_guilds += 1;
-                hidden_guilds_members += members;
-            }
-            break;
-        }
-
-        response += &line;
-        guilds.next(); // advance peekable iterator
-    }
-    if hidden_guilds > 0 {
-        let _ = writeln!(
-            response,
-            "- {} remaining servers with {} members total",
-            hidden_guilds, hidden_guilds_members
-        );
-    }
-
-    // Final safe guard (shouldn't be hit at the time of writing)
-    if response.len() > 2000 {
-        let mut truncate_at = 2000;
-        while !response.is_char_boundary(truncate_at) {
-            truncate_at -= 1;
-        }
-        response.truncate(truncate_at);
-    }
-
-    // If we show sensitive data (private guilds), it mustn't be made public, so it's ephemeral
-    let reply = CreateReply::default()
-        .content(response)
-        .ephemeral(show_private_guilds);
-
-    ctx.send(reply).await?;
-    Ok(())
-}
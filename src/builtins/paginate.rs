@@ -35,6 +35,15 @@ use crate::serenity_prelude as serenity;
 pub async fn paginate<U, E>(
     ctx: crate::Context<'_, U, E>,
     pages: &[&str],
+) -> Result<(), serenity::Error> {
+    paginate_with_visibility(ctx, pages, false).await
+}
+
+/// Like [`paginate`], but can send the pages as an ephemeral response
+pub(super) async fn paginate_with_visibility<U, E>(
+    ctx: crate::Context<'_, U, E>,
+    pages: &[&str],
+    ephemeral: bool,
 ) -> Result<(), serenity::Error> {
     // Define some unique identifiers for the navigation buttons
     let ctx_id = ctx.id();
@@ -51,6 +60,7 @@ pub async fn paginate<U, E>(
         crate::CreateReply::default()
             .embed(serenity::CreateEmbed::default().description(pages[0]))
             .components(vec![components])
+            .ephemeral(ephemeral)
     };
 
     ctx.send(reply).await?;
@@ -0,0 +1,59 @@
+//! Contains a ping command that measures gateway and REST latency
+
+use crate::{serenity_prelude as serenity, CreateReply};
+
+/// Formats a latency for [`ping`], like `42 ms`
+fn format_latency(latency: std::time::Duration) -> String {
+    format!("{} ms", latency.as_millis())
+}
+
+/// Responds with the gateway heartbeat latency of the current shard and the REST round-trip time.
+///
+/// The REST round-trip time is measured locally as the duration of an edit of the reply, so it
+/// isn't skewed by clock differences between the bot and Discord, unlike comparing message
+/// timestamps. The heartbeat latency is unknown until the shard has acknowledged its first
+/// heartbeat.
+///
+/// Example:
+/// > 🏓 Pong!
+/// > Gateway heartbeat: **42 ms**
+/// > REST round-trip: **87 ms**
+///
+/// ```rust,no_run
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// # type Context<'a> = poise::Context<'a, (), Error>;
+/// #[poise::command(slash_command, prefix_command)]
+/// pub async fn ping(ctx: Context<'_>) -> Result<(), Error> {
+///     poise::builtins::ping(ctx).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn ping<U, E>(ctx: crate::Context<'_, U, E>) -> Result<(), serenity::Error> {
+    let gateway = match ctx.ping().await {
+        std::time::Duration::ZERO => "not measured yet".to_owned(),
+        latency => format_latency(latency),
+    };
+    let gateway_line = format!("Gateway heartbeat: **{}**", gateway);
+
+    let reply = ctx.say("🏓 Pinging...").await?;
+    let start = std::time::Instant::now();
+    reply
+        .edit(
+            ctx,
+            CreateReply::default().content(format!("🏓 Pong!\n{}", gateway_line)),
+        )
+        .await?;
+    let rest = start.elapsed();
+
+    reply
+        .edit(
+            ctx,
+            CreateReply::default().content(format!(
+                "🏓 Pong!\n{}\nREST round-trip: **{}**",
+                gateway_line,
+                format_latency(rest)
+            )),
+        )
+        .await?;
+    Ok(())
+}
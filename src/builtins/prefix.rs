@@ -0,0 +1,235 @@
+//! Per-guild prefixes that server admins can change with a command
+
+use crate::serenity_prelude as serenity;
+
+/// Error type of [`PrefixStore`] operations
+pub type PrefixStoreError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Storage of per-guild prefixes, used by [`dynamic_prefix`] and the prefix command helpers
+/// ([`get_prefix`], [`set_prefix`], [`reset_prefix`]).
+///
+/// [`InMemoryPrefixStore`] keeps prefixes until restart. To persist them, implement this trait on
+/// top of your database.
+#[async_trait::async_trait]
+pub trait PrefixStore: Send + Sync {
+    /// Returns the custom prefix of the given guild, if one is set
+    async fn get_prefix(
+        &self,
+        guild_id: serenity::GuildId,
+    ) -> Result<Option<String>, PrefixStoreError>;
+
+    /// Sets the custom prefix of the given guild, or removes it if `None`
+    async fn set_prefix(
+        &self,
+        guild_id: serenity::GuildId,
+        prefix: Option<String>,
+    ) -> Result<(), PrefixStoreError>;
+}
+
+/// A [`PrefixStore`] that keeps prefixes in memory
+#[derive(Debug, Default)]
+pub struct InMemoryPrefixStore {
+    /// Custom prefix of each guild that has one
+    prefixes: parking_lot::RwLock<std::collections::HashMap<serenity::GuildId, String>>,
+}
+
+#[async_trait::async_trait]
+impl PrefixStore for InMemoryPrefixStore {
+    async fn get_prefix(
+        &self,
+        guild_id: serenity::GuildId,
+    ) -> Result<Option<String>, PrefixStoreError> {
+        Ok(self.prefixes.read().get(&guild_id).cloned())
+    }
+
+    async fn set_prefix(
+        &self,
+        guild_id: serenity::GuildId,
+        prefix: Option<String>,
+    ) -> Result<(), PrefixStoreError> {
+        let mut prefixes = self.prefixes.write();
+        match prefix {
+            Some(prefix) => prefixes.insert(guild_id, prefix),
+            None => prefixes.remove(&guild_id),
+        };
+        Ok(())
+    }
+}
+
+/// Implement this on your user data type to give the per-guild prefix builtins access to your
+/// [`PrefixStore`]
+pub trait HasPrefixStore {
+    /// Returns the store that per-guild prefixes are kept in
+    fn prefix_store(&self) -> &dyn PrefixStore;
+}
+
+/// A ready-made [`crate::PrefixFrameworkOptions::dynamic_prefix`] callback which returns the
+/// custom prefix of the guild that the message was sent in.
+///
+/// The static [`crate::PrefixFrameworkOptions::prefix`] keeps working in addition to the custom
+/// prefix, so users who don't know the custom prefix can still reach the bot.
+///
+/// ```rust
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// struct Data {
+///     prefixes: poise::builtins::InMemoryPrefixStore,
+/// }
+/// impl poise::builtins::HasPrefixStore for Data {
+///     fn prefix_store(&self) -> &dyn poise::builtins::PrefixStore {
+///         &self.prefixes
+///     }
+/// }
+///
+/// poise::PrefixFrameworkOptions::<Data, Error> {
+///     prefix: Some("~".into()),
+///     dynamic_prefix: Some(poise::builtins::dynamic_prefix),
+///     ..Default::default()
+/// };
+/// ```
+pub fn dynamic_prefix<U, E>(
+    ctx: crate::PartialContext<'_, U, E>,
+) -> crate::BoxFuture<'_, Result<Option<String>, E>>
+where
+    U: HasPrefixStore + Send + Sync,
+    E: From<PrefixStoreError>,
+{
+    Box::pin(async move {
+        let Some(guild_id) = ctx.guild_id else {
+            return Ok(None);
+        };
+        Ok(ctx.data.prefix_store().get_prefix(guild_id).await?)
+    })
+}
+
+/// Returns the guild that prefix commands act on, or tells the user that prefixes can only be
+/// changed in guilds
+async fn prefix_guild<U: Send + Sync, E>(
+    ctx: crate::Context<'_, U, E>,
+) -> Result<Option<serenity::GuildId>, PrefixStoreError> {
+    if ctx.guild_id().is_none() {
+        ctx.say("Custom prefixes can only be used in servers")
+            .await?;
+    }
+    Ok(ctx.guild_id())
+}
+
+/// Replies with the current prefix of the guild, or the framework's default prefix if the guild has
+/// none.
+///
+/// You probably want to use the prefix helpers by wrapping them in a small `prefix` command:
+/// ```rust
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// # struct Data { prefixes: poise::builtins::InMemoryPrefixStore }
+/// # impl poise::builtins::HasPrefixStore for Data {
+/// #     fn prefix_store(&self) -> &dyn poise::builtins::PrefixStore { &self.prefixes }
+/// # }
+/// # type Context<'a> = poise::Context<'a, Data, Error>;
+/// /// Manage the bot's prefix in this server
+/// #[poise::command(
+///     slash_command,
+///     prefix_command,
+///     guild_only,
+///     subcommands("prefix_get", "prefix_set", "prefix_reset"),
+///     subcommand_required
+/// )]
+/// pub async fn prefix(_: Context<'_>) -> Result<(), Error> {
+///     Ok(())
+/// }
+///
+/// /// Show the current prefix
+/// #[poise::command(slash_command, prefix_command, rename = "get")]
+/// pub async fn prefix_get(ctx: Context<'_>) -> Result<(), Error> {
+///     poise::builtins::get_prefix(ctx).await
+/// }
+///
+/// /// Change the prefix
+/// #[poise::command(
+///     slash_command,
+///     prefix_command,
+///     rename = "set",
+///     required_permissions = "MANAGE_GUILD"
+/// )]
+/// pub async fn prefix_set(
+///     ctx: Context<'_>,
+///     #[description = "New prefix"] prefix: String,
+/// ) -> Result<(), Error> {
+///     poise::builtins::set_prefix(ctx, prefix).await
+/// }
+///
+/// /// Go back to the default prefix
+/// #[poise::command(
+///     slash_command,
+///     prefix_command,
+///     rename = "reset",
+///     required_permissions = "MANAGE_GUILD"
+/// )]
+/// pub async fn prefix_reset(ctx: Context<'_>) -> Result<(), Error> {
+///     poise::builtins::reset_prefix(ctx).await
+/// }
+/// ```
+pub async fn get_prefix<U, E>(ctx: crate::Context<'_, U, E>) -> Result<(), PrefixStoreError>
+where
+    U: HasPrefixStore + Send + Sync,
+{
+    let Some(guild_id) = prefix_guild(ctx).await? else {
+        return Ok(());
+    };
+
+    let custom_prefix = ctx.data().prefix_store().get_prefix(guild_id).await?;
+    let default_prefix = ctx.framework().options().prefix_options.prefix.as_deref();
+    let response = match (custom_prefix, default_prefix) {
+        (Some(prefix), _) => format!("The prefix in this server is `{}`", prefix),
+        (None, Some(prefix)) => format!("This server uses the default prefix `{}`", prefix),
+        (None, None) => "This server has no custom prefix".to_owned(),
+    };
+    ctx.say(response).await?;
+    Ok(())
+}
+
+/// Sets the prefix of the guild and confirms it to the user. Prefixes must not be empty or contain
+/// whitespace.
+///
+/// Make sure to restrict who can use this, e.g. with `required_permissions = "MANAGE_GUILD"`. See
+/// [`get_prefix`] for an example.
+pub async fn set_prefix<U, E>(
+    ctx: crate::Context<'_, U, E>,
+    prefix: String,
+) -> Result<(), PrefixStoreError>
+where
+    U: HasPrefixStore + Send + Sync,
+{
+    let Some(guild_id) = prefix_guild(ctx).await? else {
+        return Ok(());
+    };
+
+    if prefix.is_empty() || prefix.contains(char::is_whitespace) {
+        ctx.say("The prefix must not be empty or contain spaces")
+            .await?;
+        return Ok(());
+    }
+
+    ctx.data()
+        .prefix_store()
+        .set_prefix(guild_id, Some(prefix.clone()))
+        .await?;
+    ctx.say(format!("The prefix in this server is now `{}`", prefix))
+        .await?;
+    Ok(())
+}
+
+/// Removes the custom prefix of the guild, so that the framework's default prefix applies again.
+///
+/// Make sure to restrict who can use this, e.g. with `required_permissions = "MANAGE_GUILD"`. See
+/// [`get_prefix`] for an example.
+pub async fn reset_prefix<U, E>(ctx: crate::Context<'_, U, E>) -> Result<(), PrefixStoreError>
+where
+    U: HasPrefixStore + Send + Sync,
+{
+    let Some(guild_id) = prefix_guild(ctx).await? else {
+        return Ok(());
+    };
+
+    ctx.data().prefix_store().set_prefix(guild_id, None).await?;
+    ctx.say("The prefix in this server has been reset").await?;
+    Ok(())
+}
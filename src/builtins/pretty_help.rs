@@ -54,7 +54,7 @@ async fn pretty_help_all_commands<U, E>(
     ctx: crate::Context<'_, U, E>,
     config: PrettyHelpConfiguration<'_>,
 ) -> Result<(), serenity::Error> {
-    let commands = ctx.framework().options().commands.iter().filter(|cmd| {
+    let commands = ctx.framework().commands().iter().filter(|cmd| {
         !cmd.hide_in_help
             && (cmd.prefix_action.is_some()
                 || cmd.slash_action.is_some()
@@ -152,11 +152,12 @@ async fn pretty_help_single_command<U, E>(
     command_name: &str,
     config: PrettyHelpConfiguration<'_>,
 ) -> Result<(), serenity::Error> {
-    let commands = &ctx.framework().options().commands;
+    let commands = ctx.framework().commands();
 
     // Try interpret the command name as a context menu command first
     let command = commands
         .iter()
+        .map(|cmd| &**cmd)
         .find(|cmd| {
             cmd.context_menu_name
                 .as_ref()
@@ -180,8 +181,8 @@ async fn pretty_help_single_command<U, E>(
     let mut subprefix = None;
 
     if command.slash_action.is_some() {
-        invocations.push(format!("`/{}`", command.name));
-        subprefix = Some(format!("> `/{}`", command.name));
+        invocations.push(format!("`/{}`", command.qualified_name));
+        subprefix = Some(format!("> `/{}`", command.qualified_name));
     }
     if command.prefix_action.is_some() {
         let prefix = super::help::get_prefix_from_options(ctx)
@@ -189,8 +190,9 @@ async fn pretty_help_single_command<U, E>(
             // This can happen if the prefix is dynamic, and the callback fails
             // due to help being invoked with slash or context menu commands.
             .unwrap_or_else(|| String::from("<prefix>"));
-        invocations.push(format!("`{}{}`", prefix, command.name));
-        subprefix = subprefix.or(Some(format!("> `{}{}`", prefix, command.name)));
+        invocations.push(format!("`{}{}`", prefix, command.qualified_name));
+        invocations.extend(super::help::alias_invocations(command, &prefix));
+        subprefix = subprefix.or(Some(format!("> `{}{}`", prefix, command.qualified_name)));
     }
     if command.context_menu_name.is_some() && command.context_menu_action.is_some() {
         let kind = match command.context_menu_action {
@@ -1,23 +1,28 @@
 //! Utilities for registering application commands
 
+use std::sync::Arc;
+
 use crate::serenity_prelude as serenity;
 
 /// Collects all commands into a [`Vec<serenity::CreateCommand>`] builder, which can be used
 /// to register the commands on Discord
 ///
+/// Takes the commands of the framework, see [`crate::Framework::commands`] and
+/// [`crate::FrameworkContext::commands`].
+///
 /// Also see [`register_application_commands_buttons`] for a ready to use register command
 ///
 /// ```rust,no_run
 /// # use poise::serenity_prelude as serenity;
 /// # async fn foo(ctx: poise::Context<'_, (), ()>) -> Result<(), serenity::Error> {
-/// let commands = &ctx.framework().options().commands;
+/// let commands = ctx.framework().commands();
 /// let create_commands = poise::builtins::create_application_commands(commands);
 ///
 /// serenity::Command::set_global_commands(ctx, create_commands).await?;
 /// # Ok(()) }
 /// ```
 pub fn create_application_commands<U, E>(
-    commands: &[crate::Command<U, E>],
+    commands: &[Arc<crate::Command<U, E>>],
 ) -> Vec<serenity::CreateCommand> {
     /// We decided to extract context menu commands recursively, despite the subcommand hierarchy
     /// not being preserved. Because it's more confusing to just silently discard context menu
@@ -37,6 +42,7 @@ pub fn create_application_commands<U, E>(
 
     let mut commands_builder = Vec::with_capacity(commands.len());
     for command in commands {
+        let command: &crate::Command<U, E> = command.as_ref();
         if let Some(slash_command) = command.create_as_slash_command() {
             commands_builder.push(slash_command);
         }
@@ -45,33 +51,153 @@ pub fn create_application_commands<U, E>(
     commands_builder
 }
 
+/// Stores the IDs of freshly registered application commands in
+/// [`crate::Command::application_command_id`], which enables [`crate::Command::mention`].
+///
+/// The registration functions in this module call this automatically. If you register commands
+/// manually, pass the list returned by Discord:
+///
+/// ```rust,no_run
+/// # use poise::serenity_prelude as serenity;
+/// # async fn foo(ctx: poise::Context<'_, (), ()>) -> Result<(), serenity::Error> {
+/// let commands = ctx.framework().commands();
+/// let create_commands = poise::builtins::create_application_commands(commands);
+///
+/// let registered = serenity::Command::set_global_commands(ctx, create_commands).await?;
+/// poise::builtins::set_application_command_ids(commands, &registered);
+/// # Ok(()) }
+/// ```
+pub fn set_application_command_ids<U, E>(
+    commands: &[Arc<crate::Command<U, E>>],
+    registered: &[serenity::Command],
+) {
+    /// Subcommands are mentioned with the ID of their top-level command
+    fn set_recursively<U, E>(command: &crate::Command<U, E>, id: Option<serenity::CommandId>) {
+        *command.application_command_id.lock().unwrap() = id;
+        for subcommand in &command.subcommands {
+            set_recursively(subcommand, id);
+        }
+    }
+
+    for command in commands {
+        let command: &crate::Command<U, E> = command.as_ref();
+        let id = registered
+            .iter()
+            .find(|c| c.kind == serenity::CommandType::ChatInput && c.name == command.name)
+            .map(|c| c.id);
+        set_recursively(command, id);
+    }
+}
+
+/// Logs the violations found by [`validate_application_commands`], if any
+fn log_violations<U, E>(commands: &[impl AsRef<crate::Command<U, E>>]) {
+    for violation in crate::builtins::validate_application_commands(commands) {
+        tracing::error!("invalid application command: {}", violation);
+    }
+}
+
+/// Replies with the violations found by [`validate_application_commands`], if any. Returns whether
+/// the commands are valid
+async fn report_violations<U, E>(
+    ctx: crate::Context<'_, U, E>,
+    commands: &[impl AsRef<crate::Command<U, E>>],
+) -> Result<bool, serenity::Error> {
+    let violations = crate::builtins::validate_application_commands(commands);
+    if violations.is_empty() {
+        return Ok(true);
+    }
+
+    let mut response = String::from(":x: Can't register commands:\n");
+    for violation in &violations {
+        response += &format!("- {}\n", violation);
+    }
+    ctx.send(crate::CreateReply::default().content(response))
+        .await?;
+    Ok(false)
+}
+
 /// Registers the given list of application commands to Discord as global commands.
 ///
 /// Thin wrapper around [`create_application_commands`] that funnels the returned builder into
-/// [`serenity::Command::set_global_commands`].
+/// [`serenity::Command::set_global_commands`]. Violations found by
+/// [`validate_application_commands`] are logged, but the commands are registered anyway; call it
+/// yourself to refuse registering invalid commands.
 pub async fn register_globally<U, E>(
     http: impl AsRef<serenity::Http>,
-    commands: &[crate::Command<U, E>],
+    commands: &[Arc<crate::Command<U, E>>],
 ) -> Result<(), serenity::Error> {
+    log_violations(commands);
     let builder = create_application_commands(commands);
-    serenity::Command::set_global_commands(http, builder).await?;
+    let registered = serenity::Command::set_global_commands(http, builder).await?;
+    set_application_command_ids(commands, &registered);
     Ok(())
 }
 
 /// Registers the given list of application commands to Discord as guild-specific commands.
 ///
 /// Thin wrapper around [`create_application_commands`] that funnels the returned builder into
-/// [`serenity::GuildId::set_commands`].
+/// [`serenity::GuildId::set_commands`]. Violations found by [`validate_application_commands`]
+/// are logged, but the commands are registered anyway, like in [`register_globally`].
 pub async fn register_in_guild<U, E>(
     http: impl AsRef<serenity::Http>,
-    commands: &[crate::Command<U, E>],
+    commands: &[Arc<crate::Command<U, E>>],
     guild_id: serenity::GuildId,
 ) -> Result<(), serenity::Error> {
+    log_violations(commands);
     let builder = create_application_commands(commands);
-    guild_id.set_commands(http, builder).await?;
+    let registered = guild_id.set_commands(http, builder).await?;
+    set_application_command_ids(commands, &registered);
     Ok(())
 }
 
+/// Registers the given list of application commands as guild-specific commands in each of the
+/// given guilds, like [`register_in_guild`] for every guild.
+///
+/// Up to `concurrency` guilds are registered at the same time, so startup doesn't take longer
+/// with every guild. serenity still waits for rate limits where needed. Returns the result of
+/// every guild, in the order of `guild_ids`.
+///
+/// ```rust,no_run
+/// # async fn _f(ctx: &poise::serenity_prelude::Context, commands: &poise::CommandSet<(), ()>) {
+/// # let guild_ids = [];
+/// let results = poise::builtins::register_in_guilds(ctx, commands, &guild_ids, 8).await;
+/// for (guild_id, result) in results {
+///     if let Err(e) = result {
+///         eprintln!("couldn't register commands in {}: {}", guild_id, e);
+///     }
+/// }
+/// # }
+/// ```
+pub async fn register_in_guilds<U, E>(
+    http: impl AsRef<serenity::Http>,
+    commands: &[Arc<crate::Command<U, E>>],
+    guild_ids: &[serenity::GuildId],
+    concurrency: usize,
+) -> Vec<(serenity::GuildId, Result<(), serenity::Error>)> {
+    use futures_util::StreamExt as _;
+
+    log_violations(commands);
+    let http = http.as_ref();
+    let builder = create_application_commands(commands);
+    let results = futures_util::stream::iter(guild_ids)
+        .map(|&guild_id| {
+            let builder = builder.clone();
+            async move { (guild_id, guild_id.set_commands(http, builder).await) }
+        })
+        .buffered(concurrency.max(1))
+        .collect::<Vec<_>>()
+        .await;
+
+    // Commands only have room for one ID, so mentions use the commands of the first guild
+    if let Some(registered) = results.iter().find_map(|(_, result)| result.as_ref().ok()) {
+        set_application_command_ids(commands, registered);
+    }
+    results
+        .into_iter()
+        .map(|(guild_id, result)| (guild_id, result.map(|_| ())))
+        .collect()
+}
+
 /// _Note: you probably want [`register_application_commands_buttons`] instead; it's easier and more
 /// powerful_
 ///
@@ -95,13 +221,18 @@ pub async fn register_application_commands<U, E>(
         return Ok(());
     }
 
-    let commands_builder = create_application_commands(&ctx.framework().options().commands);
+    let commands = ctx.framework().commands();
+    if !report_violations(ctx, commands).await? {
+        return Ok(());
+    }
+    let commands_builder = create_application_commands(commands);
     let num_commands = commands_builder.len();
 
     if global {
         ctx.say(format!("Registering {num_commands} commands...",))
             .await?;
-        serenity::Command::set_global_commands(ctx, commands_builder).await?;
+        let registered = serenity::Command::set_global_commands(ctx, commands_builder).await?;
+        set_application_command_ids(commands, &registered);
     } else {
         let guild_id = match ctx.guild_id() {
             Some(x) => x,
@@ -113,7 +244,8 @@ pub async fn register_application_commands<U, E>(
 
         ctx.say(format!("Registering {num_commands} commands..."))
             .await?;
-        guild_id.set_commands(ctx, commands_builder).await?;
+        let registered = guild_id.set_commands(ctx, commands_builder).await?;
+        set_application_command_ids(commands, &registered);
     }
 
     ctx.say("Done!").await?;
@@ -151,7 +283,8 @@ pub async fn register_application_commands<U, E>(
 pub async fn register_application_commands_buttons<U, E>(
     ctx: crate::Context<'_, U, E>,
 ) -> Result<(), serenity::Error> {
-    let create_commands = create_application_commands(&ctx.framework().options().commands);
+    let commands = ctx.framework().commands();
+    let create_commands = create_application_commands(commands);
     let num_commands = create_commands.len();
 
     let is_bot_owner = ctx.framework().options().owners.contains(&ctx.author().id);
@@ -220,6 +353,10 @@ pub async fn register_application_commands_buttons<U, E>(
         }
     };
 
+    if register && !report_violations(ctx, commands).await? {
+        return Ok(());
+    }
+
     let start_time = std::time::Instant::now();
 
     if global {
@@ -228,10 +365,12 @@ pub async fn register_application_commands_buttons<U, E>(
                 ":gear: Registering {num_commands} global commands...",
             ))
             .await?;
-            serenity::Command::set_global_commands(ctx, create_commands).await?;
+            let registered = serenity::Command::set_global_commands(ctx, create_commands).await?;
+            set_application_command_ids(commands, &registered);
         } else {
             ctx.say(":gear: Unregistering global commands...").await?;
             serenity::Command::set_global_commands(ctx, vec![]).await?;
+            set_application_command_ids(commands, &[]);
         }
     } else {
         let guild_id = match ctx.guild_id() {
@@ -246,10 +385,12 @@ pub async fn register_application_commands_buttons<U, E>(
                 ":gear: Registering {num_commands} guild commands...",
             ))
             .await?;
-            guild_id.set_commands(ctx, create_commands).await?;
+            let registered = guild_id.set_commands(ctx, create_commands).await?;
+            set_application_command_ids(commands, &registered);
         } else {
             ctx.say(":gear: Unregistering guild commands...").await?;
             guild_id.set_commands(ctx, vec![]).await?;
+            set_application_command_ids(commands, &[]);
         }
     }
 
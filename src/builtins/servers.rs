@@ -0,0 +1,211 @@
+//! Contains a command that lists the servers the bot is in, see [`servers`]
+
+use std::fmt::Write as _;
+
+use crate::{serenity_prelude as serenity, CreateReply};
+
+/// Order of the servers listed by [`servers_with_options`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ServersSort {
+    /// Largest servers first
+    MemberCount,
+    /// Alphabetically by server name
+    Name,
+}
+
+/// Optional configuration for how [`servers_with_options`] lists servers
+#[derive(Debug, Clone)]
+pub struct ServersOptions {
+    /// Order of the listed servers. [`ServersSort::MemberCount`] by default
+    pub sort: ServersSort,
+    /// How many servers are shown per page. 15 by default
+    pub servers_per_page: usize,
+    /// If `true` and the command is invoked by a bot owner, all servers are additionally sent as a
+    /// CSV file with ID, name and member count. `false` by default
+    pub csv_export: bool,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for ServersOptions {
+    fn default() -> Self {
+        Self {
+            sort: ServersSort::MemberCount,
+            servers_per_page: 15,
+            csv_export: false,
+            __non_exhaustive: (),
+        }
+    }
+}
+
+/// A cached guild, as listed by [`servers_with_options`]
+struct GuildEntry {
+    /// ID of the guild
+    id: serenity::GuildId,
+    /// Name of the guild
+    name: String,
+    /// Member count of the guild
+    member_count: u64,
+    /// Whether the guild is discoverable and may be listed to anyone
+    is_public: bool,
+}
+
+/// Lists servers of which the bot is a member of, including their member counts, sorted
+/// descendingly by member count. Shorthand for [`servers_with_options`] with the default
+/// options.
+///
+/// Non-[public](https://support.discord.com/hc/en-us/articles/360030843331-Enabling-Server-Discovery)
+/// guilds are hidden to preserve privacy. When the command is invoked by the bot
+/// owner as an application command, the response will be made ephemeral and private guilds are
+/// unhidden.
+///
+/// Example:
+/// > I am currently in 4 servers with 7579 members total!
+/// > - **A public server** (7123 members)
+/// > - 3 private servers with 456 members total
+pub async fn servers<U, E>(ctx: crate::Context<'_, U, E>) -> Result<(), serenity::Error> {
+    servers_with_options(ctx, ServersOptions::default()).await
+}
+
+/// Like [`servers`], but configurable with [`ServersOptions`].
+///
+/// Servers are split into pages with navigation buttons (see [`super::paginate`]) if they don't
+/// fit into one page. Without the `chrono` feature, only the first page is shown.
+///
+/// ```rust,no_run
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// # type Context<'a> = poise::Context<'a, (), Error>;
+/// #[poise::command(slash_command, prefix_command, owners_only)]
+/// pub async fn servers(ctx: Context<'_>) -> Result<(), Error> {
+///     let options = poise::builtins::ServersOptions {
+///         sort: poise::builtins::ServersSort::Name,
+///         csv_export: true,
+///         ..Default::default()
+///     };
+///     poise::builtins::servers_with_options(ctx, options).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn servers_with_options<U, E>(
+    ctx: crate::Context<'_, U, E>,
+    options: ServersOptions,
+) -> Result<(), serenity::Error> {
+    let is_owner = ctx.framework().options().owners.contains(&ctx.author().id);
+
+    // Collect all guilds. Uncached guilds are counted, but can't be listed
+    let mut guilds = Vec::new();
+    let mut uncached_guilds = 0;
+    for guild_id in ctx.cache().guilds() {
+        match ctx.cache().guild(guild_id) {
+            Some(guild) => guilds.push(GuildEntry {
+                id: guild.id,
+                name: guild.name.clone(),
+                member_count: guild.member_count,
+                is_public: guild.features.iter().any(|x| x == "DISCOVERABLE"),
+            }),
+            None => uncached_guilds += 1,
+        }
+    }
+    match options.sort {
+        ServersSort::MemberCount => guilds.sort_by_key(|guild| u64::MAX - guild.member_count),
+        ServersSort::Name => guilds.sort_by_cached_key(|guild| guild.name.to_lowercase()),
+    }
+
+    // If we show sensitive data (private guilds), it mustn't be made public, so it's ephemeral
+    if options.csv_export && is_owner {
+        let attachment = serenity::CreateAttachment::bytes(to_csv(&guilds), "servers.csv");
+        let reply = CreateReply::default()
+            .attachment(attachment)
+            .ephemeral(true);
+        ctx.send(reply).await?;
+    }
+
+    let total_members = guilds.iter().map(|guild| guild.member_count).sum::<u64>();
+    let mut header = format!(
+        "I am currently in {} servers with {} members total!\n",
+        guilds.len() + uncached_guilds,
+        total_members
+    );
+    if is_owner {
+        header.insert_str(0, "_Showing private guilds because you are a bot owner_\n");
+    }
+
+    let (shown_guilds, hidden_guilds) = guilds
+        .into_iter()
+        .partition::<Vec<_>, _>(|guild| guild.is_public || is_owner);
+    let mut lines = shown_guilds
+        .iter()
+        .map(|guild| format!("- **{}** ({} members)", guild.name, guild.member_count))
+        .collect::<Vec<_>>();
+    let hidden_count = hidden_guilds.len() + uncached_guilds;
+    if hidden_count > 0 {
+        let hidden_members = hidden_guilds
+            .iter()
+            .map(|guild| guild.member_count)
+            .sum::<u64>();
+        lines.push(format!(
+            "- {} private servers with {} members total",
+            hidden_count, hidden_members
+        ));
+    }
+
+    let page_count = lines.chunks(options.servers_per_page.max(1)).count();
+    let pages = lines
+        .chunks(options.servers_per_page.max(1))
+        .enumerate()
+        .map(|(i, lines)| {
+            let mut page = header.clone();
+            for line in lines {
+                let _ = writeln!(page, "{}", line);
+            }
+            if page_count > 1 {
+                let _ = write!(page, "\nPage {}/{}", i + 1, page_count);
+            }
+            page
+        })
+        .collect::<Vec<_>>();
+
+    #[cfg(any(feature = "chrono", feature = "time"))]
+    if pages.len() > 1 {
+        let pages = pages.iter().map(|page| &**page).collect::<Vec<_>>();
+        return super::paginate_with_visibility(ctx, &pages, is_owner).await;
+    }
+
+    let page = pages.into_iter().next().unwrap_or(header);
+    let reply = CreateReply::default()
+        .embed(serenity::CreateEmbed::new().description(page))
+        .ephemeral(is_owner);
+    ctx.send(reply).await?;
+    Ok(())
+}
+
+/// Formats the guilds as CSV with a header row
+fn to_csv(guilds: &[GuildEntry]) -> String {
+    let mut csv = String::from("id,name,member_count\n");
+    for guild in guilds {
+        let _ = writeln!(
+            csv,
+            "{},\"{}\",{}",
+            guild.id,
+            guild.name.replace('"', "\"\""),
+            guild.member_count
+        );
+    }
+    csv
+}
+
+#[cfg(test)]
+#[test]
+fn test_to_csv() {
+    let guilds = [GuildEntry {
+        id: serenity::GuildId::new(1),
+        name: "The \"best\" server".into(),
+        member_count: 42,
+        is_public: true,
+    }];
+    assert_eq!(
+        to_csv(&guilds),
+        "id,name,member_count\n1,\"The \"\"best\"\" server\",42\n"
+    );
+}
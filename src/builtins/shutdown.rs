@@ -0,0 +1,51 @@
+//! Contains a command that shuts the bot down, see [`shutdown`]
+
+use crate::serenity_prelude as serenity;
+
+/// Shuts the bot down gracefully (see [`crate::ShutdownHandle::shutdown`]) after confirming to
+/// the invoker. Only bot owners may use this; other users are told so and nothing happens.
+///
+/// If an exit code is given, it's recorded with [`crate::ShutdownHandle::shutdown_with_exit_code`]
+/// so the process can exit with it once the client has stopped, for example to tell a process
+/// manager to restart the bot.
+///
+/// The shutdown runs in the background, because it waits for running commands to finish,
+/// including this one.
+///
+/// ```rust,no_run
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// # type Context<'a> = poise::Context<'a, (), Error>;
+/// #[poise::command(prefix_command, owners_only, hide_in_help)]
+/// pub async fn shutdown(ctx: Context<'_>, exit_code: Option<i32>) -> Result<(), Error> {
+///     poise::builtins::shutdown(ctx, exit_code).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn shutdown<U, E>(
+    ctx: crate::Context<'_, U, E>,
+    exit_code: Option<i32>,
+) -> Result<(), serenity::Error> {
+    if !super::ensure_owner(ctx).await? {
+        return Ok(());
+    }
+
+    let Some(handle) = ctx.framework().shutdown_handle() else {
+        ctx.say("Can't shut down: the bot isn't running on a poise framework")
+            .await?;
+        return Ok(());
+    };
+
+    let confirmation = match exit_code {
+        Some(exit_code) => format!("Shutting down with exit code {}...", exit_code),
+        None => "Shutting down...".to_owned(),
+    };
+    ctx.say(confirmation).await?;
+
+    tokio::spawn(async move {
+        match exit_code {
+            Some(exit_code) => handle.shutdown_with_exit_code(exit_code).await,
+            None => handle.shutdown().await,
+        }
+    });
+    Ok(())
+}
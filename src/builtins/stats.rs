@@ -0,0 +1,126 @@
+//! Contains a command that shows statistics about the bot, see [`bot_stats`]
+
+use crate::{serenity_prelude as serenity, CreateReply};
+
+/// Formats a duration like `3d 4h 12m`
+fn format_uptime(uptime: std::time::Duration) -> String {
+    let minutes = uptime.as_secs() / 60;
+    let (days, hours, minutes) = (minutes / (60 * 24), minutes / 60 % 24, minutes % 60);
+    match (days, hours) {
+        (0, 0) => format!("{}m", minutes),
+        (0, _) => format!("{}h {}m", hours, minutes),
+        _ => format!("{}d {}h {}m", days, hours, minutes),
+    }
+}
+
+/// Returns the resident memory of this process in bytes, if the platform supports it
+fn process_memory() -> Option<u64> {
+    #[cfg(target_os = "linux")]
+    {
+        let status = std::fs::read_to_string("/proc/self/status").ok()?;
+        let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+        let kilobytes = line.split_whitespace().nth(1)?.parse::<u64>().ok()?;
+        Some(kilobytes * 1024)
+    }
+    #[cfg(not(target_os = "linux"))]
+    {
+        None
+    }
+}
+
+/// Returns the version of serenity, which is only exposed as part of its user agent
+fn serenity_version() -> &'static str {
+    serenity::constants::USER_AGENT
+        .rsplit(", ")
+        .next()
+        .unwrap_or("")
+        .trim_end_matches(')')
+}
+
+/// Responds with an embed of statistics for bot operators: uptime, guild count, shards and their
+/// heartbeat latencies, cached user and channel counts, memory usage of the process and the
+/// versions of poise and serenity.
+///
+/// The uptime is counted from the creation of the [`crate::Framework`]. Memory usage is only
+/// available on Linux. Complements [`super::servers`].
+///
+/// ```rust,no_run
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// # type Context<'a> = poise::Context<'a, (), Error>;
+/// #[poise::command(slash_command, prefix_command)]
+/// pub async fn stats(ctx: Context<'_>) -> Result<(), Error> {
+///     poise::builtins::bot_stats(ctx).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn bot_stats<U, E>(ctx: crate::Context<'_, U, E>) -> Result<(), serenity::Error> {
+    let cache = ctx.cache();
+
+    let uptime = match crate::framework::started_at() {
+        Some(started_at) => format_uptime(started_at.elapsed()),
+        None => "unknown".to_owned(),
+    };
+    let memory = match process_memory() {
+        Some(bytes) => format!("{:.1} MiB", bytes as f64 / (1024.0 * 1024.0)),
+        None => "unknown".to_owned(),
+    };
+
+    let mut shard_latencies = ctx
+        .framework()
+        .shard_stats()
+        .await
+        .iter()
+        .map(|shard| {
+            let latency = match shard.latency {
+                Some(latency) => format!("{} ms", latency.as_millis()),
+                None => "-".to_owned(),
+            };
+            format!("Shard {}: {}", shard.id.0, latency)
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    // Discord limits embed field values to 1024 characters
+    if shard_latencies.len() > 1024 {
+        let mut truncate_at = 1020;
+        while !shard_latencies.is_char_boundary(truncate_at) {
+            truncate_at -= 1;
+        }
+        shard_latencies.truncate(truncate_at);
+        shard_latencies += "\n…";
+    }
+
+    let embed = serenity::CreateEmbed::new()
+        .title("Bot statistics")
+        .field("Uptime", uptime, true)
+        .field("Guilds", cache.guild_count().to_string(), true)
+        .field("Shards", cache.shard_count().to_string(), true)
+        .field("Cached users", cache.user_count().to_string(), true)
+        .field(
+            "Cached channels",
+            cache.guild_channel_count().to_string(),
+            true,
+        )
+        .field("Memory", memory, true)
+        .field("Shard latencies", shard_latencies, false)
+        .field(
+            "Versions",
+            format!(
+                "poise {}\nserenity {}",
+                env!("CARGO_PKG_VERSION"),
+                serenity_version()
+            ),
+            false,
+        );
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+#[test]
+fn test_format_uptime() {
+    let minutes = |minutes: u64| std::time::Duration::from_secs(minutes * 60);
+    assert_eq!(format_uptime(minutes(5)), "5m");
+    assert_eq!(format_uptime(minutes(2 * 60 + 5)), "2h 5m");
+    assert_eq!(format_uptime(minutes(3 * 24 * 60 + 5)), "3d 0h 5m");
+}
@@ -0,0 +1,59 @@
+//! Contains a command that enables or disables other commands at runtime, see [`toggle_command`]
+
+use crate::serenity_prelude as serenity;
+
+/// Enables or disables a command by its qualified name, like `settings reset`, without
+/// redeploying or re-registering (see [`crate::Command::enabled`]). Only bot owners may use this;
+/// other users are told so and nothing happens.
+///
+/// Disabled commands stay visible on Discord and in help, but invocations fail with
+/// [`crate::FrameworkError::CommandDisabled`]. The command running this builtin can't disable
+/// itself, since it couldn't be turned back on. Changes are lost on restart.
+///
+/// ```rust,no_run
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// # type Context<'a> = poise::Context<'a, (), Error>;
+/// #[poise::command(prefix_command, owners_only, hide_in_help)]
+/// pub async fn toggle(ctx: Context<'_>, enabled: bool, #[rest] command: String) -> Result<(), Error> {
+///     poise::builtins::toggle_command(ctx, &command, enabled).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn toggle_command<U, E>(
+    ctx: crate::Context<'_, U, E>,
+    command_name: &str,
+    enabled: bool,
+) -> Result<(), serenity::Error> {
+    if !super::ensure_owner(ctx).await? {
+        return Ok(());
+    }
+
+    let commands = ctx.framework().commands();
+    let Some(command) = commands.command_by_qualified_name(command_name.trim()) else {
+        ctx.say(format!("No command named `{}`", command_name.trim()))
+            .await?;
+        return Ok(());
+    };
+
+    let is_invoking_command = std::iter::once(ctx.command())
+        .chain(ctx.parent_commands().iter().copied())
+        .any(|invoking_command| invoking_command == command);
+    if !enabled && is_invoking_command {
+        ctx.say(format!(
+            "Can't disable `{}`, because it's needed to enable it again",
+            command.qualified_name
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let response = match (command.is_enabled(), enabled) {
+        (true, true) => format!("`{}` is already enabled", command.qualified_name),
+        (false, false) => format!("`{}` is already disabled", command.qualified_name),
+        (_, true) => format!("Enabled `{}`", command.qualified_name),
+        (_, false) => format!("Disabled `{}`", command.qualified_name),
+    };
+    command.set_enabled(enabled);
+    ctx.say(response).await?;
+    Ok(())
+}
@@ -0,0 +1,72 @@
+//! Error handler that lets the error type decide what users see, see [`UserFacingError`]
+
+use crate::{serenity_prelude as serenity, CreateReply};
+
+/// Implemented by a bot's error type to control the response that
+/// [`on_error_user_facing`] sends when a command returns that error.
+///
+/// ```rust
+/// use poise::serenity_prelude as serenity;
+///
+/// #[derive(Debug)]
+/// enum Error {
+///     NotFound(String),
+///     Database(String),
+/// }
+/// # impl std::fmt::Display for Error {
+/// #     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+/// #         write!(f, "{:?}", self)
+/// #     }
+/// # }
+///
+/// impl poise::builtins::UserFacingError for Error {
+///     fn user_reply(&self) -> Option<poise::CreateReply> {
+///         match self {
+///             Self::NotFound(what) => Some(
+///                 poise::CreateReply::default()
+///                     .content(format!("Couldn't find {}", what))
+///                     .ephemeral(true),
+///             ),
+///             Self::Database(_) => Some(
+///                 poise::CreateReply::default().embed(
+///                     serenity::CreateEmbed::new()
+///                         .title("Something went wrong")
+///                         .description("Please try again later"),
+///                 ),
+///             ),
+///         }
+///     }
+/// }
+/// ```
+pub trait UserFacingError {
+    /// Returns the response to send to the user for this error, or None to not respond at all.
+    /// The error is logged either way
+    fn user_reply(&self) -> Option<CreateReply>;
+}
+
+/// Like [`super::on_error`], but the response to errors returned from commands
+/// ([`crate::FrameworkError::Command`]) is determined by [`UserFacingError::user_reply`]. All
+/// other errors are passed on to [`super::on_error`].
+pub async fn on_error_user_facing<U, E>(
+    error: crate::FrameworkError<'_, U, E>,
+) -> Result<(), serenity::Error>
+where
+    E: UserFacingError + std::fmt::Display + std::fmt::Debug,
+{
+    let (ctx, reply) = match &error {
+        crate::FrameworkError::Command { ctx, error } => {
+            tracing::error!(
+                "error in command `{}`: {}",
+                ctx.command().qualified_name,
+                error
+            );
+            (*ctx, error.user_reply())
+        }
+        _ => return super::on_error(error).await,
+    };
+
+    if let Some(reply) = reply {
+        ctx.send(reply).await?;
+    }
+    Ok(())
+}
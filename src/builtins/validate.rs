@@ -0,0 +1,474 @@
+//! Checks application commands against Discord's limits before they are registered
+
+use crate::serenity_prelude as serenity;
+
+/// Maximum number of top-level slash commands
+const MAX_SLASH_COMMANDS: usize = 100;
+/// Maximum number of user or message context menu commands, each
+const MAX_CONTEXT_MENU_COMMANDS: usize = 15;
+/// Maximum number of options (parameters or subcommands) of a command
+const MAX_OPTIONS: usize = 25;
+/// Maximum number of choices of a parameter
+const MAX_CHOICES: usize = 25;
+/// Maximum combined length of all names, descriptions and choices of a top-level command
+const MAX_PAYLOAD_SIZE: usize = 4000;
+
+/// A way in which an application command breaks Discord's rules, as found by
+/// [`validate_application_commands`].
+///
+/// Commands are identified by their qualified name, e.g. `settings reset`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandViolation {
+    /// There are more commands of the given kind than Discord allows
+    #[non_exhaustive]
+    TooManyCommands {
+        /// Slash, user or message commands
+        kind: serenity::CommandType,
+        /// How many commands of this kind there are
+        count: usize,
+        /// How many commands of this kind Discord allows
+        max: usize,
+    },
+    /// Multiple top-level commands of the same kind have the same name
+    #[non_exhaustive]
+    DuplicateName {
+        /// Slash, user or message commands
+        kind: serenity::CommandType,
+        /// The name in question
+        name: String,
+    },
+    /// Slash commands can only be nested two levels deep (command, group, subcommand)
+    #[non_exhaustive]
+    TooDeeplyNested {
+        /// The command in question
+        command: String,
+    },
+    /// A name is empty, longer than 32 characters, or (for slash commands) contains uppercase
+    /// letters or characters other than letters, digits, `-` and `_`
+    #[non_exhaustive]
+    InvalidName {
+        /// The command in question
+        command: String,
+        /// The parameter in question, if the name belongs to a parameter
+        parameter: Option<String>,
+        /// The offending name, which may be a localized name
+        name: String,
+    },
+    /// A description is empty or longer than 100 characters
+    #[non_exhaustive]
+    InvalidDescription {
+        /// The command in question
+        command: String,
+        /// The parameter in question, if the description belongs to a parameter
+        parameter: Option<String>,
+        /// The offending description, which may be a localized description
+        description: String,
+    },
+    /// A command has more than 25 parameters or subcommands
+    #[non_exhaustive]
+    TooManyOptions {
+        /// The command in question
+        command: String,
+        /// How many parameters or subcommands the command has
+        count: usize,
+    },
+    /// A parameter has more than 25 choices
+    #[non_exhaustive]
+    TooManyChoices {
+        /// The command in question
+        command: String,
+        /// The parameter in question
+        parameter: String,
+        /// How many choices the parameter has
+        count: usize,
+    },
+    /// A choice name is empty or longer than 100 characters
+    #[non_exhaustive]
+    InvalidChoiceName {
+        /// The command in question
+        command: String,
+        /// The parameter in question
+        parameter: String,
+        /// The offending choice name
+        name: String,
+    },
+    /// A required parameter comes after an optional one
+    #[non_exhaustive]
+    RequiredAfterOptional {
+        /// The command in question
+        command: String,
+        /// The required parameter in question
+        parameter: String,
+    },
+    /// The names, descriptions and choices of a top-level command and all its subcommands exceed
+    /// 4000 characters in total
+    #[non_exhaustive]
+    PayloadTooLarge {
+        /// The top-level command in question
+        command: String,
+        /// Combined length of the command's names, descriptions and choices
+        size: usize,
+    },
+    // #[non_exhaustive] forbids struct update syntax for ?? reason
+    #[doc(hidden)]
+    __NonExhaustive(std::convert::Infallible),
+}
+
+impl std::fmt::Display for CommandViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        /// Formats the location of a name or description
+        fn location(command: &str, parameter: &Option<String>) -> String {
+            match parameter {
+                Some(parameter) => format!("parameter `{}` of `{}`", parameter, command),
+                None => format!("`{}`", command),
+            }
+        }
+
+        match self {
+            Self::TooManyCommands { kind, count, max } => {
+                write!(
+                    f,
+                    "{} {:?} commands exceed the limit of {}",
+                    count, kind, max
+                )
+            }
+            Self::DuplicateName { kind, name } => {
+                write!(f, "multiple {:?} commands are named `{}`", kind, name)
+            }
+            Self::TooDeeplyNested { command } => {
+                write!(f, "`{}` is nested more than two levels deep", command)
+            }
+            Self::InvalidName {
+                command,
+                parameter,
+                name,
+            } => write!(
+                f,
+                "invalid name `{}` of {}",
+                name,
+                location(command, parameter)
+            ),
+            Self::InvalidDescription {
+                command,
+                parameter,
+                description,
+            } => write!(
+                f,
+                "description of {} must be 1-100 characters, got {}",
+                location(command, parameter),
+                description.chars().count()
+            ),
+            Self::TooManyOptions { command, count } => write!(
+                f,
+                "`{}` has {} parameters or subcommands, the limit is {}",
+                command, count, MAX_OPTIONS
+            ),
+            Self::TooManyChoices {
+                command,
+                parameter,
+                count,
+            } => write!(
+                f,
+                "parameter `{}` of `{}` has {} choices, the limit is {}",
+                parameter, command, count, MAX_CHOICES
+            ),
+            Self::InvalidChoiceName {
+                command,
+                parameter,
+                name,
+            } => write!(
+                f,
+                "choice `{}` of parameter `{}` of `{}` must be 1-100 characters",
+                name, parameter, command
+            ),
+            Self::RequiredAfterOptional { command, parameter } => write!(
+                f,
+                "required parameter `{}` of `{}` comes after an optional parameter",
+                parameter, command
+            ),
+            Self::PayloadTooLarge { command, size } => write!(
+                f,
+                "`{}` has {} characters of names, descriptions and choices, the limit is {}",
+                command, size, MAX_PAYLOAD_SIZE
+            ),
+            Self::__NonExhaustive(unreachable) => match *unreachable {},
+        }
+    }
+}
+
+impl std::error::Error for CommandViolation {}
+
+/// Returns whether the string is between `min` and `max` characters long
+fn has_length(s: &str, min: usize, max: usize) -> bool {
+    (min..=max).contains(&s.chars().count())
+}
+
+/// Returns whether the string is a valid slash command or parameter name
+fn is_valid_slash_name(name: &str) -> bool {
+    has_length(name, 1, 32)
+        && name
+            .chars()
+            .all(|c| (c.is_alphanumeric() && !c.is_uppercase()) || c == '-' || c == '_')
+}
+
+/// Validates a (sub)command and its parameters and returns the number of characters it
+/// contributes to the payload size
+fn validate_slash_command<U, E>(
+    command: &crate::Command<U, E>,
+    depth: usize,
+    violations: &mut Vec<CommandViolation>,
+) -> usize {
+    let qualified_name = || command.qualified_name.clone();
+
+    if depth > 2 {
+        violations.push(CommandViolation::TooDeeplyNested {
+            command: qualified_name(),
+        });
+    }
+
+    let description = command.description.as_deref().unwrap_or("A slash command");
+    let names = std::iter::once(&command.name).chain(command.name_localizations.values());
+    for name in names.filter(|name| !is_valid_slash_name(name)) {
+        violations.push(CommandViolation::InvalidName {
+            command: qualified_name(),
+            parameter: None,
+            name: name.clone(),
+        });
+    }
+    let descriptions = std::iter::once(description)
+        .chain(command.description_localizations.values().map(|d| &**d));
+    for description in descriptions.filter(|d| !has_length(d, 1, 100)) {
+        violations.push(CommandViolation::InvalidDescription {
+            command: qualified_name(),
+            parameter: None,
+            description: description.to_owned(),
+        });
+    }
+    let mut size = command.name.chars().count() + description.chars().count();
+
+    if !command.subcommands.is_empty() {
+        let subcommands = command
+            .subcommands
+            .iter()
+            .filter(|subcommand| subcommand.slash_action.is_some());
+        let mut count = 0;
+        for subcommand in subcommands {
+            count += 1;
+            size += validate_slash_command(subcommand, depth + 1, violations);
+        }
+        if count > MAX_OPTIONS {
+            violations.push(CommandViolation::TooManyOptions {
+                command: qualified_name(),
+                count,
+            });
+        }
+        return size;
+    }
+
+    if command.parameters.len() > MAX_OPTIONS {
+        violations.push(CommandViolation::TooManyOptions {
+            command: qualified_name(),
+            count: command.parameters.len(),
+        });
+    }
+
+    let mut seen_optional = false;
+    for param in &command.parameters {
+        let names = std::iter::once(&param.name).chain(param.name_localizations.values());
+        for name in names.filter(|name| !is_valid_slash_name(name)) {
+            violations.push(CommandViolation::InvalidName {
+                command: qualified_name(),
+                parameter: Some(param.name.clone()),
+                name: name.clone(),
+            });
+        }
+
+        let description = param
+            .description
+            .as_deref()
+            .unwrap_or("A slash command parameter");
+        let descriptions = std::iter::once(description)
+            .chain(param.description_localizations.values().map(|d| &**d));
+        for description in descriptions.filter(|d| !has_length(d, 1, 100)) {
+            violations.push(CommandViolation::InvalidDescription {
+                command: qualified_name(),
+                parameter: Some(param.name.clone()),
+                description: description.to_owned(),
+            });
+        }
+        size += param.name.chars().count() + description.chars().count();
+
+        if param.required && seen_optional {
+            violations.push(CommandViolation::RequiredAfterOptional {
+                command: qualified_name(),
+                parameter: param.name.clone(),
+            });
+        }
+        seen_optional |= !param.required;
+
+        let dynamic_choices = param.dynamic_choices.map(|f| f()).unwrap_or_default();
+        let choices = param.choices.iter().chain(&dynamic_choices);
+        let mut count = 0;
+        for choice in choices {
+            count += 1;
+            size += choice.name.chars().count();
+            let names = std::iter::once(&choice.name).chain(choice.localizations.values());
+            for name in names.filter(|name| !has_length(name, 1, 100)) {
+                violations.push(CommandViolation::InvalidChoiceName {
+                    command: qualified_name(),
+                    parameter: param.name.clone(),
+                    name: name.clone(),
+                });
+            }
+        }
+        // Dynamic choices use the name as value, so it's counted twice
+        size += dynamic_choices
+            .iter()
+            .map(|choice| choice.name.chars().count())
+            .sum::<usize>();
+        if count > MAX_CHOICES {
+            violations.push(CommandViolation::TooManyChoices {
+                command: qualified_name(),
+                parameter: param.name.clone(),
+                count,
+            });
+        }
+    }
+
+    size
+}
+
+/// Checks the given commands against Discord's limits for application commands, like name
+/// format, description length, number of parameters and choices, subcommand nesting depth and
+/// total size. Returns all violations found, or an empty list if the commands can be registered.
+///
+/// Discord rejects the whole list of commands if any one of them is invalid, with an error that
+/// doesn't always say which command is at fault. [`crate::builtins::register_globally`] and the
+/// other registration functions log the violations found by this function before registering;
+/// call it yourself to refuse registering invalid commands. The ready to use register commands
+/// like [`crate::builtins::register_application_commands_buttons`] reply with the violations and
+/// don't register.
+///
+/// ```rust
+/// # let commands: Vec<poise::Command<(), ()>> = vec![];
+/// for violation in poise::builtins::validate_application_commands(&commands) {
+///     eprintln!("{}", violation);
+/// }
+/// ```
+pub fn validate_application_commands<U, E>(
+    commands: &[impl AsRef<crate::Command<U, E>>],
+) -> Vec<CommandViolation> {
+    /// Collects the names of all context menu commands, including nested ones
+    fn collect_context_menu_names<'a, U, E>(
+        command: &'a crate::Command<U, E>,
+        user: &mut Vec<&'a str>,
+        message: &mut Vec<&'a str>,
+    ) {
+        let name = command
+            .context_menu_name
+            .as_deref()
+            .unwrap_or(&command.name);
+        match command.context_menu_action {
+            Some(crate::ContextMenuCommandAction::User(_)) => user.push(name),
+            Some(crate::ContextMenuCommandAction::Message(_)) => message.push(name),
+            Some(crate::ContextMenuCommandAction::__NonExhaustive) => unreachable!(),
+            None => {}
+        }
+        for subcommand in &command.subcommands {
+            collect_context_menu_names(subcommand, user, message);
+        }
+    }
+
+    /// Reports too many commands, duplicate names and invalid names among commands of one kind
+    fn check_names(
+        kind: serenity::CommandType,
+        names: &[&str],
+        max: usize,
+        violations: &mut Vec<CommandViolation>,
+    ) {
+        if names.len() > max {
+            violations.push(CommandViolation::TooManyCommands {
+                kind,
+                count: names.len(),
+                max,
+            });
+        }
+        for (i, name) in names.iter().enumerate() {
+            // Report each duplicated name once, at its second occurrence
+            if names[..i].iter().filter(|n| *n == name).count() == 1 {
+                violations.push(CommandViolation::DuplicateName {
+                    kind,
+                    name: (*name).to_owned(),
+                });
+            }
+            if kind != serenity::CommandType::ChatInput && !has_length(name, 1, 32) {
+                violations.push(CommandViolation::InvalidName {
+                    command: (*name).to_owned(),
+                    parameter: None,
+                    name: (*name).to_owned(),
+                });
+            }
+        }
+    }
+
+    let mut violations = Vec::new();
+
+    let mut slash_names = Vec::new();
+    let mut user_names = Vec::new();
+    let mut message_names = Vec::new();
+    for command in commands {
+        let command: &crate::Command<U, E> = command.as_ref();
+        collect_context_menu_names(command, &mut user_names, &mut message_names);
+        if command.slash_action.is_none() {
+            continue;
+        }
+        slash_names.push(&*command.name);
+
+        let size = validate_slash_command(command, 0, &mut violations);
+        if size > MAX_PAYLOAD_SIZE {
+            violations.push(CommandViolation::PayloadTooLarge {
+                command: command.qualified_name.clone(),
+                size,
+            });
+        }
+    }
+
+    let kinds = [
+        (
+            serenity::CommandType::ChatInput,
+            slash_names,
+            MAX_SLASH_COMMANDS,
+        ),
+        (
+            serenity::CommandType::User,
+            user_names,
+            MAX_CONTEXT_MENU_COMMANDS,
+        ),
+        (
+            serenity::CommandType::Message,
+            message_names,
+            MAX_CONTEXT_MENU_COMMANDS,
+        ),
+    ];
+    for (kind, names, max) in kinds {
+        check_names(kind, &names, max, &mut violations);
+    }
+
+    violations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_slash_name() {
+        assert!(is_valid_slash_name("ban"));
+        assert!(is_valid_slash_name("set-prefix_2"));
+        assert!(is_valid_slash_name("überprüfen"));
+        assert!(!is_valid_slash_name(""));
+        assert!(!is_valid_slash_name("Ban"));
+        assert!(!is_valid_slash_name("set prefix"));
+        assert!(!is_valid_slash_name(&"a".repeat(33)));
+    }
+}
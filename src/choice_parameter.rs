@@ -12,7 +12,8 @@ pub trait ChoiceParameter: Sized {
     /// Returns an instance of [`Self`] corresponding to the given index into [`Self::list()`]
     fn from_index(index: usize) -> Option<Self>;
 
-    /// Parses the name as returned by [`Self::name()`] into an instance of [`Self`]
+    /// Parses the name as returned by [`Self::name()`] into an instance of [`Self`]. The derive
+    /// macro also accepts alternative and localized names, case-insensitively
     fn from_name(name: &str) -> Option<Self>;
 
     /// Returns the non-localized name of this choice
@@ -20,6 +21,16 @@ pub trait ChoiceParameter: Sized {
 
     /// Returns the localized name for the given locale, if one is set
     fn localized_name(&self, locale: &str) -> Option<&'static str>;
+
+    /// Returns the value that Discord sends for this choice, if it has one set via
+    /// `#[value = ...]`. Otherwise, Discord sends the index of the choice.
+    fn value(&self) -> Option<crate::CommandParameterChoiceValue> {
+        let name = self.name();
+        Self::list()
+            .into_iter()
+            .find(|choice| choice.name == name)
+            .and_then(|choice| choice.value)
+    }
 }
 
 #[async_trait::async_trait]
@@ -32,22 +43,41 @@ impl<T: ChoiceParameter> crate::SlashArgument for T {
         #[allow(unused_imports)]
         use ::serenity::json::*; // Required for simd-json :|
 
-        let choice_key = match value {
-            serenity::ResolvedValue::Integer(int) => *int as u64,
+        use crate::CommandParameterChoiceValue as ChoiceValue;
+
+        let choices = Self::list();
+        let index = match *value {
+            serenity::ResolvedValue::Integer(int) if choices.iter().any(|c| c.value.is_some()) => {
+                choices.iter().position(
+                    |c| matches!(c.value, Some(ChoiceValue::Integer(v)) if i64::from(v) == int),
+                )
+            }
+            serenity::ResolvedValue::Integer(int) => Some(int as usize),
+            serenity::ResolvedValue::Number(number) => choices
+                .iter()
+                .position(|c| c.value == Some(ChoiceValue::Number(number))),
             _ => {
                 return Err(crate::SlashArgError::CommandStructureMismatch {
-                    description: "expected u64",
+                    description: "expected integer or number",
                 })
             }
         };
 
-        Self::from_index(choice_key as _).ok_or(crate::SlashArgError::CommandStructureMismatch {
-            description: "out of bounds choice key",
-        })
+        index
+            .and_then(Self::from_index)
+            .ok_or(crate::SlashArgError::CommandStructureMismatch {
+                description: "out of bounds choice key",
+            })
     }
 
     fn create(builder: serenity::CreateCommandOption) -> serenity::CreateCommandOption {
-        builder.kind(serenity::CommandOptionType::Integer)
+        let has_number_values = Self::list()
+            .iter()
+            .any(|c| matches!(c.value, Some(crate::CommandParameterChoiceValue::Number(_))));
+        match has_number_values {
+            true => builder.kind(serenity::CommandOptionType::Number),
+            false => builder.kind(serenity::CommandOptionType::Integer),
+        }
     }
 
     fn choices() -> Vec<crate::CommandParameterChoice> {
@@ -0,0 +1,175 @@
+//! Infrastructure for limiting how many invocations of a command may run at the same time
+
+use crate::serenity_prelude as serenity;
+use std::collections::HashMap;
+
+/// Configuration struct for [`ConcurrencyTracker`]. Each field is the maximum number of
+/// invocations of the command that may run at once in the respective scope
+#[derive(Default, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct ConcurrencyLimits {
+    /// This limit applies to all invocations together
+    pub global: Option<usize>,
+    /// This limit operates on a per-user basis
+    pub user: Option<usize>,
+    /// This limit operates on a per-guild basis
+    pub guild: Option<usize>,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+/// Scope of a concurrency limit, see [`ConcurrencyLimits`]
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+#[non_exhaustive]
+pub enum ConcurrencyScope {
+    /// [`ConcurrencyLimits::global`]
+    Global,
+    /// [`ConcurrencyLimits::user`]
+    User,
+    /// [`ConcurrencyLimits::guild`]
+    Guild,
+}
+
+/// Tracks how many invocations of a single command are currently running
+///
+/// You probably don't need to use this directly. The framework acquires a slot before running a
+/// command and releases it when the command finishes.
+#[derive(Default, Clone, Debug, PartialEq, Eq)]
+pub struct ConcurrencyTracker {
+    /// Number of running invocations
+    global: usize,
+    /// Number of running invocations per user
+    user: HashMap<serenity::UserId, usize>,
+    /// Number of running invocations per guild
+    guild: HashMap<serenity::GuildId, usize>,
+}
+
+impl ConcurrencyTracker {
+    /// Create a new concurrency tracker
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of invocations currently running in the given scope
+    pub fn running(&self, ctx: &crate::CooldownContext, scope: ConcurrencyScope) -> usize {
+        match scope {
+            ConcurrencyScope::Global => self.global,
+            ConcurrencyScope::User => self.user.get(&ctx.user_id).copied().unwrap_or(0),
+            ConcurrencyScope::Guild => ctx
+                .guild_id
+                .and_then(|guild_id| self.guild.get(&guild_id).copied())
+                .unwrap_or(0),
+        }
+    }
+
+    /// Registers a new running invocation, unless that would exceed one of the limits. In that
+    /// case, nothing is registered and the exceeded scope is returned
+    pub fn try_start(
+        &mut self,
+        ctx: &crate::CooldownContext,
+        limits: &ConcurrencyLimits,
+    ) -> Result<(), ConcurrencyScope> {
+        let mut checks = vec![
+            (ConcurrencyScope::Global, limits.global),
+            (ConcurrencyScope::User, limits.user),
+        ];
+        if ctx.guild_id.is_some() {
+            checks.push((ConcurrencyScope::Guild, limits.guild));
+        }
+        for (scope, limit) in checks {
+            if limit.is_some_and(|limit| self.running(ctx, scope) >= limit) {
+                return Err(scope);
+            }
+        }
+
+        self.global += 1;
+        *self.user.entry(ctx.user_id).or_default() += 1;
+        if let Some(guild_id) = ctx.guild_id {
+            *self.guild.entry(guild_id).or_default() += 1;
+        }
+        Ok(())
+    }
+
+    /// Unregisters an invocation that was registered with [`Self::try_start`]
+    pub fn finish(&mut self, ctx: &crate::CooldownContext) {
+        /// Decrements the counter of the key and removes it once it reaches zero
+        fn decrement<K: std::hash::Hash + Eq>(map: &mut HashMap<K, usize>, key: K) {
+            if let std::collections::hash_map::Entry::Occupied(mut entry) = map.entry(key) {
+                *entry.get_mut() -= 1;
+                if *entry.get() == 0 {
+                    entry.remove();
+                }
+            }
+        }
+
+        self.global = self.global.saturating_sub(1);
+        decrement(&mut self.user, ctx.user_id);
+        if let Some(guild_id) = ctx.guild_id {
+            decrement(&mut self.guild, guild_id);
+        }
+    }
+}
+
+/// Keeps an invocation registered in a [`ConcurrencyTracker`] while alive
+pub(crate) struct ConcurrencySlot<'a> {
+    /// The tracker of the running command
+    tracker: &'a std::sync::Mutex<ConcurrencyTracker>,
+    /// Where the command was invoked
+    ctx: crate::CooldownContext,
+}
+
+impl<'a> ConcurrencySlot<'a> {
+    /// Registers the invocation in the command's tracker, or returns the scope of the limit that
+    /// was reached
+    pub(crate) fn acquire<U, E>(ctx: crate::Context<'a, U, E>) -> Result<Self, ConcurrencyScope> {
+        let command = ctx.command();
+        let cooldown_context = ctx.cooldown_context();
+        command
+            .concurrency
+            .lock()
+            .unwrap()
+            .try_start(&cooldown_context, &command.max_concurrent)?;
+        Ok(Self {
+            tracker: &command.concurrency,
+            ctx: cooldown_context,
+        })
+    }
+}
+
+impl Drop for ConcurrencySlot<'_> {
+    fn drop(&mut self) {
+        self.tracker.lock().unwrap().finish(&self.ctx);
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_concurrency_tracker() {
+    let ctx = |user_id: u64, guild_id: u64| crate::CooldownContext {
+        user_id: serenity::UserId::new(user_id),
+        guild_id: Some(serenity::GuildId::new(guild_id)),
+        channel_id: serenity::ChannelId::new(1),
+    };
+    let limits = ConcurrencyLimits {
+        global: Some(3),
+        user: Some(1),
+        ..Default::default()
+    };
+
+    let mut tracker = ConcurrencyTracker::new();
+    assert_eq!(tracker.try_start(&ctx(1, 1), &limits), Ok(()));
+    assert_eq!(
+        tracker.try_start(&ctx(1, 1), &limits),
+        Err(ConcurrencyScope::User)
+    );
+    assert_eq!(tracker.try_start(&ctx(2, 1), &limits), Ok(()));
+    assert_eq!(tracker.try_start(&ctx(3, 2), &limits), Ok(()));
+    assert_eq!(
+        tracker.try_start(&ctx(4, 2), &limits),
+        Err(ConcurrencyScope::Global)
+    );
+
+    tracker.finish(&ctx(1, 1));
+    assert_eq!(tracker.running(&ctx(1, 1), ConcurrencyScope::User), 0);
+    assert_eq!(tracker.running(&ctx(2, 1), ConcurrencyScope::Guild), 1);
+    assert_eq!(tracker.try_start(&ctx(1, 1), &limits), Ok(()));
+}
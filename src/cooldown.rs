@@ -50,6 +50,8 @@ pub struct CooldownTracker {
     channel_invocations: HashMap<serenity::ChannelId, Instant>,
     /// Stores the timestamps of the last invocation per member (user and guild)
     member_invocations: HashMap<(serenity::UserId, serenity::GuildId), Instant>,
+    /// Number of entries after the last [`Self::prune_expired`], to decide when to prune again
+    entries_after_prune: usize,
 }
 
 /// Possible types of command cooldowns.
@@ -81,9 +83,61 @@ impl CooldownTracker {
             guild_invocations: HashMap::new(),
             channel_invocations: HashMap::new(),
             member_invocations: HashMap::new(),
+            entries_after_prune: 0,
         }
     }
 
+    /// Returns the number of stored invocation timestamps across all cooldown buckets
+    pub fn entry_count(&self) -> usize {
+        usize::from(self.global_invocation.is_some())
+            + self.user_invocations.len()
+            + self.guild_invocations.len()
+            + self.channel_invocations.len()
+            + self.member_invocations.len()
+    }
+
+    /// Removes the timestamps whose cooldown has run out, including those of buckets without a
+    /// configured cooldown. Otherwise, the tracker keeps an entry for every user, guild and
+    /// channel that ever invoked the command.
+    ///
+    /// Unless [`crate::FrameworkOptions::manual_cooldowns`] is set, the framework calls this with
+    /// the command's [`CooldownConfig`] whenever the tracker has doubled in size since the last
+    /// call.
+    ///
+    /// The buckets aren't sharded: every command has its own tracker, which is only locked for a
+    /// few hash lookups, and [`crate::Command::cooldowns`] is locked directly for manual cooldowns.
+    pub fn prune_expired(&mut self, config: &CooldownConfig) {
+        /// Drops the expired timestamps of one bucket kind, or all if it has no cooldown
+        fn prune<K>(map: &mut HashMap<K, Instant>, cooldown: Option<Duration>, now: Instant) {
+            match cooldown {
+                Some(cooldown) => {
+                    map.retain(|_, &mut last| now.saturating_duration_since(last) < cooldown)
+                }
+                None => map.clear(),
+            }
+        }
+
+        let now = Instant::now();
+        let global_expired = match (config.global, self.global_invocation) {
+            (Some(cooldown), Some(last)) => now.saturating_duration_since(last) >= cooldown,
+            _ => true,
+        };
+        if global_expired {
+            self.global_invocation = None;
+        }
+        prune(&mut self.user_invocations, config.user, now);
+        prune(&mut self.guild_invocations, config.guild, now);
+        prune(&mut self.channel_invocations, config.channel, now);
+        prune(&mut self.member_invocations, config.member, now);
+        self.entries_after_prune = self.entry_count();
+    }
+
+    /// Returns whether the tracker has grown enough since the last [`Self::prune_expired`] to
+    /// prune again. Pruning at doubled size keeps its cost per invocation constant
+    pub(crate) fn should_prune(&self) -> bool {
+        self.entry_count() > 2 * self.entries_after_prune.max(32)
+    }
+
     /// Queries the cooldown buckets and checks if all cooldowns have expired and command
     /// execution may proceed. If not, Some is returned with the remaining cooldown
     pub fn remaining_cooldown(
@@ -173,3 +227,30 @@ impl<'a> From<&'a serenity::Message> for CooldownContext {
         }
     }
 }
+
+#[cfg(test)]
+#[test]
+fn test_prune_expired() {
+    let mut tracker = CooldownTracker::new();
+    let long_ago = Instant::now() - Duration::from_secs(60);
+    for i in 1..=3 {
+        tracker.set_last_invocation(CooldownType::User(serenity::UserId::new(i)), long_ago);
+    }
+    tracker.start_cooldown(CooldownContext {
+        user_id: serenity::UserId::new(4),
+        guild_id: None,
+        channel_id: serenity::ChannelId::new(1),
+    });
+    assert_eq!(tracker.entry_count(), 6);
+
+    let config = CooldownConfig {
+        user: Some(Duration::from_secs(10)),
+        ..Default::default()
+    };
+    tracker.prune_expired(&config);
+    // Only the recent user cooldown is left. Global and channel cooldowns aren't configured
+    assert_eq!(tracker.entry_count(), 1);
+    assert!(tracker
+        .user_invocations
+        .contains_key(&serenity::UserId::new(4)));
+}
@@ -56,12 +56,36 @@ async fn missing_permissions<U, E>(
     Some(required_permissions - permissions?)
 }
 
+/// Returns whether NSFW commands may run in the channel of the invocation. DMs allow them. Threads
+/// don't have their own NSFW flag, so that of their parent channel is used
+async fn is_nsfw_channel<U, E>(ctx: crate::Context<'_, U, E>) -> Result<bool, serenity::Error> {
+    let channel = match ctx.channel_id().to_channel(ctx.serenity_context()).await? {
+        serenity::Channel::Guild(channel) => channel,
+        _ => return Ok(true),
+    };
+    if channel.thread_metadata.is_none() {
+        return Ok(channel.nsfw);
+    }
+    let Some(parent_id) = channel.parent_id else {
+        return Ok(channel.nsfw);
+    };
+    match parent_id.to_channel(ctx.serenity_context()).await? {
+        serenity::Channel::Guild(parent) => Ok(parent.nsfw),
+        _ => Ok(channel.nsfw),
+    }
+}
+
 /// See [`check_permissions_and_cooldown`]. Runs the check only for a single command. The caller
 /// should call this multiple time for each parent command to achieve the check inheritance logic.
 async fn check_permissions_and_cooldown_single<'a, U, E>(
     ctx: crate::Context<'a, U, E>,
     cmd: &'a crate::Command<U, E>,
 ) -> Result<(), crate::FrameworkError<'a, U, E>> {
+    // Disabled commands can't be run by anyone, not even owners
+    if !cmd.is_enabled() {
+        return Err(crate::FrameworkError::CommandDisabled { ctx });
+    }
+
     // Skip command checks if `FrameworkOptions::skip_checks_for_owners` is set to true
     if ctx.framework().options.skip_checks_for_owners
         && ctx.framework().options().owners.contains(&ctx.author().id)
@@ -93,18 +117,14 @@ async fn check_permissions_and_cooldown_single<'a, U, E>(
         return Err(crate::FrameworkError::DmOnly { ctx });
     }
 
+    // Discord enforces this for application commands, but prefix commands rely on this check
     if cmd.nsfw_only {
-        let channel = match ctx.channel_id().to_channel(ctx.serenity_context()).await {
-            Ok(channel) => channel,
+        match is_nsfw_channel(ctx).await {
+            Ok(true) => {}
+            Ok(false) => return Err(crate::FrameworkError::NsfwOnly { ctx }),
             Err(e) => {
                 tracing::warn!("Error when getting channel: {}", e);
 
-                return Err(crate::FrameworkError::NsfwOnly { ctx });
-            }
-        };
-
-        if let serenity::Channel::Guild(guild_channel) = channel {
-            if !guild_channel.nsfw {
                 return Err(crate::FrameworkError::NsfwOnly { ctx });
             }
         }
@@ -144,15 +164,29 @@ async fn check_permissions_and_cooldown_single<'a, U, E>(
 
     // Only continue if command checks returns true
     // First perform global checks, then command checks (if necessary)
-    for check in Option::iter(&ctx.framework().options().command_check).chain(&cmd.checks) {
+    let global_check = ctx
+        .framework()
+        .options()
+        .command_check
+        .map(|check| (check, None));
+    let command_checks = cmd.checks.iter().enumerate().map(|(i, &check)| {
+        let message = cmd.check_failure_messages.get(i).and_then(Option::as_deref);
+        (check, message)
+    });
+    for (check, message) in global_check.into_iter().chain(command_checks) {
         match check(ctx).await {
             Ok(true) => {}
             Ok(false) => {
-                return Err(crate::FrameworkError::CommandCheckFailed { ctx, error: None })
+                return Err(crate::FrameworkError::CommandCheckFailed {
+                    ctx,
+                    error: None,
+                    message,
+                })
             }
             Err(error) => {
                 return Err(crate::FrameworkError::CommandCheckFailed {
                     error: Some(error),
+                    message,
                     ctx,
                 })
             }
@@ -160,10 +194,14 @@ async fn check_permissions_and_cooldown_single<'a, U, E>(
     }
 
     if !ctx.framework().options().manual_cooldowns {
-        let cooldowns = cmd.cooldowns.lock().unwrap();
+        let mut cooldowns = cmd.cooldowns.lock().unwrap();
         let config = cmd.cooldown_config.read().unwrap();
+        if cooldowns.should_prune() {
+            cooldowns.prune_expired(&config);
+        }
         let remaining_cooldown = cooldowns.remaining_cooldown(ctx.cooldown_context(), &config);
         if let Some(remaining_cooldown) = remaining_cooldown {
+            crate::metrics::record_cooldown_hit(cmd);
             return Err(crate::FrameworkError::CooldownHit {
                 ctx,
                 remaining_cooldown,
@@ -190,3 +228,37 @@ pub async fn check_permissions_and_cooldown<'a, U, E>(
 
     Ok(())
 }
+
+/// Runs a command invocation inside a [`tracing`] span with the command name, guild ID, user ID
+/// and invocation kind. Errors are recorded as events on that span.
+#[cfg(feature = "tracing_spans")]
+pub(crate) async fn instrument_invocation<'a, U, E>(
+    ctx: crate::Context<'a, U, E>,
+    invocation: impl std::future::Future<Output = Result<(), crate::FrameworkError<'a, U, E>>>,
+) -> Result<(), crate::FrameworkError<'a, U, E>> {
+    use tracing::Instrument as _;
+
+    let span = tracing::info_span!(
+        "invocation",
+        command = %ctx.command().qualified_name,
+        guild_id = ctx.guild_id().map(|guild_id| guild_id.get()),
+        user_id = ctx.author().id.get(),
+        kind = ?ctx.invocation_kind(),
+    );
+    let result = crate::metrics::measure_invocation(ctx, invocation)
+        .instrument(span.clone())
+        .await;
+    if let Err(error) = &result {
+        span.in_scope(|| tracing::warn!(error = error.variant_name(), "invocation failed"));
+    }
+    result
+}
+
+/// Without the `tracing_spans` feature, runs the invocation without a span
+#[cfg(not(feature = "tracing_spans"))]
+pub(crate) async fn instrument_invocation<'a, U, E>(
+    ctx: crate::Context<'a, U, E>,
+    invocation: impl std::future::Future<Output = Result<(), crate::FrameworkError<'a, U, E>>>,
+) -> Result<(), crate::FrameworkError<'a, U, E>> {
+    crate::metrics::measure_invocation(ctx, invocation).await
+}
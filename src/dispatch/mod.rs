@@ -13,17 +13,25 @@ use crate::serenity_prelude as serenity;
 // TODO: integrate serenity::Context in here? Every place where FrameworkContext is passed is also
 // passed serenity::Context
 /// A view into data stored by [`crate::Framework`]
+///
+/// To run your own event loop, create it with [`Self::new`].
 pub struct FrameworkContext<'a, U, E> {
     /// User ID of this bot
     pub bot_id: serenity::UserId,
     /// Framework configuration
     pub options: &'a crate::FrameworkOptions<U, E>,
+    /// The commands to dispatch to, loaded from [`Self::command_registry`] when the event was
+    /// received
+    pub(crate) commands: &'a crate::CommandSet<U, E>,
+    /// Holds the current commands, which may have changed since the event was received
+    pub(crate) command_registry: &'a crate::CommandRegistry<U, E>,
     /// Your provided user data
     pub user_data: &'a U,
     /// Serenity shard manager. Can be used for example to shutdown the bot
     pub shard_manager: &'a std::sync::Arc<serenity::ShardManager>,
-    // deliberately not non exhaustive because you need to create FrameworkContext from scratch
-    // to run your own event loop
+    /// Handle to shut down the [`crate::Framework`] gracefully, or None if events are dispatched
+    /// manually without a [`crate::Framework`]
+    pub(crate) shutdown_handle: Option<&'a crate::ShutdownHandle>,
 }
 impl<U, E> Copy for FrameworkContext<'_, U, E> {}
 impl<U, E> Clone for FrameworkContext<'_, U, E> {
@@ -32,7 +40,32 @@ impl<U, E> Clone for FrameworkContext<'_, U, E> {
     }
 }
 impl<'a, U, E> FrameworkContext<'a, U, E> {
-    /// Returns the stored framework options, including commands.
+    /// Creates a framework context for dispatching events without a [`crate::Framework`], see
+    /// the `manual_dispatch` example in the repository.
+    ///
+    /// `commands` should be [loaded](crate::CommandRegistry::load) from `command_registry` once
+    /// per event. Graceful shutdown is managed by [`crate::Framework`], so
+    /// [`Self::shutdown_handle`] returns None for such a context.
+    pub fn new(
+        bot_id: serenity::UserId,
+        options: &'a crate::FrameworkOptions<U, E>,
+        commands: &'a crate::CommandSet<U, E>,
+        command_registry: &'a crate::CommandRegistry<U, E>,
+        user_data: &'a U,
+        shard_manager: &'a std::sync::Arc<serenity::ShardManager>,
+    ) -> Self {
+        Self {
+            bot_id,
+            options,
+            commands,
+            command_registry,
+            user_data,
+            shard_manager,
+            shutdown_handle: None,
+        }
+    }
+
+    /// Returns the stored framework options. The commands are in [`Self::commands`].
     ///
     /// This function exists for API compatiblity with [`crate::Framework`]. On this type, you can
     /// also just access the public `options` field.
@@ -40,6 +73,36 @@ impl<'a, U, E> FrameworkContext<'a, U, E> {
         self.options
     }
 
+    /// Returns the commands of the framework, as they were when the event was received
+    pub fn commands(&self) -> &'a crate::CommandSet<U, E> {
+        self.commands
+    }
+
+    /// Adds a command at runtime, replacing an existing top-level command with the same name. See
+    /// [`crate::Framework::add_command`].
+    ///
+    /// [`Self::commands`] of this context keeps the commands from when the event was received;
+    /// only events received afterwards see the change.
+    pub fn add_command(&self, command: crate::Command<U, E>) {
+        self.command_registry.add_command(self.options, command);
+    }
+
+    /// Removes the top-level command with the given name at runtime and returns it, if it
+    /// exists. See [`Self::add_command`] for when changes take effect
+    pub fn remove_command(&self, name: &str) -> Option<std::sync::Arc<crate::Command<U, E>>> {
+        self.command_registry.remove_command(name)
+    }
+
+    /// Replaces all commands at runtime and returns the previous ones. See [`Self::add_command`]
+    /// for when changes take effect
+    pub fn replace_commands(
+        &self,
+        commands: Vec<crate::Command<U, E>>,
+    ) -> std::sync::Arc<crate::CommandSet<U, E>> {
+        self.command_registry
+            .replace_commands(self.options, commands)
+    }
+
     /// Returns the serenity's client shard manager.
     ///
     /// This function exists for API compatiblity with [`crate::Framework`]. On this type, you can
@@ -48,6 +111,20 @@ impl<'a, U, E> FrameworkContext<'a, U, E> {
         self.shard_manager.clone()
     }
 
+    /// Returns a handle that can shut down the bot gracefully, for example from within a command.
+    /// See [`crate::ShutdownHandle::shutdown`].
+    ///
+    /// Returns None if events are dispatched manually without a [`crate::Framework`].
+    pub fn shutdown_handle(&self) -> Option<crate::ShutdownHandle> {
+        self.shutdown_handle.cloned()
+    }
+
+    /// Returns the connection stage and heartbeat latency of every shard, see
+    /// [`crate::ShardStats`]
+    pub async fn shard_stats(&self) -> Vec<crate::ShardStats> {
+        crate::shard_stats(self.shard_manager).await
+    }
+
     /// Retrieves user data
     ///
     /// This function exists for API compatiblity with [`crate::Framework`]. On this type, you can
@@ -59,14 +136,18 @@ impl<'a, U, E> FrameworkContext<'a, U, E> {
 }
 
 /// Central event handling function of this library
-pub async fn dispatch_event<U: Send + Sync, E>(
+pub async fn dispatch_event<U: Send + Sync, E: Send>(
     framework: crate::FrameworkContext<'_, U, E>,
     ctx: &serenity::Context,
     event: serenity::FullEvent,
 ) {
+    let received_at = std::time::Instant::now();
+    crate::metrics::record_event(&event);
     match &event {
         serenity::FullEvent::Message { new_message } => {
             let invocation_data = tokio::sync::Mutex::new(Box::new(()) as _);
+            let replies = crate::ReplyRegistry::new();
+            let timing = crate::InvocationTiming::new(received_at);
             let mut parent_commands = Vec::new();
             let trigger = crate::MessageDispatchTrigger::MessageCreate;
             if let Err(error) = prefix::dispatch_message(
@@ -75,6 +156,8 @@ pub async fn dispatch_event<U: Send + Sync, E>(
                 new_message,
                 trigger,
                 &invocation_data,
+                &replies,
+                &timing,
                 &mut parent_commands,
             )
             .await
@@ -94,6 +177,8 @@ pub async fn dispatch_event<U: Send + Sync, E>(
 
                 if let Some((msg, previously_tracked)) = msg {
                     let invocation_data = tokio::sync::Mutex::new(Box::new(()) as _);
+                    let replies = crate::ReplyRegistry::new();
+                    let timing = crate::InvocationTiming::new(received_at);
                     let mut parent_commands = Vec::new();
                     let trigger = match previously_tracked {
                         true => crate::MessageDispatchTrigger::MessageEdit,
@@ -105,6 +190,8 @@ pub async fn dispatch_event<U: Send + Sync, E>(
                         &msg,
                         trigger,
                         &invocation_data,
+                        &replies,
+                        &timing,
                         &mut parent_commands,
                     )
                     .await
@@ -118,11 +205,11 @@ pub async fn dispatch_event<U: Send + Sync, E>(
             deleted_message_id, ..
         } => {
             if let Some(edit_tracker) = &framework.options.prefix_options.edit_tracker {
-                let bot_response = edit_tracker
+                let bot_responses = edit_tracker
                     .write()
                     .unwrap()
-                    .process_message_delete(*deleted_message_id);
-                if let Some(bot_response) = bot_response {
+                    .process_message_delete_all(*deleted_message_id);
+                for bot_response in bot_responses {
                     if let Err(e) = bot_response.delete(ctx).await {
                         tracing::warn!("failed to delete bot response: {}", e);
                     }
@@ -133,6 +220,8 @@ pub async fn dispatch_event<U: Send + Sync, E>(
             interaction: serenity::Interaction::Command(interaction),
         } => {
             let invocation_data = tokio::sync::Mutex::new(Box::new(()) as _);
+            let replies = crate::ReplyRegistry::new();
+            let timing = crate::InvocationTiming::new(received_at);
             let mut parent_commands = Vec::new();
             if let Err(error) = slash::dispatch_interaction(
                 framework,
@@ -140,6 +229,8 @@ pub async fn dispatch_event<U: Send + Sync, E>(
                 interaction,
                 &std::sync::atomic::AtomicBool::new(false),
                 &invocation_data,
+                &replies,
+                &timing,
                 &interaction.data.options(),
                 &mut parent_commands,
             )
@@ -152,6 +243,8 @@ pub async fn dispatch_event<U: Send + Sync, E>(
             interaction: serenity::Interaction::Autocomplete(interaction),
         } => {
             let invocation_data = tokio::sync::Mutex::new(Box::new(()) as _);
+            let replies = crate::ReplyRegistry::new();
+            let timing = crate::InvocationTiming::new(received_at);
             let mut parent_commands = Vec::new();
             if let Err(error) = slash::dispatch_autocomplete(
                 framework,
@@ -159,6 +252,8 @@ pub async fn dispatch_event<U: Send + Sync, E>(
                 interaction,
                 &std::sync::atomic::AtomicBool::new(false),
                 &invocation_data,
+                &replies,
+                &timing,
                 &interaction.data.options(),
                 &mut parent_commands,
             )
@@ -167,20 +262,68 @@ pub async fn dispatch_event<U: Send + Sync, E>(
                 error.handle(framework.options).await;
             }
         }
+        serenity::FullEvent::InteractionCreate {
+            interaction: serenity::Interaction::Component(interaction),
+        } => {
+            if let Err(error) = dispatch_component(framework, ctx, interaction).await {
+                error.handle(framework.options).await;
+            }
+        }
         _ => {}
     }
 
     // Do this after the framework's Ready handling, so that get_user_data() doesnt
     // potentially block infinitely
-    if let Err(error) =
-        (framework.options.event_handler)(ctx, &event, framework, framework.user_data).await
-    {
+    let event_handler =
+        (framework.options.event_handler)(ctx, &event, framework, framework.user_data);
+    let typed_handlers = framework
+        .options
+        .event_handlers
+        .iter()
+        .filter_map(|handler| handler.run(ctx, &event, framework));
+    let results =
+        futures_util::future::join_all(std::iter::once(event_handler).chain(typed_handlers)).await;
+    for error in results.into_iter().filter_map(Result::err) {
         let error = crate::FrameworkError::EventHandler {
             error,
             ctx,
             event: &event,
             framework,
         };
-        (framework.options.on_error)(error).await;
+        error.handle(framework.options).await;
     }
 }
+
+/// Runs the [`crate::ComponentHandler`] with the longest custom ID prefix matching the interaction,
+/// if any
+async fn dispatch_component<'a, U: Send + Sync, E>(
+    framework: crate::FrameworkContext<'a, U, E>,
+    ctx: &'a serenity::Context,
+    interaction: &'a serenity::ComponentInteraction,
+) -> Result<(), crate::FrameworkError<'a, U, E>> {
+    let custom_id = &interaction.data.custom_id;
+    let Some(handler) = framework
+        .options
+        .component_handlers
+        .iter()
+        .filter(|handler| custom_id.starts_with(&handler.custom_id_prefix))
+        .max_by_key(|handler| handler.custom_id_prefix.len())
+    else {
+        return Ok(());
+    };
+
+    let component_ctx = crate::ComponentContext {
+        serenity_context: ctx,
+        interaction,
+        framework,
+        data: framework.user_data,
+        custom_id_suffix: &custom_id[handler.custom_id_prefix.len()..],
+        __non_exhaustive: (),
+    };
+    (handler.action)(component_ctx)
+        .await
+        .map_err(|error| crate::FrameworkError::ComponentHandler {
+            error,
+            ctx: component_ctx,
+        })
+}
@@ -2,6 +2,50 @@
 
 use crate::serenity_prelude as serenity;
 
+/// Splits `content` into the given prefix and the rest, if it starts with the prefix. The returned
+/// prefix is taken from `content`, so it may differ in case from `prefix` if `case_insensitive` is
+/// set
+fn split_prefix<'a>(
+    content: &'a str,
+    prefix: &str,
+    case_insensitive: bool,
+) -> Option<(&'a str, &'a str)> {
+    if !case_insensitive {
+        return Some((content.get(..prefix.len())?, content.strip_prefix(prefix)?));
+    }
+
+    let mut content_chars = content.char_indices();
+    for prefix_char in prefix.chars() {
+        let (_, content_char) = content_chars.next()?;
+        if !content_char.to_lowercase().eq(prefix_char.to_lowercase()) {
+            return None;
+        }
+    }
+    let prefix_end = content_chars.next().map_or(content.len(), |(i, _)| i);
+    Some(content.split_at(prefix_end))
+}
+
+/// Finds the first span enclosed in the given delimiters in `content`, for
+/// [`crate::PrefixFrameworkOptions::inline_invocation_delimiters`]. Returns the opening delimiter
+/// and the enclosed text, to be treated like a prefix and the rest of the message
+fn find_inline_invocation<'a>(
+    content: &'a str,
+    open: &str,
+    close: &str,
+) -> Option<(&'a str, &'a str)> {
+    if open.is_empty() || close.is_empty() {
+        return None;
+    }
+    let start = content.find(open)?;
+    let inner_start = start + open.len();
+    let inner_len = content[inner_start..].find(close)?;
+    let inner = content[inner_start..inner_start + inner_len].trim();
+    if inner.is_empty() {
+        return None;
+    }
+    Some((&content[start..inner_start], inner))
+}
+
 /// Checks if this message is a bot invocation by attempting to strip the prefix
 ///
 /// Returns tuple of stripped prefix and rest of the message, if any prefix matches
@@ -20,51 +64,65 @@ async fn strip_prefix<'a, U, E>(
         __non_exhaustive: (),
     };
 
-    if let Some(dynamic_prefix) = framework.options.prefix_options.dynamic_prefix {
+    let prefix_options = &framework.options.prefix_options;
+    let case_insensitive = prefix_options.case_insensitive_prefix;
+    // All matching prefixes, in order of definition
+    let mut matches = Vec::new();
+
+    if let Some(dynamic_prefix) = prefix_options.dynamic_prefix {
         match dynamic_prefix(partial_ctx).await {
             Ok(prefix) => {
                 if let Some(prefix) = prefix {
-                    if msg.content.starts_with(&prefix) {
-                        return Some(msg.content.split_at(prefix.len()));
-                    }
+                    matches.extend(split_prefix(&msg.content, &prefix, case_insensitive));
                 }
             }
             Err(error) => {
-                (framework.options.on_error)(crate::FrameworkError::DynamicPrefix {
+                crate::FrameworkError::DynamicPrefix {
                     error,
                     ctx: partial_ctx,
                     msg,
-                })
+                }
+                .handle(framework.options)
                 .await;
             }
         }
     }
 
-    if let Some(prefix) = &framework.options.prefix_options.prefix {
-        if let Some(content) = msg.content.strip_prefix(prefix) {
-            return Some((prefix, content));
-        }
+    if let Some(prefix) = &prefix_options.prefix {
+        matches.extend(split_prefix(&msg.content, prefix, case_insensitive));
     }
 
-    if let Some((prefix, content)) = framework
-        .options
-        .prefix_options
-        .additional_prefixes
-        .iter()
-        .find_map(|prefix| match prefix {
-            &crate::Prefix::Literal(prefix) => Some((prefix, msg.content.strip_prefix(prefix)?)),
-            crate::Prefix::Regex(prefix) => {
-                let regex_match = prefix.find(&msg.content)?;
-                if regex_match.start() == 0 {
-                    Some(msg.content.split_at(regex_match.end()))
-                } else {
-                    None
+    matches.extend(
+        prefix_options
+            .additional_prefixes
+            .iter()
+            .filter_map(|prefix| match prefix {
+                &crate::Prefix::Literal(prefix) => {
+                    split_prefix(&msg.content, prefix, case_insensitive)
                 }
-            }
-            crate::Prefix::__NonExhaustive => unreachable!(),
-        })
-    {
-        return Some((prefix, content));
+                crate::Prefix::Regex(prefix) => {
+                    let regex_match = prefix.find(&msg.content)?;
+                    if regex_match.start() == 0 {
+                        Some(msg.content.split_at(regex_match.end()))
+                    } else {
+                        None
+                    }
+                }
+                crate::Prefix::__NonExhaustive => unreachable!(),
+            }),
+    );
+
+    let best_match = match prefix_options.prefix_priority {
+        // On ties, the first defined prefix wins
+        crate::PrefixPriority::LongestMatch => matches
+            .into_iter()
+            .rev()
+            .max_by_key(|(prefix, _)| prefix.len()),
+        crate::PrefixPriority::FirstMatch => matches.into_iter().next(),
+        crate::PrefixPriority::__NonExhaustive => unreachable!(),
+    };
+    if best_match.is_some() {
+        return best_match;
     }
 
     if let Some(dynamic_prefix) = framework.options.prefix_options.stripped_dynamic_prefix {
@@ -75,24 +133,28 @@ async fn strip_prefix<'a, U, E>(
                 }
             }
             Err(error) => {
-                (framework.options.on_error)(crate::FrameworkError::DynamicPrefix {
+                crate::FrameworkError::DynamicPrefix {
                     error,
                     ctx: partial_ctx,
                     msg,
-                })
+                }
+                .handle(framework.options)
                 .await;
             }
         }
     }
 
     if framework.options.prefix_options.mention_as_prefix {
-        // Mentions are either <@USER_ID> or <@!USER_ID>
+        // Mentions are either <@USER_ID> or <@!USER_ID>. The ID is parsed instead of comparing
+        // against the formatted bot ID, so that messages don't cost an allocation each
         if let Some(stripped_content) = (|| {
-            msg.content
-                .strip_prefix("<@")?
-                .trim_start_matches('!')
-                .strip_prefix(&framework.bot_id.to_string())?
-                .strip_prefix('>')
+            let mention = msg.content.strip_prefix("<@")?.trim_start_matches('!');
+            let id_len = mention.find(|c: char| !c.is_ascii_digit())?;
+            let id = &mention[..id_len];
+            if id.starts_with('0') || id.parse::<u64>().ok()? != framework.bot_id.get() {
+                return None;
+            }
+            mention[id_len..].strip_prefix('>')
         })() {
             let mention_prefix = &msg.content[..(msg.content.len() - stripped_content.len())];
             return Some((mention_prefix, stripped_content));
@@ -102,19 +164,22 @@ async fn strip_prefix<'a, U, E>(
     None
 }
 
-/// Find a command or subcommand within `&[Command]`, given a command invocation without a prefix.
-/// Returns the verbatim command name string as well as the command arguments (i.e. the remaining
-/// string).
+/// Find a command or subcommand within `&[Command]` or a [`crate::CommandSet`], given a command
+/// invocation without a prefix. Returns the verbatim command name string as well as the command
+/// arguments (i.e. the remaining string).
 ///
 /// The API must be like this (as opposed to just taking the command name upfront) because of
 /// subcommands.
 ///
+/// [Aliases](crate::Command::aliases) are matched on every level, so subcommands can be invoked
+/// through any combination of names and aliases of the command path.
+///
 /// ```rust
 /// #[poise::command(prefix_command)]
 /// async fn command1(ctx: poise::Context<'_, (), ()>) -> Result<(), ()> { Ok(()) }
-/// #[poise::command(prefix_command, subcommands("command3"))]
+/// #[poise::command(prefix_command, subcommands("command3"), aliases("c2"))]
 /// async fn command2(ctx: poise::Context<'_, (), ()>) -> Result<(), ()> { Ok(()) }
-/// #[poise::command(prefix_command)]
+/// #[poise::command(prefix_command, aliases("c3"))]
 /// async fn command3(ctx: poise::Context<'_, (), ()>) -> Result<(), ()> { Ok(()) }
 /// let commands = vec![command1(), command2()];
 ///
@@ -134,12 +199,37 @@ async fn strip_prefix<'a, U, E>(
 ///
 /// parent_commands.clear();
 /// assert_eq!(
+///     poise::find_command(&commands, "C2 c3 my arguments", true, &mut parent_commands),
+///     Some((&commands[1].subcommands[0], "c3", "my arguments")),
+/// );
+/// assert_eq!(&parent_commands, &[&commands[1]]);
+///
+/// parent_commands.clear();
+/// assert_eq!(
 ///     poise::find_command(&commands, "CoMmAnD2 cOmMaNd99 my arguments", true, &mut parent_commands),
 ///     Some((&commands[1], "CoMmAnD2", "cOmMaNd99 my arguments")),
 /// );
 /// assert!(parent_commands.is_empty());
 pub fn find_command<'a, U, E>(
-    commands: &'a [crate::Command<U, E>],
+    commands: &'a [impl AsRef<crate::Command<U, E>>],
+    remaining_message: &'a str,
+    case_insensitive: bool,
+    parent_commands: &mut Vec<&'a crate::Command<U, E>>,
+) -> Option<(&'a crate::Command<U, E>, &'a str, &'a str)> {
+    find_indexed_command(
+        None,
+        commands,
+        remaining_message,
+        case_insensitive,
+        parent_commands,
+    )
+}
+
+/// Like [`find_command`], but narrows down the commands on each level with the given
+/// [`crate::CommandIndex`] of `commands`, built by [`crate::CommandSet`]
+pub(crate) fn find_indexed_command<'a, U, E>(
+    index: Option<&crate::CommandIndex>,
+    commands: &'a [impl AsRef<crate::Command<U, E>>],
     remaining_message: &'a str,
     case_insensitive: bool,
     parent_commands: &mut Vec<&'a crate::Command<U, E>>,
@@ -155,7 +245,9 @@ pub fn find_command<'a, U, E>(
         (iter.next().unwrap(), iter.next().unwrap_or("").trim_start())
     };
 
-    for command in commands {
+    for (command, subcommand_index) in
+        crate::CommandIndex::candidates(index, commands, command_name)
+    {
         let primary_name_matches = string_equal(&command.name, command_name);
         let alias_matches = command
             .aliases
@@ -167,7 +259,8 @@ pub fn find_command<'a, U, E>(
 
         parent_commands.push(command);
         return Some(
-            find_command(
+            find_indexed_command(
+                subcommand_index,
                 &command.subcommands,
                 remaining_message,
                 case_insensitive,
@@ -183,13 +276,78 @@ pub fn find_command<'a, U, E>(
     None
 }
 
+/// Returns the number of single-character edits (insertions, deletions, substitutions and swaps of
+/// adjacent characters) needed to turn `a` into `b`, ignoring case
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().flat_map(char::to_lowercase).collect::<Vec<_>>();
+    let b = b.chars().flat_map(char::to_lowercase).collect::<Vec<_>>();
+    // Distances from the first i-2, i-1 and i characters of `a` to each prefix of `b`
+    let mut before_previous = vec![0; b.len() + 1];
+    let mut previous = (0..=b.len()).collect::<Vec<_>>();
+    let mut current = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = i;
+        for j in 1..=b.len() {
+            let substitution = previous[j - 1] + usize::from(a[i - 1] != b[j - 1]);
+            current[j] = substitution.min(previous[j] + 1).min(current[j - 1] + 1);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                current[j] = current[j].min(before_previous[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut before_previous, &mut previous);
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
+
+/// Finds the command name or alias that is most similar to the first word of `msg_content`, if any
+/// is similar enough to likely be what the user meant
+fn suggest_command<U, E>(
+    commands: &[impl AsRef<crate::Command<U, E>>],
+    msg_content: &str,
+) -> Option<String> {
+    let command_name = msg_content.split_whitespace().next()?;
+    // Allow roughly one typo per three characters
+    let max_distance = (command_name.chars().count() / 3).max(1);
+    commands
+        .iter()
+        .map(AsRef::as_ref)
+        .filter(|command| command.prefix_action.is_some() || !command.subcommands.is_empty())
+        .flat_map(|command| std::iter::once(&command.name).chain(&command.aliases))
+        .map(|name| (edit_distance(command_name, name), name))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, name)| name.clone())
+}
+
+/// Returns how long ago the message was sent, at millisecond resolution.
+///
+/// [`serenity::Timestamp`] only exposes whole seconds independently of its time library, so this
+/// reads the creation time from the message ID instead, which Discord stores in milliseconds.
+fn message_age(msg: &serenity::Message) -> std::time::Duration {
+    /// Start of 2015 in milliseconds since the Unix epoch, which snowflake IDs count from
+    const DISCORD_EPOCH_MILLIS: u64 = 1_420_070_400_000;
+
+    let sent_at = std::time::UNIX_EPOCH
+        + std::time::Duration::from_millis((msg.id.get() >> 22) + DISCORD_EPOCH_MILLIS);
+    std::time::SystemTime::now()
+        .duration_since(sent_at)
+        .unwrap_or_default()
+}
+
 /// Manually dispatches a message with the prefix framework
+///
+/// Like `invocation_data`, `replies` and `timing` are created fresh for every event, with
+/// [`crate::ReplyRegistry::new`] and [`crate::InvocationTiming::new`].
+#[allow(clippy::too_many_arguments)] // We need to pass them all in to create Context.
 pub async fn dispatch_message<'a, U: Send + Sync, E>(
     framework: crate::FrameworkContext<'a, U, E>,
     ctx: &'a serenity::Context,
     msg: &'a serenity::Message,
     trigger: crate::MessageDispatchTrigger,
     invocation_data: &'a tokio::sync::Mutex<Box<dyn std::any::Any + Send + Sync>>,
+    replies: &'a crate::ReplyRegistry,
+    timing: &'a crate::InvocationTiming,
     parent_commands: &'a mut Vec<&'a crate::Command<U, E>>,
 ) -> Result<(), crate::FrameworkError<'a, U, E>> {
     if let Some(ctx) = parse_invocation(
@@ -198,16 +356,22 @@ pub async fn dispatch_message<'a, U: Send + Sync, E>(
         msg,
         trigger,
         invocation_data,
+        replies,
+        timing,
         parent_commands,
     )
     .await?
     {
-        crate::catch_unwind_maybe(run_invocation(ctx))
-            .await
-            .map_err(|payload| crate::FrameworkError::CommandPanic {
-                payload,
-                ctx: ctx.into(),
-            })??;
+        let invocation = async {
+            crate::catch_unwind_maybe(run_invocation(ctx))
+                .await
+                .map_err(|payload| crate::FrameworkError::CommandPanic {
+                    payload,
+                    ctx: ctx.into(),
+                })
+                .and_then(std::convert::identity)
+        };
+        super::common::instrument_invocation(ctx.into(), invocation).await?;
     } else if let Some(non_command_message) = framework.options.prefix_options.non_command_message {
         non_command_message(&framework, ctx, msg)
             .await
@@ -227,16 +391,26 @@ pub async fn dispatch_message<'a, U: Send + Sync, E>(
 /// Returns `Ok(None)` if the message does not look like a command invocation.
 /// Returns `Err(...)` if the message _does_ look like a command invocation, but cannot be
 /// fully parsed.
+#[allow(clippy::too_many_arguments)] // We need to pass them all in to create Context.
 pub async fn parse_invocation<'a, U: Send + Sync, E>(
     framework: crate::FrameworkContext<'a, U, E>,
     ctx: &'a serenity::Context,
     msg: &'a serenity::Message,
     trigger: crate::MessageDispatchTrigger,
     invocation_data: &'a tokio::sync::Mutex<Box<dyn std::any::Any + Send + Sync>>,
+    replies: &'a crate::ReplyRegistry,
+    timing: &'a crate::InvocationTiming,
     parent_commands: &'a mut Vec<&'a crate::Command<U, E>>,
 ) -> Result<Option<crate::PrefixContext<'a, U, E>>, crate::FrameworkError<'a, U, E>> {
     // Check if we're allowed to invoke from bot messages
-    if msg.author.bot && framework.options.prefix_options.ignore_bots {
+    let prefix_options = &framework.options.prefix_options;
+    if msg.author.bot
+        && prefix_options.ignore_bots
+        && !prefix_options.allowed_bots.contains(&msg.author.id)
+        && !prefix_options
+            .allow_bot_message
+            .is_some_and(|allow| allow(msg))
+    {
         return Ok(None);
     }
 
@@ -254,19 +428,30 @@ pub async fn parse_invocation<'a, U: Send + Sync, E>(
     }
 
     // Strip prefix, trim whitespace between prefix and rest, split rest into command name and args
+    let inline_delimiters = &framework
+        .options
+        .prefix_options
+        .inline_invocation_delimiters;
     let (prefix, msg_content) = match strip_prefix(framework, ctx, msg).await {
         Some(x) => x,
-        None => return Ok(None),
+        None => match inline_delimiters
+            .as_ref()
+            .and_then(|(open, close)| find_inline_invocation(&msg.content, open, close))
+        {
+            Some(x) => x,
+            None => return Ok(None),
+        },
     };
     let msg_content = msg_content.trim_start();
 
-    let (command, invoked_command_name, args) = find_command(
-        &framework.options.commands,
+    let (command, invoked_command_name, args) = find_indexed_command(
+        Some(framework.commands.index()),
+        framework.commands,
         msg_content,
         framework.options.prefix_options.case_insensitive_commands,
         parent_commands,
     )
-    .ok_or(crate::FrameworkError::UnknownCommand {
+    .ok_or_else(|| crate::FrameworkError::UnknownCommand {
         ctx,
         msg,
         prefix,
@@ -274,6 +459,10 @@ pub async fn parse_invocation<'a, U: Send + Sync, E>(
         framework,
         invocation_data,
         trigger,
+        suggestion: match framework.options.prefix_options.suggest_unknown_commands {
+            true => suggest_command(framework.commands, msg_content),
+            false => None,
+        },
     })?;
 
     let action = match command.prefix_action {
@@ -293,19 +482,45 @@ pub async fn parse_invocation<'a, U: Send + Sync, E>(
         parent_commands,
         command,
         invocation_data,
+        replies,
+        timing,
         trigger,
         action,
         __non_exhaustive: (),
     }))
 }
 
+/// Returns the command that the invocation message originally invoked, if it was edited to invoke
+/// a different command and [`crate::PrefixFrameworkOptions::redispatch_edits`] is set
+pub(crate) fn original_command<'a, U, E>(
+    ctx: crate::PrefixContext<'a, U, E>,
+) -> Option<&'a crate::Command<U, E>> {
+    let prefix_options = &ctx.framework.options.prefix_options;
+    if ctx.trigger != crate::MessageDispatchTrigger::MessageEdit || !prefix_options.redispatch_edits
+    {
+        return None;
+    }
+    let name = prefix_options
+        .edit_tracker
+        .as_ref()?
+        .read()
+        .unwrap()
+        .original_command(ctx.msg.id)?;
+    if name == ctx.command.qualified_name {
+        return None;
+    }
+    ctx.framework.commands.command_by_qualified_name(&name)
+}
+
 /// Given an existing parsed command invocation from [`parse_invocation`], run it, including all the
 /// before and after code like checks and built in filters from edit tracking
 pub async fn run_invocation<U, E>(
     ctx: crate::PrefixContext<'_, U, E>,
 ) -> Result<(), crate::FrameworkError<'_, U, E>> {
     // Check if we should disregard this invocation if it was triggered by an edit
-    if ctx.trigger == crate::MessageDispatchTrigger::MessageEdit && !ctx.command.invoke_on_edit {
+    let invoke_on_edit = ctx.command.invoke_on_edit
+        || original_command(ctx).is_some_and(|command| command.invoke_on_edit);
+    if ctx.trigger == crate::MessageDispatchTrigger::MessageEdit && !invoke_on_edit {
         return Ok(());
     }
     if ctx.trigger == crate::MessageDispatchTrigger::MessageEditFromInvalid
@@ -313,10 +528,36 @@ pub async fn run_invocation<U, E>(
     {
         return Ok(());
     }
+    let is_edit = matches!(
+        ctx.trigger,
+        crate::MessageDispatchTrigger::MessageEdit
+            | crate::MessageDispatchTrigger::MessageEditFromInvalid
+    );
+    if let Some(window) = ctx.command.edit_tracking_window.filter(|_| is_edit) {
+        if message_age(ctx.msg) >= window {
+            return Ok(());
+        }
+    }
 
     if ctx.command.subcommand_required {
         // None of this command's subcommands were invoked, or else we'd have the subcommand in
         // ctx.command and not the parent command
+        if ctx
+            .framework
+            .options
+            .prefix_options
+            .help_on_missing_subcommand
+        {
+            let help = crate::builtins::help(
+                ctx.into(),
+                Some(&ctx.command.qualified_name),
+                crate::builtins::HelpConfiguration::default(),
+            );
+            if let Err(e) = help.await {
+                tracing::warn!("failed to send subcommand help: {}", e);
+            }
+            return Ok(());
+        }
         return Err(crate::FrameworkError::SubcommandRequired {
             ctx: crate::Context::Prefix(ctx),
         });
@@ -324,6 +565,14 @@ pub async fn run_invocation<U, E>(
 
     super::common::check_permissions_and_cooldown(ctx.into()).await?;
 
+    // Counts as running until this function returns
+    let _concurrency_slot = crate::ConcurrencySlot::acquire(ctx.into()).map_err(|scope| {
+        crate::FrameworkError::AlreadyRunning {
+            scope,
+            ctx: ctx.into(),
+        }
+    })?;
+
     // Typing is broadcasted as long as this object is alive
     let _typing_broadcaster = if ctx.command.broadcast_typing {
         Some(ctx.msg.channel_id.start_typing(&ctx.serenity_context.http))
@@ -342,12 +591,69 @@ pub async fn run_invocation<U, E>(
             .write()
             .unwrap()
             .track_command(ctx.msg, ctx.command.track_deletion);
+        edit_tracker
+            .write()
+            .unwrap()
+            .set_original_command(ctx.msg.id, &ctx.command.qualified_name);
     }
 
     // Execute command
-    (ctx.action)(ctx).await?;
+    ctx.timing.start_execution();
+    let result = crate::Next::new(&ctx.framework.options.middleware, |ctx| match ctx {
+        crate::Context::Prefix(ctx) => (ctx.action)(ctx),
+        crate::Context::Application(_) => {
+            unreachable!("prefix middleware chain got application context")
+        }
+    })
+    .run(crate::Context::Prefix(ctx))
+    .await;
+    ctx.timing.finish_execution();
+    let outcome = crate::Context::Prefix(ctx).outcome(result.is_ok());
+    if let Some(command_usage) = &ctx.framework.options.command_usage {
+        let guild_id = crate::Context::Prefix(ctx).guild_id();
+        command_usage.record(&ctx.command.qualified_name, guild_id, outcome.success);
+    }
+    (ctx.framework.options.post_command_result)(crate::Context::Prefix(ctx), outcome).await;
+    result?;
 
     (ctx.framework.options.post_command)(crate::Context::Prefix(ctx)).await;
 
     Ok(())
 }
+
+#[cfg(test)]
+#[test]
+fn test_split_prefix() {
+    assert_eq!(split_prefix("!help", "!", false), Some(("!", "help")));
+    assert_eq!(split_prefix("Bot help", "bot", false), None);
+    assert_eq!(
+        split_prefix("Bot help", "bot", true),
+        Some(("Bot", " help"))
+    );
+    assert_eq!(split_prefix("ÄBC", "äbc", true), Some(("ÄBC", "")));
+    assert_eq!(split_prefix("b", "bot", true), None);
+}
+
+#[cfg(test)]
+#[test]
+fn test_edit_distance() {
+    assert_eq!(edit_distance("ban", "ban"), 0);
+    assert_eq!(edit_distance("bna", "ban"), 1);
+    assert_eq!(edit_distance("Help", "hlep"), 1);
+    assert_eq!(edit_distance("pig", "ping"), 1);
+    assert_eq!(edit_distance("", "abc"), 3);
+    assert_eq!(edit_distance("kitten", "sitting"), 3);
+}
+
+#[cfg(test)]
+#[test]
+fn test_find_inline_invocation() {
+    let find = |content| find_inline_invocation(content, "{{", "}}");
+    assert_eq!(
+        find("have you tried {{docs Vec::push}}?"),
+        Some(("{{", "docs Vec::push"))
+    );
+    assert_eq!(find("{{ ping }} and {{ pong }}"), Some(("{{", "ping")));
+    assert_eq!(find("no invocation {{ here"), None);
+    assert_eq!(find("empty {{ }}"), None);
+}
@@ -6,10 +6,12 @@ use crate::serenity_prelude as serenity;
 fn find_matching_command<'a, 'b, U, E>(
     interaction_name: &str,
     interaction_options: &'b [serenity::ResolvedOption<'b>],
-    commands: &'a [crate::Command<U, E>],
+    index: Option<&crate::CommandIndex>,
+    commands: &'a [impl AsRef<crate::Command<U, E>>],
     parent_commands: &mut Vec<&'a crate::Command<U, E>>,
 ) -> Option<(&'a crate::Command<U, E>, &'b [serenity::ResolvedOption<'b>])> {
-    commands.iter().find_map(|cmd| {
+    let candidates = crate::CommandIndex::candidates(index, commands, interaction_name);
+    candidates.into_iter().find_map(|(cmd, subcommand_index)| {
         if interaction_name != cmd.name
             && Some(interaction_name) != cmd.context_menu_name.as_deref()
         {
@@ -26,7 +28,13 @@ fn find_matching_command<'a, 'b, U, E>(
                 })
         {
             parent_commands.push(cmd);
-            find_matching_command(sub_name, sub_interaction, &cmd.subcommands, parent_commands)
+            find_matching_command(
+                sub_name,
+                sub_interaction,
+                subcommand_index,
+                &cmd.subcommands,
+                parent_commands,
+            )
         } else {
             Some((cmd, interaction_options))
         }
@@ -45,13 +53,16 @@ fn extract_command<'a, U, E>(
     interaction_type: crate::CommandInteractionType,
     has_sent_initial_response: &'a std::sync::atomic::AtomicBool,
     invocation_data: &'a tokio::sync::Mutex<Box<dyn std::any::Any + Send + Sync>>,
+    replies: &'a crate::ReplyRegistry,
+    timing: &'a crate::InvocationTiming,
     options: &'a [serenity::ResolvedOption<'a>],
     parent_commands: &'a mut Vec<&'a crate::Command<U, E>>,
 ) -> Result<crate::ApplicationContext<'a, U, E>, crate::FrameworkError<'a, U, E>> {
     let search_result = find_matching_command(
         &interaction.data.name,
         options,
-        &framework.options.commands,
+        Some(framework.commands.index()),
+        framework.commands,
         parent_commands,
     );
     let (command, leaf_interaction_options) =
@@ -72,6 +83,8 @@ fn extract_command<'a, U, E>(
         parent_commands,
         has_sent_initial_response,
         invocation_data,
+        replies,
+        timing,
         __non_exhaustive: (),
     })
 }
@@ -85,6 +98,8 @@ pub async fn extract_command_and_run_checks<'a, U, E>(
     interaction_type: crate::CommandInteractionType,
     has_sent_initial_response: &'a std::sync::atomic::AtomicBool,
     invocation_data: &'a tokio::sync::Mutex<Box<dyn std::any::Any + Send + Sync>>,
+    replies: &'a crate::ReplyRegistry,
+    timing: &'a crate::InvocationTiming,
     options: &'a [serenity::ResolvedOption<'a>],
     parent_commands: &'a mut Vec<&'a crate::Command<U, E>>,
 ) -> Result<crate::ApplicationContext<'a, U, E>, crate::FrameworkError<'a, U, E>> {
@@ -95,6 +110,8 @@ pub async fn extract_command_and_run_checks<'a, U, E>(
         interaction_type,
         has_sent_initial_response,
         invocation_data,
+        replies,
+        timing,
         options,
         parent_commands,
     )?;
@@ -102,15 +119,74 @@ pub async fn extract_command_and_run_checks<'a, U, E>(
     Ok(ctx)
 }
 
+/// Runs the given command action. If the command hasn't sent its initial response after its
+/// [`crate::Command::auto_defer`] or [`crate::FrameworkOptions::auto_defer`] delay, the response
+/// is deferred in the meantime
+async fn run_with_auto_defer<'a, U, E>(
+    ctx: crate::ApplicationContext<'a, U, E>,
+    action: crate::BoxFuture<'a, Result<(), crate::FrameworkError<'a, U, E>>>,
+) -> Result<(), crate::FrameworkError<'a, U, E>> {
+    let Some(delay) = ctx.command.auto_defer.or(ctx.framework.options.auto_defer) else {
+        return action.await;
+    };
+
+    let timeout = Box::pin(tokio::time::sleep(delay));
+    match futures_util::future::select(action, timeout).await {
+        futures_util::future::Either::Left((result, _)) => result,
+        futures_util::future::Either::Right(((), action)) => {
+            if let Err(e) = ctx.defer_response(ctx.command.ephemeral).await {
+                tracing::warn!("failed to automatically defer response: {}", e);
+            }
+            action.await
+        }
+    }
+}
+
 /// Given the extracted application command data from [`extract_command`], runs the command,
 /// including all the before and after code like checks.
-async fn run_command<U, E>(
+async fn run_command<U: Send + Sync, E: Send>(
     ctx: crate::ApplicationContext<'_, U, E>,
 ) -> Result<(), crate::FrameworkError<'_, U, E>> {
     super::common::check_permissions_and_cooldown(ctx.into()).await?;
 
+    // Counts as running until this function returns
+    let _concurrency_slot = crate::ConcurrencySlot::acquire(ctx.into()).map_err(|scope| {
+        crate::FrameworkError::AlreadyRunning {
+            scope,
+            ctx: ctx.into(),
+        }
+    })?;
+
     (ctx.framework.options.pre_command)(crate::Context::Application(ctx)).await;
 
+    ctx.timing.start_execution();
+    let action_result = crate::Next::new(&ctx.framework.options.middleware, |ctx| match ctx {
+        crate::Context::Application(ctx) => Box::pin(run_action(ctx)),
+        crate::Context::Prefix(_) => {
+            unreachable!("application middleware chain got prefix context")
+        }
+    })
+    .run(crate::Context::Application(ctx))
+    .await;
+    ctx.timing.finish_execution();
+    let outcome = crate::Context::Application(ctx).outcome(action_result.is_ok());
+    if let Some(command_usage) = &ctx.framework.options.command_usage {
+        let guild_id = crate::Context::Application(ctx).guild_id();
+        command_usage.record(&ctx.command.qualified_name, guild_id, outcome.success);
+    }
+    (ctx.framework.options.post_command_result)(crate::Context::Application(ctx), outcome).await;
+    action_result?;
+
+    (ctx.framework.options.post_command)(crate::Context::Application(ctx)).await;
+
+    Ok(())
+}
+
+/// Runs the slash command or context menu action of the command, at the end of the
+/// [`crate::CommandMiddleware`] chain
+async fn run_action<U: Send + Sync, E: Send>(
+    ctx: crate::ApplicationContext<'_, U, E>,
+) -> Result<(), crate::FrameworkError<'_, U, E>> {
     // Check which interaction type we received and grab the command action and, if context menu,
     // the resolved click target, and execute the action
     let command_structure_mismatch_error = crate::FrameworkError::CommandStructureMismatch {
@@ -118,13 +194,13 @@ async fn run_command<U, E>(
         description: "received interaction type but command contained no \
                 matching action or interaction contained no matching context menu object",
     };
-    let action_result = match ctx.interaction.data.kind {
+    match ctx.interaction.data.kind {
         serenity::CommandType::ChatInput => {
             let action = ctx
                 .command
                 .slash_action
                 .ok_or(command_structure_mismatch_error)?;
-            action(ctx).await
+            run_with_auto_defer(ctx, action(ctx)).await
         }
         serenity::CommandType::User => {
             match (
@@ -134,8 +210,8 @@ async fn run_command<U, E>(
                 (
                     Some(crate::ContextMenuCommandAction::User(action)),
                     Some(serenity::ResolvedTarget::User(user, _)),
-                ) => action(ctx, (*user).clone()).await,
-                _ => return Err(command_structure_mismatch_error),
+                ) => run_with_auto_defer(ctx, action(ctx, (*user).clone())).await,
+                _ => Err(command_structure_mismatch_error),
             }
         }
         serenity::CommandType::Message => {
@@ -146,24 +222,20 @@ async fn run_command<U, E>(
                 (
                     Some(crate::ContextMenuCommandAction::Message(action)),
                     Some(serenity::ResolvedTarget::Message(message)),
-                ) => action(ctx, (*message).clone()).await,
-                _ => return Err(command_structure_mismatch_error),
+                ) => run_with_auto_defer(ctx, action(ctx, (*message).clone())).await,
+                _ => Err(command_structure_mismatch_error),
             }
         }
         other => {
             tracing::warn!("unknown interaction command type: {:?}", other);
-            return Ok(());
+            Ok(())
         }
-    };
-    action_result?;
-
-    (ctx.framework.options.post_command)(crate::Context::Application(ctx)).await;
-
-    Ok(())
+    }
 }
 
 /// Dispatches this interaction onto framework commands, i.e. runs the associated command
-pub async fn dispatch_interaction<'a, U, E>(
+#[allow(clippy::too_many_arguments)] // We need to pass them all in to create Context.
+pub async fn dispatch_interaction<'a, U: Send + Sync, E: Send>(
     framework: crate::FrameworkContext<'a, U, E>,
     ctx: &'a serenity::Context,
     interaction: &'a serenity::CommandInteraction,
@@ -171,6 +243,8 @@ pub async fn dispatch_interaction<'a, U, E>(
     has_sent_initial_response: &'a std::sync::atomic::AtomicBool,
     // Need to pass this in from outside because of lifetime issues
     invocation_data: &'a tokio::sync::Mutex<Box<dyn std::any::Any + Send + Sync>>,
+    replies: &'a crate::ReplyRegistry,
+    timing: &'a crate::InvocationTiming,
     // Need to pass this in from outside because of lifetime issues
     options: &'a [serenity::ResolvedOption<'a>],
     parent_commands: &'a mut Vec<&'a crate::Command<U, E>>,
@@ -182,16 +256,22 @@ pub async fn dispatch_interaction<'a, U, E>(
         crate::CommandInteractionType::Command,
         has_sent_initial_response,
         invocation_data,
+        replies,
+        timing,
         options,
         parent_commands,
     )?;
 
-    crate::catch_unwind_maybe(run_command(ctx))
-        .await
-        .map_err(|payload| crate::FrameworkError::CommandPanic {
-            payload,
-            ctx: ctx.into(),
-        })??;
+    let invocation = async {
+        crate::catch_unwind_maybe(run_command(ctx))
+            .await
+            .map_err(|payload| crate::FrameworkError::CommandPanic {
+                payload,
+                ctx: ctx.into(),
+            })
+            .and_then(std::convert::identity)
+    };
+    super::common::instrument_invocation(ctx.into(), invocation).await?;
 
     Ok(())
 }
@@ -260,6 +340,7 @@ async fn run_autocomplete<U, E>(
 
 /// Dispatches this interaction onto framework commands, i.e. runs the associated autocomplete
 /// callback
+#[allow(clippy::too_many_arguments)] // We need to pass them all in to create Context.
 pub async fn dispatch_autocomplete<'a, U, E>(
     framework: crate::FrameworkContext<'a, U, E>,
     ctx: &'a serenity::Context,
@@ -267,6 +348,8 @@ pub async fn dispatch_autocomplete<'a, U, E>(
     // Need to pass the following in from outside because of lifetime issues
     has_sent_initial_response: &'a std::sync::atomic::AtomicBool,
     invocation_data: &'a tokio::sync::Mutex<Box<dyn std::any::Any + Send + Sync>>,
+    replies: &'a crate::ReplyRegistry,
+    timing: &'a crate::InvocationTiming,
     options: &'a [serenity::ResolvedOption<'a>],
     parent_commands: &'a mut Vec<&'a crate::Command<U, E>>,
 ) -> Result<(), crate::FrameworkError<'a, U, E>> {
@@ -277,6 +360,8 @@ pub async fn dispatch_autocomplete<'a, U, E>(
         crate::CommandInteractionType::Autocomplete,
         has_sent_initial_response,
         invocation_data,
+        replies,
+        timing,
         options,
         parent_commands,
     )?;
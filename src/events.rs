@@ -0,0 +1,157 @@
+//! Handlers for specific Discord event types, see [`on`]
+//!
+//! ```rust
+//! # type Data = (); type Error = Box<dyn std::error::Error + Send + Sync>;
+//! use poise::events::{on, GuildMemberAddition};
+//!
+//! poise::FrameworkOptions::<Data, Error> {
+//!     event_handlers: vec![on::<GuildMemberAddition, _, _>(|_ctx, member, _framework, _data| {
+//!         Box::pin(async move {
+//!             println!("{} joined", member.user.name);
+//!             Ok(())
+//!         })
+//!     })],
+//!     ..Default::default()
+//! };
+//! ```
+
+use crate::{serenity_prelude as serenity, BoxFuture};
+
+/// A type of Discord event that handlers can be registered for with [`on`]
+///
+/// Implemented for the marker types in this module. You can implement it yourself for events
+/// that don't have a marker type yet.
+pub trait Event: 'static {
+    /// Data passed to the handlers of this event, borrowed from the [`serenity::FullEvent`]
+    type Args<'a>: Send;
+
+    /// Returns the handler arguments if the event is of this type
+    fn extract(event: &serenity::FullEvent) -> Option<Self::Args<'_>>;
+}
+
+/// Defines marker types for [`serenity::FullEvent`] variants. Variants with multiple fields pass
+/// them to the handler as a tuple
+macro_rules! events {
+    ($(
+        $(#[$attr:meta])*
+        $name:ident { $( $field:ident ),* } => $args:ty;
+    )*) => { $(
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy)]
+        pub struct $name;
+
+        impl Event for $name {
+            type Args<'a> = $args;
+
+            fn extract(event: &serenity::FullEvent) -> Option<Self::Args<'_>> {
+                match event {
+                    serenity::FullEvent::$name { $( $field, )* .. } => Some(( $( $field ),* )),
+                    _ => None,
+                }
+            }
+        }
+    )* };
+}
+
+events! {
+    /// The bot connected to Discord
+    Ready { data_about_bot } => &'a serenity::Ready;
+    /// A message was sent
+    Message { new_message } => &'a serenity::Message;
+    /// A message was edited. Passes the old message if it was cached, the new message if it's
+    /// cached, and the raw event
+    MessageUpdate { old_if_available, new, event }
+        => (&'a Option<serenity::Message>, &'a Option<serenity::Message>, &'a serenity::MessageUpdateEvent);
+    /// A message was deleted. Passes channel, message and guild ID
+    MessageDelete { channel_id, deleted_message_id, guild_id }
+        => (&'a serenity::ChannelId, &'a serenity::MessageId, &'a Option<serenity::GuildId>);
+    /// A reaction was added to a message
+    ReactionAdd { add_reaction } => &'a serenity::Reaction;
+    /// A reaction was removed from a message
+    ReactionRemove { removed_reaction } => &'a serenity::Reaction;
+    /// The bot joined a guild, or a guild became available. Passes the guild and whether the
+    /// bot just joined it
+    GuildCreate { guild, is_new } => (&'a serenity::Guild, &'a Option<bool>);
+    /// The bot left a guild, or a guild became unavailable. Passes the event data and the guild
+    /// if it was cached
+    GuildDelete { incomplete, full } => (&'a serenity::UnavailableGuild, &'a Option<serenity::Guild>);
+    /// A user joined a guild
+    GuildMemberAddition { new_member } => &'a serenity::Member;
+    /// A user left a guild or was kicked or banned. Passes guild ID, user and the member if it
+    /// was cached
+    GuildMemberRemoval { guild_id, user, member_data_if_available }
+        => (&'a serenity::GuildId, &'a serenity::User, &'a Option<serenity::Member>);
+    /// A channel was created
+    ChannelCreate { channel } => &'a serenity::GuildChannel;
+    /// A channel was deleted. Passes the channel and its cached messages, if any
+    ChannelDelete { channel, messages } => (&'a serenity::GuildChannel, &'a Option<Vec<serenity::Message>>);
+    /// A thread was created
+    ThreadCreate { thread } => &'a serenity::GuildChannel;
+    /// A user joined, left or moved between voice channels. Passes the old state if it was
+    /// cached and the new state
+    VoiceStateUpdate { old, new } => (&'a Option<serenity::VoiceState>, &'a serenity::VoiceState);
+    /// A shard changed its connection stage, e.g. connected or started resuming. See
+    /// [`crate::FrameworkContext::shard_stats`] for the current state of all shards
+    ShardStageUpdate { event } => &'a serenity::ShardStageUpdateEvent;
+    /// An interaction was created. Commands are dispatched by the framework regardless
+    InteractionCreate { interaction } => &'a serenity::Interaction;
+}
+
+/// Signature of a handler for the event type `T`
+pub type EventHandlerFn<T, U, E> = for<'a> fn(
+    &'a serenity::Context,
+    <T as Event>::Args<'a>,
+    crate::FrameworkContext<'a, U, E>,
+    &'a U,
+) -> BoxFuture<'a, Result<(), E>>;
+
+/// A handler for one event type, created with [`on`]. See
+/// [`crate::FrameworkOptions::event_handlers`]
+#[derive(derivative::Derivative)]
+#[derivative(Debug(bound = ""))]
+pub struct TypedEventHandler<U, E> {
+    /// Name of the event type, for debugging
+    event: &'static str,
+    /// Runs the handler if the event has the right type
+    #[derivative(Debug = "ignore")]
+    #[allow(clippy::type_complexity)]
+    run: Box<
+        dyn for<'a> Fn(
+                &'a serenity::Context,
+                &'a serenity::FullEvent,
+                crate::FrameworkContext<'a, U, E>,
+            ) -> Option<BoxFuture<'a, Result<(), E>>>
+            + Send
+            + Sync,
+    >,
+}
+
+impl<U, E> TypedEventHandler<U, E> {
+    /// Starts the handler if the event has the right type
+    pub(crate) fn run<'a>(
+        &self,
+        ctx: &'a serenity::Context,
+        event: &'a serenity::FullEvent,
+        framework: crate::FrameworkContext<'a, U, E>,
+    ) -> Option<BoxFuture<'a, Result<(), E>>> {
+        (self.run)(ctx, event, framework)
+    }
+}
+
+/// Creates a handler for the event type `T`, to be put into
+/// [`crate::FrameworkOptions::event_handlers`]. See the [module docs](self) for an example.
+///
+/// All handlers of an event run concurrently, together with
+/// [`crate::FrameworkOptions::event_handler`]. Errors are passed to
+/// [`crate::FrameworkOptions::on_error`] as [`crate::FrameworkError::EventHandler`].
+pub fn on<T: Event, U: 'static, E: 'static>(
+    handler: EventHandlerFn<T, U, E>,
+) -> TypedEventHandler<U, E> {
+    TypedEventHandler {
+        event: std::any::type_name::<T>(),
+        run: Box::new(move |ctx, event, framework| {
+            let args = T::extract(event)?;
+            Some(handler(ctx, args, framework, framework.user_data))
+        }),
+    }
+}
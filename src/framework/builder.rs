@@ -27,6 +27,8 @@ pub struct FrameworkBuilder<U, E> {
     commands: Vec<crate::Command<U, E>>,
     /// See [`Self::initialize_owners()`]
     initialize_owners: bool,
+    /// See [`Self::plugin()`]
+    plugins: Vec<Box<dyn crate::Plugin<U, E>>>,
 }
 
 impl<U, E> Default for FrameworkBuilder<U, E> {
@@ -36,6 +38,7 @@ impl<U, E> Default for FrameworkBuilder<U, E> {
             options: Default::default(),
             commands: Default::default(),
             initialize_owners: true,
+            plugins: Vec::new(),
         }
     }
 }
@@ -74,8 +77,18 @@ impl<U, E> FrameworkBuilder<U, E> {
         self
     }
 
+    /// Installs a plugin: its commands, event handlers and scheduled tasks are added to the
+    /// framework options, and its setup runs after the user data setup. See [`crate::Plugin`]
+    #[must_use]
+    pub fn plugin(mut self, plugin: impl crate::Plugin<U, E> + 'static) -> Self {
+        self.plugins.push(Box::new(plugin));
+        self
+    }
+
     /// Build the framework with the specified configuration.
     ///
+    /// Issues in the configuration found by [`crate::FrameworkOptions::validate`] are logged.
+    ///
     /// For more information, see [`FrameworkBuilder`]
     pub fn build(self) -> crate::Framework<U, E>
     where
@@ -90,8 +103,25 @@ impl<U, E> FrameworkBuilder<U, E> {
         // Build framework options by concatenating user-set options with commands and owners
         options.commands.extend(self.commands);
         options.initialize_owners = self.initialize_owners;
+        for plugin in &self.plugins {
+            options.commands.extend(plugin.commands());
+            options.event_handlers.extend(plugin.event_handlers());
+            options.scheduled_tasks.extend(plugin.scheduled_tasks());
+            plugin.insert_data(&options.data_map);
+        }
 
         // Create framework with specified settings
-        crate::Framework::new(options, setup)
+        let mut framework = crate::Framework::new(options, setup);
+        framework.plugins = self.plugins;
+
+        // The framework has filled in the qualified names, which the issues refer to
+        let issues = framework.options().validate_commands(&framework.commands());
+        for issue in issues {
+            match issue.is_error() {
+                true => tracing::error!("invalid framework configuration: {}", issue),
+                false => tracing::warn!("suspicious framework configuration: {}", issue),
+            }
+        }
+        framework
     }
 }
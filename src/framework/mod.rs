@@ -3,6 +3,8 @@
 use std::sync::Arc;
 
 pub use builder::*;
+pub use plugin::*;
+pub use shutdown::*;
 
 use crate::{
     serenity_prelude::{self as serenity, TeamMemberRole},
@@ -10,14 +12,19 @@ use crate::{
 };
 
 mod builder;
+mod plugin;
+mod shutdown;
 
 /// The main framework struct which stores all data and handles message and interaction dispatch.
 ///
 /// Technically, this is just an optional abstraction over [`crate::dispatch_event`] with some
 /// additional conveniences built-in:
 /// - fills in correct values for [`crate::Command::qualified_name`]: [`set_qualified_names`]
+/// - applies `FrameworkOptions::translations` to the commands (`i18n` feature)
 /// - spawns a background task to periodically clear edit tracker cache
 /// - sets up user data on the first Ready event
+/// - runs the setup of [`Plugin`]s and [`crate::FrameworkOptions::scheduled_tasks`] after the
+///   user data is set up
 /// - keeps track of shard manager and bot ID automatically
 ///
 /// You can build a bot without [`Framework`]: see the `manual_dispatch` example in the repository
@@ -28,6 +35,9 @@ pub struct Framework<U, E> {
     bot_id: std::sync::OnceLock<serenity::UserId>,
     /// Stores the framework options
     options: crate::FrameworkOptions<U, E>,
+    /// The commands, moved out of [`crate::FrameworkOptions::commands`] on construction and
+    /// swappable at runtime
+    commands: crate::CommandRegistry<U, E>,
 
     /// Initialized to Some during construction; so shouldn't be None at any observable point
     shard_manager: Option<Arc<serenity::ShardManager>>,
@@ -48,6 +58,21 @@ pub struct Framework<U, E> {
 
     /// Handle to the background task in order to `abort()` it on `Drop`
     edit_tracker_purge_task: Option<tokio::task::JoinHandle<()>>,
+    /// Keeps track of in-flight events for graceful shutdown
+    shutdown: ShutdownHandle,
+    /// Plugins installed with [`FrameworkBuilder::plugin`], whose setup runs after the user data
+    /// setup
+    plugins: Vec<Box<dyn Plugin<U, E>>>,
+}
+
+/// When the first [`Framework`] of this process was created, used as start time by
+/// [`crate::builtins::bot_stats`]
+static STARTED_AT: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+
+/// Returns when the first [`Framework`] of this process was created, if any
+#[cfg(feature = "cache")]
+pub(crate) fn started_at() -> Option<std::time::Instant> {
+    STARTED_AT.get().copied()
 }
 
 impl<U, E> Framework<U, E> {
@@ -72,7 +97,7 @@ impl<U, E> Framework<U, E> {
     /// user ID or connected guilds can be made available to the user data setup function. The user
     /// data setup is not allowed to return Result because there would be no reasonable
     /// course of action on error.
-    pub fn new<F>(options: crate::FrameworkOptions<U, E>, setup: F) -> Self
+    pub fn new<F>(mut options: crate::FrameworkOptions<U, E>, setup: F) -> Self
     where
         F: Send
             + Sync
@@ -85,21 +110,64 @@ impl<U, E> Framework<U, E> {
         U: Send + Sync + 'static,
         E: Send + 'static,
     {
+        STARTED_AT.get_or_init(std::time::Instant::now);
+        let commands = crate::CommandRegistry::new(&mut options);
         Self {
             user_data: std::sync::OnceLock::new(),
             bot_id: std::sync::OnceLock::new(),
             setup: std::sync::Mutex::new(Some(Box::new(setup))),
             edit_tracker_purge_task: None,
             shard_manager: None,
+            shutdown: ShutdownHandle::new(
+                options.shutdown_grace_period,
+                options.prefix_options.edit_tracker.clone(),
+            ),
+            plugins: Vec::new(),
             options,
+            commands,
         }
     }
 
-    /// Return the stored framework options, including commands.
+    /// Return the stored framework options.
+    ///
+    /// The commands are moved out of [`crate::FrameworkOptions::commands`] when the framework is
+    /// created, see [`Self::commands`] instead.
     pub fn options(&self) -> &crate::FrameworkOptions<U, E> {
         &self.options
     }
 
+    /// Returns the current commands of the framework, see [`crate::CommandRegistry::load`]
+    pub fn commands(&self) -> Arc<crate::CommandSet<U, E>> {
+        self.commands.load()
+    }
+
+    /// Adds a command, replacing an existing top-level command with the same name. Its qualified
+    /// names are filled in and translations are applied, as on startup.
+    ///
+    /// This works while the bot is running: events received afterwards are dispatched to the new
+    /// commands, while running invocations finish with the commands they started with (see
+    /// [`crate::CommandRegistry`]). Get the framework from the user data setup or a
+    /// [`crate::Plugin`], or use [`crate::FrameworkContext::add_command`] from within commands.
+    /// Application commands have to be registered again for changes to show up on Discord.
+    pub fn add_command(&self, command: crate::Command<U, E>) {
+        self.commands.add_command(&self.options, command);
+    }
+
+    /// Removes the top-level command with the given name and returns it, if it exists. See
+    /// [`Self::add_command`] for when changes take effect
+    pub fn remove_command(&self, name: &str) -> Option<Arc<crate::Command<U, E>>> {
+        self.commands.remove_command(name)
+    }
+
+    /// Replaces all commands and returns the previous ones. See [`Self::add_command`] for when
+    /// changes take effect
+    pub fn replace_commands(
+        &self,
+        commands: Vec<crate::Command<U, E>>,
+    ) -> Arc<crate::CommandSet<U, E>> {
+        self.commands.replace_commands(&self.options, commands)
+    }
+
     /// Returns the serenity's client shard manager.
     // Returns a reference so you can plug it into [`FrameworkContext`]
     pub fn shard_manager(&self) -> &Arc<serenity::ShardManager> {
@@ -108,6 +176,26 @@ impl<U, E> Framework<U, E> {
             .expect("framework should have started")
     }
 
+    /// Returns the connection stage and heartbeat latency of every shard, see
+    /// [`crate::ShardStats`]. Panics if the framework hasn't started yet
+    pub async fn shard_stats(&self) -> Vec<crate::ShardStats> {
+        crate::shard_stats(self.shard_manager()).await
+    }
+
+    /// Returns a handle that can shut down the bot gracefully, see [`ShutdownHandle::shutdown`].
+    ///
+    /// Get the handle before passing the framework to serenity's client builder, because the
+    /// framework isn't accessible afterwards. From within commands, use
+    /// [`crate::FrameworkContext::shutdown_handle`].
+    pub fn shutdown_handle(&self) -> ShutdownHandle {
+        self.shutdown.clone()
+    }
+
+    /// Shuts the bot down gracefully, see [`ShutdownHandle::shutdown`]
+    pub async fn shutdown(&self) {
+        self.shutdown.shutdown().await
+    }
+
     /// Retrieves user data, or blocks until it has been initialized (once the Ready event has been
     /// received).
     pub async fn user_data(&self) -> &U {
@@ -131,14 +219,14 @@ impl<U, E> Drop for Framework<U, E> {
 #[serenity::async_trait]
 impl<U: Send + Sync, E: Send + Sync> serenity::Framework for Framework<U, E> {
     async fn init(&mut self, client: &serenity::Client) {
-        set_qualified_names(&mut self.options.commands);
-
         message_content_intent_sanity_check(
             &self.options.prefix_options,
             client.shard_manager.intents(),
         );
 
         self.shard_manager = Some(client.shard_manager.clone());
+        self.shutdown
+            .set_shard_manager(client.shard_manager.clone());
 
         if self.options.initialize_owners {
             if let Err(e) = insert_owners_from_http(&client.http, &mut self.options.owners).await {
@@ -165,22 +253,43 @@ async fn raw_dispatch_event<U, E>(
     event: serenity::FullEvent,
 ) where
     U: Send + Sync,
+    E: Send,
 {
+    let Some(guard) = framework.shutdown.start_dispatch() else {
+        return;
+    };
+
+    let mut start_scheduled_tasks = false;
     if let serenity::FullEvent::Ready { data_about_bot } = &event {
         let _: Result<_, _> = framework.bot_id.set(data_about_bot.user.id);
         let setup = Option::take(&mut *framework.setup.lock().unwrap());
         if let Some(setup) = setup {
             match setup(&ctx, data_about_bot, framework).await {
                 Ok(user_data) => {
-                    let _: Result<_, _> = framework.user_data.set(user_data);
+                    let user_data = framework.user_data.get_or_init(|| user_data);
+                    for plugin in &framework.plugins {
+                        if let Err(error) = plugin.setup(&ctx, data_about_bot, user_data).await {
+                            tracing::warn!("setup of plugin `{}` failed", plugin.name());
+                            crate::FrameworkError::Setup {
+                                error,
+                                framework,
+                                data_about_bot,
+                                ctx: &ctx,
+                            }
+                            .handle(&framework.options)
+                            .await
+                        }
+                    }
+                    start_scheduled_tasks = true;
                 }
                 Err(error) => {
-                    (framework.options.on_error)(crate::FrameworkError::Setup {
+                    crate::FrameworkError::Setup {
                         error,
                         framework,
                         data_about_bot,
                         ctx: &ctx,
-                    })
+                    }
+                    .handle(&framework.options)
                     .await
                 }
             }
@@ -195,13 +304,33 @@ async fn raw_dispatch_event<U, E>(
         .bot_id
         .get()
         .expect("bot ID not set even though we awaited Ready");
+    // Loaded once, so the whole event sees the same commands even if they're swapped meanwhile
+    let commands = framework.commands.load();
     let framework = crate::FrameworkContext {
         bot_id,
         options: &framework.options,
+        commands: &commands,
+        command_registry: &framework.commands,
         user_data,
         shard_manager: framework.shard_manager(),
+        shutdown_handle: Some(&framework.shutdown),
+    };
+    let dispatch = async {
+        crate::dispatch_event(framework, &ctx, event).await;
+        // Scheduled tasks below don't count as in flight for graceful shutdown
+        drop(guard);
     };
-    crate::dispatch_event(framework, &ctx, event).await;
+    if start_scheduled_tasks {
+        // Tasks need to borrow the framework, so they run as part of this dispatch task instead
+        // of being spawned separately
+        futures_util::future::join(
+            dispatch,
+            crate::scheduler::run_scheduled_tasks(&ctx, framework),
+        )
+        .await;
+    } else {
+        dispatch.await;
+    }
 }
 
 /// Traverses commands recursively and sets [`crate::Command::qualified_name`] to its actual value
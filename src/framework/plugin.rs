@@ -0,0 +1,71 @@
+//! Bundling of commands, event handlers and tasks into reusable plugins, see [`Plugin`]
+
+use crate::{serenity_prelude as serenity, BoxFuture};
+
+/// A bundle of commands, event handlers, scheduled tasks and setup code that is installed as a
+/// whole with [`crate::FrameworkBuilder::plugin`]. Useful to split large bots into modules or
+/// crates. All methods have defaults that provide nothing.
+///
+/// ```rust
+/// # type Data = (); type Error = Box<dyn std::error::Error + Send + Sync>;
+/// # #[poise::command(slash_command)]
+/// # async fn play(ctx: poise::Context<'_, Data, Error>) -> Result<(), Error> { Ok(()) }
+/// # #[poise::command(slash_command)]
+/// # async fn skip(ctx: poise::Context<'_, Data, Error>) -> Result<(), Error> { Ok(()) }
+/// struct MusicPlugin;
+///
+/// impl poise::Plugin<Data, Error> for MusicPlugin {
+///     fn name(&self) -> &str {
+///         "music"
+///     }
+///
+///     fn commands(&self) -> Vec<poise::Command<Data, Error>> {
+///         vec![play(), skip()]
+///     }
+/// }
+///
+/// let framework = poise::Framework::builder()
+///     .options(poise::FrameworkOptions::default())
+///     .plugin(MusicPlugin)
+///     .setup(|_, _, _| Box::pin(async { Ok(()) }))
+///     .build();
+/// ```
+pub trait Plugin<U, E>: Send + Sync {
+    /// Name of the plugin, used in logs
+    fn name(&self) -> &str;
+
+    /// Commands to add to [`crate::FrameworkOptions::commands`]
+    fn commands(&self) -> Vec<crate::Command<U, E>> {
+        Vec::new()
+    }
+
+    /// Handlers to add to [`crate::FrameworkOptions::event_handlers`]
+    fn event_handlers(&self) -> Vec<crate::events::TypedEventHandler<U, E>> {
+        Vec::new()
+    }
+
+    /// Tasks to add to [`crate::FrameworkOptions::scheduled_tasks`]
+    fn scheduled_tasks(&self) -> Vec<crate::ScheduledTask<U, E>> {
+        Vec::new()
+    }
+
+    /// Called when the framework is built, to store the plugin's state in
+    /// [`crate::FrameworkOptions::data_map`]. Commands of the plugin can access it with
+    /// [`crate::Context::data_get`]
+    fn insert_data(&self, data_map: &crate::DataMap) {
+        let _ = data_map;
+    }
+
+    /// Called once after the user data has been set up, before scheduled tasks are started.
+    /// Errors are passed to [`crate::FrameworkOptions::on_error`] as
+    /// [`crate::FrameworkError::Setup`]
+    fn setup<'a>(
+        &'a self,
+        ctx: &'a serenity::Context,
+        ready: &'a serenity::Ready,
+        data: &'a U,
+    ) -> BoxFuture<'a, Result<(), E>> {
+        let _ = (ctx, ready, data);
+        Box::pin(async { Ok(()) })
+    }
+}
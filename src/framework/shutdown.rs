@@ -0,0 +1,167 @@
+//! Graceful shutdown of a [`crate::Framework`]
+
+use std::sync::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    Arc,
+};
+
+use crate::serenity_prelude as serenity;
+
+/// State shared between the framework and its [`ShutdownHandle`]s
+#[derive(Debug)]
+struct ShutdownState {
+    /// Set once shutdown has begun; no new events are dispatched after that
+    shutting_down: AtomicBool,
+    /// Number of events currently being dispatched
+    in_flight: AtomicUsize,
+    /// Notified whenever an event finishes dispatching
+    dispatch_finished: tokio::sync::Notify,
+    /// How long to wait for in-flight events before disconnecting anyway
+    grace_period: std::time::Duration,
+    /// Set when the framework is initialized by serenity
+    shard_manager: std::sync::OnceLock<Arc<serenity::ShardManager>>,
+    /// Edit tracker to purge before disconnecting
+    edit_tracker: Option<Arc<std::sync::RwLock<crate::EditTracker>>>,
+    /// Exit code requested with [`ShutdownHandle::shutdown_with_exit_code`]
+    exit_code: std::sync::OnceLock<i32>,
+}
+
+/// Shuts down a [`crate::Framework`] gracefully, see [`crate::Framework::shutdown_handle`]
+#[derive(Debug, Clone)]
+pub struct ShutdownHandle {
+    /// Shared with the framework
+    state: Arc<ShutdownState>,
+}
+
+/// Marks an event as in flight while alive
+pub(super) struct DispatchGuard<'a> {
+    /// Shared with the framework
+    state: &'a ShutdownState,
+}
+
+impl Drop for DispatchGuard<'_> {
+    fn drop(&mut self) {
+        self.state.in_flight.fetch_sub(1, Ordering::SeqCst);
+        self.state.dispatch_finished.notify_waiters();
+    }
+}
+
+impl ShutdownHandle {
+    /// Creates the handle of a new framework
+    pub(super) fn new(
+        grace_period: std::time::Duration,
+        edit_tracker: Option<Arc<std::sync::RwLock<crate::EditTracker>>>,
+    ) -> Self {
+        Self {
+            state: Arc::new(ShutdownState {
+                shutting_down: AtomicBool::new(false),
+                in_flight: AtomicUsize::new(0),
+                dispatch_finished: tokio::sync::Notify::new(),
+                grace_period,
+                shard_manager: std::sync::OnceLock::new(),
+                edit_tracker,
+                exit_code: std::sync::OnceLock::new(),
+            }),
+        }
+    }
+
+    /// Stores the shard manager once serenity has initialized the framework
+    pub(super) fn set_shard_manager(&self, shard_manager: Arc<serenity::ShardManager>) {
+        let _: Result<_, _> = self.state.shard_manager.set(shard_manager);
+    }
+
+    /// Marks an event as in flight, or returns None if the framework is shutting down and the
+    /// event should be dropped
+    pub(super) fn start_dispatch(&self) -> Option<DispatchGuard<'_>> {
+        self.state.in_flight.fetch_add(1, Ordering::SeqCst);
+        let guard = DispatchGuard { state: &self.state };
+        match self.is_shutting_down() {
+            true => None,
+            false => Some(guard),
+        }
+    }
+
+    /// Returns true once [`Self::shutdown`] has been called
+    pub fn is_shutting_down(&self) -> bool {
+        self.state.shutting_down.load(Ordering::SeqCst)
+    }
+
+    /// Shuts the bot down gracefully:
+    /// 1. stops dispatching new events, so no new commands are started
+    /// 2. waits for running commands and event handlers to finish, for at most
+    ///    [`crate::FrameworkOptions::shutdown_grace_period`]
+    /// 3. purges outdated entries from the edit tracker
+    /// 4. disconnects all shards, which makes [`serenity::Client::start`] return
+    pub async fn shutdown(&self) {
+        self.state.shutting_down.store(true, Ordering::SeqCst);
+
+        let drained = tokio::time::timeout(self.state.grace_period, async {
+            loop {
+                let finished = self.state.dispatch_finished.notified();
+                if self.state.in_flight.load(Ordering::SeqCst) == 0 {
+                    break;
+                }
+                finished.await;
+            }
+        })
+        .await;
+        if drained.is_err() {
+            tracing::warn!(
+                "{} events still being processed after the shutdown grace period",
+                self.state.in_flight.load(Ordering::SeqCst)
+            );
+        }
+
+        if let Some(edit_tracker) = &self.state.edit_tracker {
+            edit_tracker.write().unwrap().purge();
+        }
+
+        match self.state.shard_manager.get() {
+            Some(shard_manager) => shard_manager.shutdown_all().await,
+            None => tracing::warn!("framework shut down before it was started"),
+        }
+    }
+
+    /// Like [`Self::shutdown`], but additionally records an exit code that can be retrieved with
+    /// [`Self::exit_code`] once the client has stopped. Process managers can use it to tell a
+    /// restart apart from a stop:
+    ///
+    /// ```rust,no_run
+    /// # use poise::serenity_prelude as serenity;
+    /// # async fn _test(framework: poise::Framework<(), ()>, token: String) {
+    /// let shutdown_handle = framework.shutdown_handle();
+    /// let mut client = serenity::ClientBuilder::new(token, serenity::GatewayIntents::non_privileged())
+    ///     .framework(framework)
+    ///     .await
+    ///     .unwrap();
+    /// client.start().await.unwrap();
+    /// std::process::exit(shutdown_handle.exit_code().unwrap_or(0));
+    /// # }
+    /// ```
+    pub async fn shutdown_with_exit_code(&self, exit_code: i32) {
+        let _: Result<_, _> = self.state.exit_code.set(exit_code);
+        self.shutdown().await
+    }
+
+    /// Returns the exit code passed to [`Self::shutdown_with_exit_code`], if any
+    pub fn exit_code(&self) -> Option<i32> {
+        self.state.exit_code.get().copied()
+    }
+
+    /// Spawns a task that shuts the bot down once the given future completes, e.g. on Ctrl+C:
+    ///
+    /// ```rust,no_run
+    /// # async fn ctrl_c() {}
+    /// # async fn _test(framework: poise::Framework<(), ()>) {
+    /// // With tokio's `signal` feature: `async { let _ = tokio::signal::ctrl_c().await; }`
+    /// framework.shutdown_handle().shutdown_on(ctrl_c());
+    /// # }
+    /// ```
+    pub fn shutdown_on(&self, signal: impl std::future::Future<Output = ()> + Send + 'static) {
+        let handle = self.clone();
+        tokio::spawn(async move {
+            signal.await;
+            handle.shutdown().await;
+        });
+    }
+}
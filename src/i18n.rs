@@ -0,0 +1,364 @@
+//! Localization of commands with [Fluent](https://projectfluent.org/) files (`i18n` feature)
+//!
+//! [`Translations`] loads one `.ftl` file per locale. When set as
+//! [`crate::FrameworkOptions::translations`], the framework fills in the localized names and
+//! descriptions of all commands, parameters and choices on startup, so they're registered with
+//! Discord. The translations can also be used for command responses, see [`crate::Context::t`].
+//!
+//! # Key naming scheme
+//!
+//! Each command is a message whose ID is the [qualified name](crate::Command::qualified_name) with
+//! spaces replaced by `-`. Its value is the localized command name. Descriptions and parameters
+//! are attributes of that message. Choices are separate messages, with the parameter name and the
+//! choice name appended to the command message ID. Choice names are lowercased and all characters
+//! other than letters and digits are replaced by `-`.
+//!
+//! ```ftl
+//! # Command `config set`
+//! config-set = einstellen
+//!     .description = Ändert eine Einstellung
+//!     # Name and description of parameter `key`
+//!     .key = schlüssel
+//!     .key-description = Welche Einstellung geändert wird
+//!
+//! # Choice "Dark mode" of parameter `key`
+//! config-set-key-dark-mode = Dunkler Modus
+//! ```
+//!
+//! Missing entries are skipped. Entries of the fallback locale are used as
+//! [`crate::Command::description`] and [`crate::CommandParameter::description`] where those are
+//! empty; names are never changed, because prefix commands are invoked by them.
+
+use std::collections::HashMap;
+
+#[doc(no_inline)]
+pub use fluent::{FluentArgs, FluentValue};
+
+/// Thread-safe Fluent bundle of a single locale
+type FluentBundle = fluent::bundle::FluentBundle<
+    fluent::FluentResource,
+    intl_memoizer::concurrent::IntlLangMemoizer,
+>;
+
+/// Error while loading [`Translations`]
+#[derive(Debug)]
+pub enum TranslationError {
+    /// A translation file couldn't be read
+    #[non_exhaustive]
+    Io {
+        /// Path of the file
+        path: std::path::PathBuf,
+        /// Error from the file system
+        error: std::io::Error,
+    },
+    /// The locale isn't a valid language identifier
+    #[non_exhaustive]
+    InvalidLocale {
+        /// The locale as given, e.g. derived from a file name
+        locale: String,
+    },
+    /// A translation file contains syntax errors or duplicate messages
+    #[non_exhaustive]
+    InvalidFtl {
+        /// Locale of the translation file
+        locale: String,
+        /// Human-readable description of the errors
+        errors: String,
+    },
+    #[doc(hidden)]
+    __NonExhaustive(std::convert::Infallible),
+}
+
+impl std::fmt::Display for TranslationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, error } => write!(f, "couldn't read {}: {}", path.display(), error),
+            Self::InvalidLocale { locale } => write!(f, "invalid locale `{}`", locale),
+            Self::InvalidFtl { locale, errors } => {
+                write!(f, "invalid translations for `{}`: {}", locale, errors)
+            }
+            Self::__NonExhaustive(unreachable) => match *unreachable {},
+        }
+    }
+}
+
+impl std::error::Error for TranslationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Fluent translations for all locales of the bot. See the [module docs](self)
+pub struct Translations {
+    /// Locale whose texts are used when there's no translation for the requested locale
+    fallback_locale: String,
+    /// Bundles by locale
+    bundles: HashMap<String, FluentBundle>,
+}
+
+impl std::fmt::Debug for Translations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Translations")
+            .field("fallback_locale", &self.fallback_locale)
+            .field("locales", &self.bundles.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl Translations {
+    /// Creates empty translations. Texts of `fallback_locale` are used for locales without
+    /// translation
+    pub fn new(fallback_locale: impl Into<String>) -> Self {
+        Self {
+            fallback_locale: fallback_locale.into(),
+            bundles: HashMap::new(),
+        }
+    }
+
+    /// Loads all `<locale>.ftl` files in the directory, like `translations/en-US.ftl` and
+    /// `translations/de.ftl`
+    pub fn load_dir(
+        dir: impl AsRef<std::path::Path>,
+        fallback_locale: impl Into<String>,
+    ) -> Result<Self, TranslationError> {
+        let dir = dir.as_ref();
+        let io_error = |path: &std::path::Path| {
+            let path = path.to_owned();
+            move |error| TranslationError::Io { path, error }
+        };
+
+        let mut translations = Self::new(fallback_locale);
+        for entry in std::fs::read_dir(dir).map_err(io_error(dir))? {
+            let path = entry.map_err(io_error(dir))?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some("ftl") {
+                continue;
+            }
+            let Some(locale) = path.file_stem().and_then(|stem| stem.to_str()) else {
+                continue;
+            };
+            let source = std::fs::read_to_string(&path).map_err(io_error(&path))?;
+            translations.add_ftl(locale, source)?;
+        }
+        Ok(translations)
+    }
+
+    /// Adds the translations of a locale from the contents of a `.ftl` file
+    pub fn add_ftl(&mut self, locale: &str, source: String) -> Result<(), TranslationError> {
+        let invalid_ftl = |errors: String| TranslationError::InvalidFtl {
+            locale: locale.to_owned(),
+            errors,
+        };
+
+        let resource = fluent::FluentResource::try_new(source)
+            .map_err(|(_, errors)| invalid_ftl(format!("{:?}", errors)))?;
+        let language = locale
+            .parse()
+            .map_err(|_| TranslationError::InvalidLocale {
+                locale: locale.to_owned(),
+            })?;
+        let mut bundle = FluentBundle::new_concurrent(vec![language]);
+        // Otherwise, Fluent wraps arguments in invisible Unicode isolation marks
+        bundle.set_use_isolating(false);
+        bundle
+            .add_resource(resource)
+            .map_err(|errors| invalid_ftl(format!("{:?}", errors)))?;
+
+        self.bundles.insert(locale.to_owned(), bundle);
+        Ok(())
+    }
+
+    /// Returns the locales with translations
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        self.bundles.keys().map(|locale| &**locale)
+    }
+
+    /// Formats a single message (or one of its attributes) of the given locale, without falling
+    /// back to other locales
+    fn format_in(
+        &self,
+        locale: &str,
+        id: &str,
+        attribute: Option<&str>,
+        args: Option<&FluentArgs<'_>>,
+    ) -> Option<String> {
+        let bundle = self.bundles.get(locale)?;
+        let message = bundle.get_message(id)?;
+        let pattern = match attribute {
+            Some(attribute) => message.get_attribute(attribute)?.value(),
+            None => message.value()?,
+        };
+        let mut errors = Vec::new();
+        let text = bundle.format_pattern(pattern, args, &mut errors);
+        if !errors.is_empty() {
+            tracing::warn!(
+                "errors while formatting `{}` in {}: {:?}",
+                id,
+                locale,
+                errors
+            );
+        }
+        Some(text.into_owned())
+    }
+
+    /// Formats a message (or one of its attributes) in the given locale, e.g.
+    /// [`crate::Context::locale`]. Falls back to the language without region and then to the
+    /// fallback locale. Returns None if the message doesn't exist in any of them
+    pub fn get(
+        &self,
+        locale: Option<&str>,
+        id: &str,
+        attribute: Option<&str>,
+        args: Option<&FluentArgs<'_>>,
+    ) -> Option<String> {
+        let language = locale.and_then(|locale| locale.split('-').next());
+        [locale, language, Some(&*self.fallback_locale)]
+            .into_iter()
+            .flatten()
+            .find_map(|locale| self.format_in(locale, id, attribute, args))
+    }
+
+    /// Fills in the localizations of the given commands, their subcommands, parameters and
+    /// choices. Called by the framework on startup for
+    /// [`crate::FrameworkOptions::translations`]. Requires the qualified names to be set, see
+    /// [`crate::set_qualified_names`]
+    pub fn apply_to_commands<U, E>(&self, commands: &mut [crate::Command<U, E>]) {
+        for command in commands {
+            self.apply_to_command(command);
+            self.apply_to_commands(&mut command.subcommands);
+        }
+    }
+
+    /// Fills in the localizations of a single command, see [`Self::apply_to_commands`]
+    fn apply_to_command<U, E>(&self, command: &mut crate::Command<U, E>) {
+        let id = command.qualified_name.replace(' ', "-");
+
+        for locale in self.bundles.keys() {
+            let format = |attribute: Option<&str>| self.format_in(locale, &id, attribute, None);
+
+            if let Some(name) = format(None) {
+                command.name_localizations.insert(locale.clone(), name);
+            }
+            if let Some(description) = format(Some("description")) {
+                command
+                    .description_localizations
+                    .insert(locale.clone(), description);
+            }
+            for parameter in &mut command.parameters {
+                if let Some(name) = format(Some(&parameter.name)) {
+                    parameter.name_localizations.insert(locale.clone(), name);
+                }
+                let description_attribute = format!("{}-description", parameter.name);
+                if let Some(description) = format(Some(&description_attribute)) {
+                    parameter
+                        .description_localizations
+                        .insert(locale.clone(), description);
+                }
+                for choice in &mut parameter.choices {
+                    let choice_id = format!("{}-{}-{}", id, parameter.name, slug(&choice.name));
+                    if let Some(name) = self.format_in(locale, &choice_id, None, None) {
+                        choice.localizations.insert(locale.clone(), name);
+                    }
+                }
+            }
+        }
+
+        let fallback =
+            |attribute: &str| self.format_in(&self.fallback_locale, &id, Some(attribute), None);
+        if command.description.is_none() {
+            command.description = fallback("description");
+        }
+        for parameter in &mut command.parameters {
+            if parameter.description.is_none() {
+                parameter.description = fallback(&format!("{}-description", parameter.name));
+            }
+        }
+    }
+}
+
+/// Turns a choice name into a valid part of a Fluent message ID, which may only contain ASCII
+/// letters, digits, `-` and `_`
+fn slug(name: &str) -> String {
+    name.chars()
+        .map(|c| match c.is_ascii_alphanumeric() {
+            true => c.to_ascii_lowercase(),
+            false => '-',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[test]
+fn test_slug() {
+    assert_eq!(slug("Dark Mode"), "dark-mode");
+    assert_eq!(slug("Größe"), "gr--e");
+}
+
+#[cfg(test)]
+#[test]
+fn test_translations() {
+    let mut translations = Translations::new("en-US");
+    let en = "
+config-set = set
+    .description = Change a setting
+    .key-description = Which setting to change
+greeting = Hello { $name }!
+";
+    let de = "
+config-set = einstellen
+    .description = Ändert eine Einstellung
+    .key = schlüssel
+config-set-key-dark-mode = Dunkler Modus
+";
+    translations.add_ftl("en-US", en.into()).unwrap();
+    translations.add_ftl("de", de.into()).unwrap();
+
+    let mut args = FluentArgs::new();
+    args.set("name", "poise");
+    assert_eq!(
+        translations.get(Some("de"), "greeting", None, Some(&args)),
+        Some("Hello poise!".into())
+    );
+    assert_eq!(
+        translations.get(Some("de-AT"), "config-set", None, None),
+        Some("einstellen".into())
+    );
+
+    let mut command = crate::Command::<(), ()> {
+        name: "set".into(),
+        qualified_name: "config set".into(),
+        parameters: vec![crate::CommandParameter {
+            name: "key".into(),
+            name_localizations: HashMap::new(),
+            description: None,
+            description_localizations: HashMap::new(),
+            required: true,
+            channel_types: None,
+            choices: vec![crate::CommandParameterChoice {
+                name: "Dark mode".into(),
+                localizations: HashMap::new(),
+                value: None,
+                __non_exhaustive: (),
+            }],
+            dynamic_choices: None,
+            type_setter: None,
+            autocomplete_callback: None,
+            __non_exhaustive: (),
+        }],
+        ..Default::default()
+    };
+    translations.apply_to_commands(std::slice::from_mut(&mut command));
+
+    assert_eq!(command.name, "set");
+    assert_eq!(command.description.as_deref(), Some("Change a setting"));
+    assert_eq!(command.name_localizations["de"], "einstellen");
+    let parameter = &command.parameters[0];
+    assert_eq!(parameter.name_localizations["de"], "schlüssel");
+    assert_eq!(
+        parameter.description.as_deref(),
+        Some("Which setting to change")
+    );
+    assert_eq!(parameter.choices[0].localizations["de"], "Dunkler Modus");
+}
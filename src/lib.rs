@@ -274,7 +274,7 @@ client.unwrap().start().await.unwrap();
 As explained in [Introduction to slash commands](#introduction-to-slash-commands), slash
 commands need to be _registered_ to Discord. Poise provides several ways to do it, with varying
 degree of abstraction. (Note: you can access a list of framework commands from anywhere with
-[`ctx.framework().options.commands`](Context::framework)).
+[`ctx.framework().commands()`](crate::FrameworkContext::commands)).
 
 The easiest way is with [`builtins::register_application_commands_buttons`].
 It spawns a message with buttons to register and unregister all commands, globally or in the current
@@ -381,6 +381,24 @@ fn test_calc() {
 }
 ```
 
+## Metrics
+
+With the `metrics` feature, the framework records metrics through the
+[`metrics`](https://docs.rs/metrics) facade. Install a recorder such as
+[`metrics-exporter-prometheus`](https://docs.rs/metrics-exporter-prometheus) on startup to collect
+them, e.g. to scrape them into Prometheus:
+
+- `poise_command_invocations_total` (counter, labels `command` and `kind`): started command
+  invocations by qualified command name and `prefix`, `slash` or `context_menu`
+- `poise_command_duration_seconds` (histogram, label `command`): how long command invocations
+  took, including checks, argument parsing and failed invocations
+- `poise_errors_total` (counter, label `error`): errors passed to the error handlers by
+  [`FrameworkError`] variant, e.g. `Command` or `ArgumentParse`
+- `poise_cooldown_hits_total` (counter, label `command`): invocations rejected because of a
+  cooldown
+- `poise_events_total` (counter, label `event`): events received by the framework by name, e.g.
+  `message` or `interaction_create`
+
 # About the weird name
 I'm bad at names. Google lists "poise" as a synonym to "serenity" which is the Discord library
 underlying this framework, so that's what I chose.
@@ -388,17 +406,26 @@ underlying this framework, so that's what I chose.
 Also, poise is a stat in Dark Souls
 */
 
+pub mod argument_types;
 pub mod builtins;
 pub mod choice_parameter;
+pub mod concurrency;
 pub mod cooldown;
 pub mod dispatch;
+pub mod events;
 pub mod framework;
+#[cfg(feature = "i18n")]
+pub mod i18n;
+mod metrics;
+pub mod middleware;
 pub mod modal;
 pub mod prefix_argument;
 pub mod reply;
+pub mod scheduler;
 pub mod slash_argument;
 pub mod structs;
 pub mod track_edits;
+pub mod validation;
 pub mod macros {
     //! Procedural macros used in poise, like [`command`]
     #[doc(inline)]
@@ -407,8 +434,9 @@ pub mod macros {
 
 #[doc(no_inline)]
 pub use {
-    choice_parameter::*, cooldown::*, dispatch::*, framework::*, macros::*, modal::*,
-    prefix_argument::*, reply::*, slash_argument::*, structs::*, track_edits::*,
+    argument_types::*, choice_parameter::*, concurrency::*, cooldown::*, dispatch::*, framework::*,
+    macros::*, middleware::*, modal::*, prefix_argument::*, reply::*, scheduler::*,
+    slash_argument::*, structs::*, track_edits::*, validation::*,
 };
 
 /// See [`builtins`]
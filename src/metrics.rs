@@ -0,0 +1,73 @@
+//! Records metrics through the [`metrics`](https://docs.rs/metrics) facade if the `metrics`
+//! feature is enabled, see the crate docs for the recorded metrics. Without the feature, the
+//! functions in here do nothing
+
+use crate::serenity_prelude as serenity;
+
+/// Label value of an invocation kind
+#[cfg(feature = "metrics")]
+fn kind_label(kind: crate::InvocationKind) -> &'static str {
+    match kind {
+        crate::InvocationKind::Prefix => "prefix",
+        crate::InvocationKind::Slash => "slash",
+        crate::InvocationKind::ContextMenu => "context_menu",
+    }
+}
+
+/// Counts the command invocation and records how long it took, including errors
+#[cfg(feature = "metrics")]
+pub(crate) async fn measure_invocation<'a, U, E>(
+    ctx: crate::Context<'a, U, E>,
+    invocation: impl std::future::Future<Output = Result<(), crate::FrameworkError<'a, U, E>>>,
+) -> Result<(), crate::FrameworkError<'a, U, E>> {
+    let command = ctx.command().qualified_name.clone();
+    ::metrics::counter!(
+        "poise_command_invocations_total",
+        "command" => command.clone(),
+        "kind" => kind_label(ctx.invocation_kind()),
+    )
+    .increment(1);
+
+    let start = std::time::Instant::now();
+    let result = invocation.await;
+    ::metrics::histogram!("poise_command_duration_seconds", "command" => command)
+        .record(start.elapsed());
+    result
+}
+
+/// Without the `metrics` feature, just runs the invocation
+#[cfg(not(feature = "metrics"))]
+pub(crate) async fn measure_invocation<'a, U, E>(
+    _ctx: crate::Context<'a, U, E>,
+    invocation: impl std::future::Future<Output = Result<(), crate::FrameworkError<'a, U, E>>>,
+) -> Result<(), crate::FrameworkError<'a, U, E>> {
+    invocation.await
+}
+
+/// Counts an error passed to the error handler, by variant
+pub(crate) fn record_error<U, E>(error: &crate::FrameworkError<'_, U, E>) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!("poise_errors_total", "error" => error.variant_name()).increment(1);
+    #[cfg(not(feature = "metrics"))]
+    let _ = error;
+}
+
+/// Counts a command invocation that was rejected because of a cooldown
+pub(crate) fn record_cooldown_hit<U, E>(command: &crate::Command<U, E>) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!(
+        "poise_cooldown_hits_total",
+        "command" => command.qualified_name.clone(),
+    )
+    .increment(1);
+    #[cfg(not(feature = "metrics"))]
+    let _ = command;
+}
+
+/// Counts an event received by the framework, by event name
+pub(crate) fn record_event(event: &serenity::FullEvent) {
+    #[cfg(feature = "metrics")]
+    ::metrics::counter!("poise_events_total", "event" => event.snake_case_name()).increment(1);
+    #[cfg(not(feature = "metrics"))]
+    let _ = event;
+}
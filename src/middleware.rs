@@ -0,0 +1,85 @@
+//! Middleware that wraps command execution, see [`crate::FrameworkOptions::middleware`]
+
+use crate::BoxFuture;
+
+/// Result of running a command, as passed through the middleware chain
+pub type CommandResult<'a, U, E> = Result<(), crate::FrameworkError<'a, U, E>>;
+
+/// A layer around command execution. Each layer receives the invocation context and a [`Next`]
+/// continuation that runs the remaining layers and finally the command itself.
+///
+/// Middleware runs after checks, cooldowns and [`crate::FrameworkOptions::pre_command`], and
+/// before [`crate::FrameworkOptions::post_command`]. A layer can run code around the command,
+/// skip it by not calling [`Next::run`], run it multiple times, or change the result.
+///
+/// ```rust
+/// # type Data = (); type Error = Box<dyn std::error::Error + Send + Sync>;
+/// struct Timing;
+///
+/// #[poise::async_trait]
+/// impl poise::CommandMiddleware<Data, Error> for Timing {
+///     async fn handle<'a>(
+///         &self,
+///         ctx: poise::Context<'a, Data, Error>,
+///         next: poise::Next<'_, 'a, Data, Error>,
+///     ) -> poise::CommandResult<'a, Data, Error> {
+///         let start = std::time::Instant::now();
+///         let result = next.run(ctx).await;
+///         println!("{} took {:?}", ctx.command().qualified_name, start.elapsed());
+///         result
+///     }
+/// }
+///
+/// poise::FrameworkOptions::<Data, Error> {
+///     middleware: vec![Box::new(Timing)],
+///     ..Default::default()
+/// };
+/// ```
+#[async_trait::async_trait]
+pub trait CommandMiddleware<U, E>: Send + Sync {
+    /// Handles a command invocation. Call `next.run(ctx)` to continue with the next layer
+    async fn handle<'a>(
+        &self,
+        ctx: crate::Context<'a, U, E>,
+        next: Next<'_, 'a, U, E>,
+    ) -> CommandResult<'a, U, E>;
+}
+
+/// The rest of the middleware chain, passed to [`CommandMiddleware::handle`]
+pub struct Next<'m, 'a, U, E> {
+    /// Layers that haven't run yet
+    middleware: &'m [Box<dyn CommandMiddleware<U, E>>],
+    /// Runs the command itself, after all layers
+    action: fn(crate::Context<'a, U, E>) -> BoxFuture<'a, CommandResult<'a, U, E>>,
+}
+
+impl<U, E> Copy for Next<'_, '_, U, E> {}
+impl<U, E> Clone for Next<'_, '_, U, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'m, 'a, U, E> Next<'m, 'a, U, E> {
+    /// Creates a chain that runs the given layers, in order, and then `action`
+    pub(crate) fn new(
+        middleware: &'m [Box<dyn CommandMiddleware<U, E>>],
+        action: fn(crate::Context<'a, U, E>) -> BoxFuture<'a, CommandResult<'a, U, E>>,
+    ) -> Self {
+        Self { middleware, action }
+    }
+
+    /// Runs the remaining layers and the command
+    pub async fn run(self, ctx: crate::Context<'a, U, E>) -> CommandResult<'a, U, E> {
+        match self.middleware.split_first() {
+            Some((layer, rest)) => {
+                let next = Self {
+                    middleware: rest,
+                    action: self.action,
+                };
+                layer.handle(ctx, next).await
+            }
+            None => (self.action)(ctx).await,
+        }
+    }
+}
@@ -0,0 +1,570 @@
+//! Modal trait and utility items for implementing it (mainly for the derive macro)
+
+use crate::serenity_prelude as serenity;
+
+mod wizard;
+pub use wizard::*;
+
+/// Meant for use in derived [`Modal::parse`] implementation
+///
+/// _Takes_ the String out of the data. Logs warnings on unexpected state
+#[doc(hidden)]
+pub fn find_modal_text(
+    data: &mut serenity::ModalInteractionData,
+    custom_id: &str,
+) -> Option<String> {
+    for row in &mut data.components {
+        let text = match row.components.get_mut(0) {
+            Some(serenity::ActionRowComponent::InputText(text)) => text,
+            Some(_) => {
+                tracing::warn!("unexpected non input text component in modal response");
+                continue;
+            }
+            None => {
+                tracing::warn!("empty action row in modal response");
+                continue;
+            }
+        };
+
+        if text.custom_id == custom_id {
+            return match std::mem::take(&mut text.value) {
+                Some(val) if val.is_empty() => None,
+                Some(val) => Some(val),
+                None => None,
+            };
+        }
+    }
+    tracing::warn!(
+        "{} not found in modal response (expected at least blank string)",
+        custom_id
+    );
+    None
+}
+
+/// Error returned by [`execute_modal_with_options`] and
+/// [`execute_modal_on_component_interaction_with_options`]
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ModalError {
+    /// The user didn't submit the modal before the timeout expired
+    ///
+    /// Discord doesn't tell bots when a user closes a modal without submitting, so this also
+    /// covers dismissed modals, once the timeout has expired.
+    Timeout,
+    /// The user pressed the cancel button of a [modal wizard](execute_modal_wizard)
+    Cancelled,
+    /// The submitted data couldn't be parsed by [`Modal::parse()`]
+    Parse(&'static str),
+    /// Sending the modal or acknowledging the submission failed
+    Serenity(serenity::Error),
+}
+impl std::fmt::Display for ModalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Timeout => f.write_str("the modal wasn't submitted in time"),
+            Self::Cancelled => f.write_str("the modal was cancelled"),
+            Self::Parse(error) => write!(f, "failed to parse modal submission: {}", error),
+            Self::Serenity(error) => write!(f, "failed to execute modal: {}", error),
+        }
+    }
+}
+impl std::error::Error for ModalError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Serenity(error) => Some(error),
+            Self::Timeout | Self::Cancelled | Self::Parse(_) => None,
+        }
+    }
+}
+impl From<serenity::Error> for ModalError {
+    fn from(error: serenity::Error) -> Self {
+        Self::Serenity(error)
+    }
+}
+
+/// Settings for [`execute_modal_with_options`], [`execute_modal_wizard`] and their variants for
+/// component interactions
+#[derive(Clone, derivative::Derivative)]
+#[derivative(Debug)]
+pub struct ModalOptions {
+    /// How long to wait for the user to submit the modal. Defaults to one hour
+    pub timeout: std::time::Duration,
+    /// Called with the interaction that opened the modal if the user doesn't submit in time, e.g.
+    /// to disable the button that opened the modal or to send a follow-up message
+    #[derivative(Debug = "ignore")]
+    pub on_timeout: Option<
+        for<'a> fn(
+            &'a serenity::Context,
+            &'a serenity::Interaction,
+        ) -> crate::BoxFuture<'a, Result<(), serenity::Error>>,
+    >,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for ModalOptions {
+    fn default() -> Self {
+        Self {
+            timeout: std::time::Duration::from_secs(3600),
+            on_timeout: None,
+            __non_exhaustive: (),
+        }
+    }
+}
+
+impl ModalOptions {
+    /// Creates options with the given timeout, or the default timeout if `None`
+    fn with_timeout(timeout: Option<std::time::Duration>) -> Self {
+        let mut options = Self::default();
+        if let Some(timeout) = timeout {
+            options.timeout = timeout;
+        }
+        options
+    }
+}
+
+/// Converts the result of the `_with_options` functions into the result of the older functions,
+/// which signal a timeout with `None`
+#[allow(clippy::result_large_err)] // same error type as the public functions
+fn legacy_modal_result<M>(result: Result<M, ModalError>) -> Result<Option<M>, serenity::Error> {
+    match result {
+        Ok(modal) => Ok(Some(modal)),
+        Err(ModalError::Timeout | ModalError::Cancelled) => Ok(None),
+        Err(ModalError::Parse(error)) => Err(serenity::Error::Other(error)),
+        Err(ModalError::Serenity(error)) => Err(error),
+    }
+}
+
+/// Sends the modal and waits for the user to submit it, without responding to the submission
+async fn open_modal<M: Modal, F: std::future::Future<Output = Result<(), serenity::Error>>>(
+    ctx: &serenity::Context,
+    create_interaction_response: impl FnOnce(serenity::CreateInteractionResponse) -> F,
+    interaction: impl FnOnce() -> serenity::Interaction,
+    modal_custom_id: String,
+    defaults: Option<M>,
+    options: ModalOptions,
+) -> Result<serenity::ModalInteraction, ModalError> {
+    // Send modal
+    create_interaction_response(M::create(defaults, modal_custom_id.clone())).await?;
+
+    // Wait for user to submit
+    let response = serenity::collector::ModalInteractionCollector::new(&ctx.shard)
+        .filter(move |d| d.data.custom_id == modal_custom_id)
+        .timeout(options.timeout)
+        .await;
+    let response = match response {
+        Some(x) => x,
+        None => {
+            if let Some(on_timeout) = options.on_timeout {
+                on_timeout(ctx, &interaction()).await?;
+            }
+            return Err(ModalError::Timeout);
+        }
+    };
+    Ok(response)
+}
+
+/// Underlying code for the modal spawning convenience function which abstracts over the kind of
+/// interaction
+async fn execute_modal_generic<
+    M: Modal,
+    F: std::future::Future<Output = Result<(), serenity::Error>>,
+>(
+    ctx: &serenity::Context,
+    create_interaction_response: impl FnOnce(serenity::CreateInteractionResponse) -> F,
+    interaction: impl FnOnce() -> serenity::Interaction,
+    modal_custom_id: String,
+    defaults: Option<M>,
+    options: ModalOptions,
+) -> Result<M, ModalError> {
+    let response = open_modal(
+        ctx,
+        create_interaction_response,
+        interaction,
+        modal_custom_id,
+        defaults,
+        options,
+    )
+    .await?;
+
+    // Send acknowledgement so that the pop-up is closed
+    response
+        .create_response(ctx, serenity::CreateInteractionResponse::Acknowledge)
+        .await?;
+
+    M::parse(response.data.clone()).map_err(ModalError::Parse)
+}
+
+/// Convenience function for showing the modal and waiting for a response.
+///
+/// If the user doesn't submit before the timeout expires, `None` is returned.
+///
+/// Note: a modal must be the first response to a command. You cannot send any messages before,
+/// or the modal will fail.
+///
+/// This function:
+/// 1. sends the modal via [`Modal::create()`]
+/// 2. waits for the user to submit via [`serenity::ModalInteractionCollector`]
+/// 3. acknowledges the submitted data so that Discord closes the pop-up for the user
+/// 4. parses the submitted data via [`Modal::parse()`], wrapping errors in [`serenity::Error::Other`]
+///
+/// If you need more specialized behavior, you can copy paste the implementation of this function
+/// and adjust to your needs. The code of this function is just a starting point. For a
+/// configurable timeout callback and distinct timeout and parse errors, see
+/// [`execute_modal_with_options`].
+pub async fn execute_modal<U: Send + Sync, E, M: Modal>(
+    ctx: crate::ApplicationContext<'_, U, E>,
+    defaults: Option<M>,
+    timeout: Option<std::time::Duration>,
+) -> Result<Option<M>, serenity::Error> {
+    legacy_modal_result(
+        execute_modal_with_options(ctx, defaults, ModalOptions::with_timeout(timeout)).await,
+    )
+}
+
+/// Like [`execute_modal`], but configurable via [`ModalOptions`], and with a distinct
+/// [`ModalError::Timeout`] error if the user doesn't submit in time.
+///
+/// ```rust,no_run
+/// # use poise::serenity_prelude as serenity;
+/// # #[derive(poise::Modal)] struct MyModal { name: String }
+/// # async fn _test(ctx: poise::ApplicationContext<'_, (), serenity::Error>) -> Result<(), serenity::Error> {
+/// let options = poise::ModalOptions {
+///     timeout: std::time::Duration::from_secs(300),
+///     on_timeout: Some(|ctx, interaction| Box::pin(async move {
+///         let serenity::Interaction::Command(interaction) = interaction else { return Ok(()) };
+///         let message = serenity::CreateInteractionResponseFollowup::new()
+///             .content("You took too long to fill in the form")
+///             .ephemeral(true);
+///         interaction.create_followup(ctx, message).await?;
+///         Ok(())
+///     })),
+///     ..Default::default()
+/// };
+/// match poise::execute_modal_with_options::<_, _, MyModal>(ctx, None, options).await {
+///     Ok(data) => println!("Hello {}", data.name),
+///     Err(poise::ModalError::Timeout) => {}
+///     Err(e) => println!("Modal failed: {}", e),
+/// }
+/// # Ok(()) }
+/// ```
+pub async fn execute_modal_with_options<U: Send + Sync, E, M: Modal>(
+    ctx: crate::ApplicationContext<'_, U, E>,
+    defaults: Option<M>,
+    options: ModalOptions,
+) -> Result<M, ModalError> {
+    let interaction = ctx.interaction;
+    let response = execute_modal_generic(
+        ctx.serenity_context,
+        |resp| interaction.create_response(ctx, resp),
+        || serenity::Interaction::Command(interaction.clone()),
+        interaction.id.to_string(),
+        defaults,
+        options,
+    )
+    .await;
+    if !matches!(response, Err(ModalError::Serenity(_))) {
+        ctx.has_sent_initial_response
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+    response
+}
+
+/// A submitted modal whose submission hasn't been responded to yet, returned by
+/// [`execute_modal_with_submission`]
+///
+/// Discord shows an error to the user if the submission isn't responded to within three seconds,
+/// so call [`Self::reply`] or [`Self::acknowledge`] promptly.
+#[derive(Debug)]
+pub struct ModalSubmission<M> {
+    /// The parsed modal data
+    pub data: M,
+    /// The modal submit interaction, for responding to it manually
+    pub interaction: serenity::ModalInteraction,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl<M> ModalSubmission<M> {
+    /// Closes the modal and sends the given reply as the response to the submission. Like
+    /// [`crate::Context::send`], this applies the command's defaults, e.g. `ephemeral`.
+    pub async fn reply<U, E>(
+        &self,
+        ctx: crate::ApplicationContext<'_, U, E>,
+        reply: crate::CreateReply,
+    ) -> Result<(), serenity::Error> {
+        let reply = crate::Context::Application(ctx).reply_builder(reply);
+        let response = reply.to_slash_initial_response(Default::default());
+        self.interaction
+            .create_response(
+                ctx.serenity_context,
+                serenity::CreateInteractionResponse::Message(response),
+            )
+            .await
+    }
+
+    /// Closes the modal without sending a message
+    pub async fn acknowledge(&self, ctx: impl serenity::CacheHttp) -> Result<(), serenity::Error> {
+        self.interaction
+            .create_response(ctx, serenity::CreateInteractionResponse::Acknowledge)
+            .await
+    }
+}
+
+/// Like [`execute_modal_with_options`], but leaves responding to the submission to the caller, so
+/// that the command can answer the form with a message.
+///
+/// This is handy for context menu commands that open a form right away, like a "Report message"
+/// command which confirms the report once it's submitted:
+///
+/// ```rust,no_run
+/// # use poise::serenity_prelude as serenity;
+/// # type Error = Box<dyn std::error::Error + Send + Sync>;
+/// #[derive(poise::Modal)]
+/// #[name = "Report message"]
+/// struct ReportModal {
+///     #[paragraph]
+///     reason: String,
+/// }
+///
+/// #[poise::command(context_menu_command = "Report message", ephemeral)]
+/// async fn report(
+///     ctx: poise::ApplicationContext<'_, (), Error>,
+///     msg: serenity::Message,
+/// ) -> Result<(), Error> {
+///     let submission =
+///         poise::execute_modal_with_submission::<_, _, ReportModal>(ctx, None, Default::default())
+///             .await?;
+///     println!("{} reported {}: {}", ctx.interaction.user.name, msg.link(), submission.data.reason);
+///
+///     let reply = poise::CreateReply::default().content("Thanks, the moderators will take a look");
+///     submission.reply(ctx, reply).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn execute_modal_with_submission<U: Send + Sync, E, M: Modal>(
+    ctx: crate::ApplicationContext<'_, U, E>,
+    defaults: Option<M>,
+    options: ModalOptions,
+) -> Result<ModalSubmission<M>, ModalError> {
+    let interaction = ctx.interaction;
+    let response = open_modal(
+        ctx.serenity_context,
+        |resp| interaction.create_response(ctx, resp),
+        || serenity::Interaction::Command(interaction.clone()),
+        interaction.id.to_string(),
+        defaults,
+        options,
+    )
+    .await;
+    if !matches!(response, Err(ModalError::Serenity(_))) {
+        ctx.has_sent_initial_response
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+
+    let response = response?;
+    Ok(ModalSubmission {
+        data: M::parse(response.data.clone()).map_err(ModalError::Parse)?,
+        interaction: response,
+        __non_exhaustive: (),
+    })
+}
+
+/// Convenience function for showing the modal on a message interaction and waiting for a response.
+///
+/// If the user doesn't submit before the timeout expires, `None` is returned.
+///
+/// This function:
+/// 1. sends the modal via [`Modal::create()`] as a mci interaction response
+/// 2. waits for the user to submit via [`serenity::ModalInteractionCollector`]
+/// 3. acknowledges the submitted data so that Discord closes the pop-up for the user
+/// 4. parses the submitted data via [`Modal::parse()`], wrapping errors in [`serenity::Error::Other`]
+///
+/// If you need more specialized behavior, you can copy paste the implementation of this function
+/// and adjust to your needs. The code of this function is just a starting point. For a
+/// configurable timeout callback and distinct timeout and parse errors, see
+/// [`execute_modal_on_component_interaction_with_options`].
+pub async fn execute_modal_on_component_interaction<M: Modal>(
+    ctx: impl AsRef<serenity::Context>,
+    interaction: serenity::ComponentInteraction,
+    defaults: Option<M>,
+    timeout: Option<std::time::Duration>,
+) -> Result<Option<M>, serenity::Error> {
+    legacy_modal_result(
+        execute_modal_on_component_interaction_with_options(
+            ctx,
+            interaction,
+            defaults,
+            ModalOptions::with_timeout(timeout),
+        )
+        .await,
+    )
+}
+
+/// Like [`execute_modal_on_component_interaction`], but configurable via [`ModalOptions`], and
+/// with a distinct [`ModalError::Timeout`] error if the user doesn't submit in time.
+pub async fn execute_modal_on_component_interaction_with_options<M: Modal>(
+    ctx: impl AsRef<serenity::Context>,
+    interaction: serenity::ComponentInteraction,
+    defaults: Option<M>,
+    options: ModalOptions,
+) -> Result<M, ModalError> {
+    execute_modal_generic(
+        ctx.as_ref(),
+        |resp| interaction.create_response(ctx.as_ref(), resp),
+        || serenity::Interaction::Component(interaction.clone()),
+        interaction.id.to_string(),
+        defaults,
+        options,
+    )
+    .await
+}
+
+/// Shows the modal in any kind of command and waits for a response.
+///
+/// Modals can only be opened in response to an interaction. In application commands, this is
+/// the same as [`execute_modal_with_options`]. In prefix commands, a message with an "Open form"
+/// button is sent instead, and the modal is opened when the command author presses it. The button
+/// is removed afterwards.
+///
+/// In prefix commands, [`ModalOptions::timeout`] applies to both pressing the button and
+/// submitting the modal. [`ModalOptions::on_timeout`] is only called if the user pressed the
+/// button but didn't submit the modal in time.
+///
+/// ```rust,no_run
+/// # use poise::serenity_prelude as serenity;
+/// # #[derive(poise::Modal)] struct MyModal { name: String }
+/// #[poise::command(prefix_command, slash_command)]
+/// async fn register(ctx: poise::Context<'_, (), poise::ModalError>) -> Result<(), poise::ModalError> {
+///     let data = poise::execute_modal_in_context::<_, _, MyModal>(ctx, None, Default::default()).await?;
+///     ctx.say(format!("Hello {}", data.name)).await?;
+///     Ok(())
+/// }
+/// ```
+pub async fn execute_modal_in_context<U: Send + Sync, E, M: Modal>(
+    ctx: crate::Context<'_, U, E>,
+    defaults: Option<M>,
+    options: ModalOptions,
+) -> Result<M, ModalError> {
+    let msg_id = match ctx {
+        crate::Context::Application(ctx) => {
+            return execute_modal_with_options(ctx, defaults, options).await
+        }
+        crate::Context::Prefix(ctx) => ctx.msg.id,
+    };
+
+    let button_id = format!("{}-open-form", msg_id);
+    let button = serenity::CreateButton::new(&button_id)
+        .label("Open form")
+        .style(serenity::ButtonStyle::Primary);
+    let reply = crate::CreateReply::default()
+        .content("Click the button below to open the form")
+        .components(vec![serenity::CreateActionRow::Buttons(vec![button])]);
+    let handle = ctx.send(reply).await?;
+
+    let press = serenity::ComponentInteractionCollector::new(ctx.serenity_context())
+        .author_id(ctx.author().id)
+        .channel_id(ctx.channel_id())
+        .filter(move |press| press.data.custom_id == button_id)
+        .timeout(options.timeout)
+        .await;
+    let result = match press {
+        Some(press) => {
+            execute_modal_on_component_interaction_with_options(ctx, press, defaults, options).await
+        }
+        None => Err(ModalError::Timeout),
+    };
+
+    // The modal already ran, so failing to remove the button shouldn't discard its result
+    let remove_button = crate::CreateReply::default().components(vec![]);
+    if let Err(e) = handle.edit(ctx, remove_button).await {
+        tracing::warn!("failed to remove modal button: {}", e);
+    }
+    result
+}
+
+/// Derivable trait for modal interactions, Discords version of interactive forms
+///
+/// You don't need to implement this trait manually; use `#[derive(poise::Modal)]` instead
+///
+/// # Example
+///
+/// ```rust
+/// # use poise::serenity_prelude as serenity;
+/// # type Data = ();
+/// # type Error = serenity::Error;
+/// use poise::Modal;
+/// type ApplicationContext<'a> = poise::ApplicationContext<'a, Data, Error>;
+///
+/// #[derive(Debug, Modal)]
+/// #[name = "Modal title"] // Struct name by default
+/// struct MyModal {
+///     #[name = "First input label"] // Field name by default
+///     #[placeholder = "Your first input goes here"] // No placeholder by default
+///     #[min_length = 5] // No length restriction by default (so, 1-4000 chars)
+///     #[max_length = 500]
+///     first_input: String,
+///     #[name = "Second input label"]
+///     #[paragraph] // Switches from single-line input to multiline text box
+///     second_input: Option<String>, // Option means optional input
+///     #[validate = "check_number"] // Checked by execute_validated_modal() and execute_modal_wizard()
+///     third_input: Option<String>,
+/// }
+///
+/// fn check_number(input: &str) -> Result<(), String> {
+///     match input.parse::<u32>() {
+///         Ok(_) => Ok(()),
+///         Err(e) => Err(format!("must be a number ({})", e)),
+///     }
+/// }
+///
+/// #[poise::command(slash_command)]
+/// pub async fn modal(ctx: ApplicationContext<'_>) -> Result<(), Error> {
+///     let data = MyModal::execute(ctx).await?;
+///     println!("Got data: {:?}", data);
+///
+///     Ok(())
+/// }
+/// ```
+#[async_trait::async_trait]
+pub trait Modal: Sized {
+    /// Returns an interaction response builder which creates the modal for this type
+    ///
+    /// Optionally takes an initialized instance as pre-filled values of this modal (see
+    /// [`Self::execute_with_defaults()`] for more info)
+    fn create(defaults: Option<Self>, custom_id: String) -> serenity::CreateInteractionResponse;
+
+    /// Parses a received modal submit interaction into this type
+    ///
+    /// Returns an error if a field was missing. This should never happen, because Discord will only
+    /// let users submit when all required fields are filled properly
+    fn parse(data: serenity::ModalInteractionData) -> Result<Self, &'static str>;
+
+    /// Checks the parsed input with the validators given by `#[validate]` field attributes
+    ///
+    /// Returns a user-facing description of all invalid fields. Validation is run by
+    /// [`execute_validated_modal`] and [`execute_modal_wizard`], which re-open the modal until the
+    /// input is valid; the other modal functions ignore it.
+    fn validate(&self) -> Result<(), String> {
+        Ok(())
+    }
+
+    /// Calls `execute_modal(ctx, None, None)`. See [`execute_modal`]
+    ///
+    /// For a variant that is triggered on component interactions, see [`execute_modal_on_component_interaction`].
+    // TODO: add execute_with_defaults? Or add a `defaults: Option<Self>` param?
+    async fn execute<U: Send + Sync, E>(
+        ctx: crate::ApplicationContext<'_, U, E>,
+    ) -> Result<Option<Self>, serenity::Error> {
+        execute_modal(ctx, None::<Self>, None).await
+    }
+
+    /// Calls `execute_modal(ctx, Some(defaults), None)`. See [`execute_modal`]
+    // TODO: deprecate this in favor of execute_modal()?
+    async fn execute_with_defaults<U: Send + Sync, E>(
+        ctx: crate::ApplicationContext<'_, U, E>,
+        defaults: Self,
+    ) -> Result<Option<Self>, serenity::Error> {
+        execute_modal(ctx, Some(defaults), None).await
+    }
+}
@@ -0,0 +1,419 @@
+//! Multi-step forms made of multiple modals, for forms with more fields than fit into one modal, and
+//! modals that are re-opened until the input is valid
+
+use super::{Modal, ModalError, ModalOptions};
+use crate::serenity_prelude as serenity;
+use futures_util::StreamExt as _;
+
+/// A sequence of [`Modal`] types that can be shown as a multi-step form with
+/// [`execute_modal_wizard`].
+///
+/// Discord modals are limited to five inputs. This trait is implemented for tuples of up to five
+/// modals, so a wizard of type `(A, B, C)` shows the modals `A`, `B` and `C` one after another and
+/// returns the three parsed modals at the end.
+pub trait ModalWizard: Sized {
+    /// Results of the steps that have been submitted so far
+    #[doc(hidden)]
+    type State: Default + Send;
+
+    /// Number of steps in this wizard
+    #[doc(hidden)]
+    const STEPS: usize;
+
+    /// Creates the modal for the given step, pre-filled with the previous submission of this step
+    /// if there is one
+    #[doc(hidden)]
+    fn create_step(
+        state: &mut Self::State,
+        step: usize,
+        custom_id: String,
+    ) -> serenity::CreateInteractionResponse;
+
+    /// Parses the submission of the given step into the state
+    #[doc(hidden)]
+    fn parse_step(
+        state: &mut Self::State,
+        step: usize,
+        data: serenity::ModalInteractionData,
+    ) -> Result<(), &'static str>;
+
+    /// Runs [`Modal::validate()`] on the submission of the given step
+    #[doc(hidden)]
+    fn validate_step(state: &Self::State, step: usize) -> Result<(), String>;
+
+    /// Returns the final result, or `None` if a step wasn't submitted
+    #[doc(hidden)]
+    fn finish(state: Self::State) -> Option<Self>;
+}
+
+/// Implements [`ModalWizard`] for a tuple of modals. Takes the number of steps and the tuple index
+/// and type parameter of each step
+macro_rules! impl_modal_wizard {
+    ($steps:literal: $( $index:tt $modal:ident ),*) => {
+        impl<$( $modal: Modal + Send ),*> ModalWizard for ($( $modal, )*) {
+            type State = ($( Option<$modal>, )*);
+
+            const STEPS: usize = $steps;
+
+            fn create_step(
+                state: &mut Self::State,
+                step: usize,
+                custom_id: String,
+            ) -> serenity::CreateInteractionResponse {
+                match step {
+                    $( $index => $modal::create(state.$index.take(), custom_id), )*
+                    _ => unreachable!("modal wizard step out of bounds"),
+                }
+            }
+
+            fn parse_step(
+                state: &mut Self::State,
+                step: usize,
+                data: serenity::ModalInteractionData,
+            ) -> Result<(), &'static str> {
+                match step {
+                    $( $index => state.$index = Some($modal::parse(data)?), )*
+                    _ => unreachable!("modal wizard step out of bounds"),
+                }
+                Ok(())
+            }
+
+            fn validate_step(state: &Self::State, step: usize) -> Result<(), String> {
+                match step {
+                    $( $index => state.$index.as_ref().map_or(Ok(()), Modal::validate), )*
+                    _ => unreachable!("modal wizard step out of bounds"),
+                }
+            }
+
+            fn finish(state: Self::State) -> Option<Self> {
+                Some(($( state.$index?, )*))
+            }
+        }
+    };
+}
+
+impl_modal_wizard!(1: 0 A);
+impl_modal_wizard!(2: 0 A, 1 B);
+impl_modal_wizard!(3: 0 A, 1 B, 2 C);
+impl_modal_wizard!(4: 0 A, 1 B, 2 C, 3 D);
+impl_modal_wizard!(5: 0 A, 1 B, 2 C, 3 D, 4 E);
+
+/// Responds to any kind of interaction that supports responses
+async fn create_response(
+    ctx: &serenity::Context,
+    interaction: &serenity::Interaction,
+    response: serenity::CreateInteractionResponse,
+) -> Result<(), serenity::Error> {
+    match interaction {
+        serenity::Interaction::Command(interaction) => {
+            interaction.create_response(ctx, response).await
+        }
+        serenity::Interaction::Component(interaction) => {
+            interaction.create_response(ctx, response).await
+        }
+        serenity::Interaction::Modal(interaction) => {
+            interaction.create_response(ctx, response).await
+        }
+        _ => Err(serenity::Error::Other(
+            "can't open a modal wizard on this kind of interaction",
+        )),
+    }
+}
+
+/// Builds the message shown between the steps of a wizard, with the buttons to navigate the steps.
+/// `step` is the step that the continue button opens. If the step was submitted with invalid input,
+/// `validation_error` describes the problem.
+fn create_step_message(
+    step: usize,
+    steps: usize,
+    validation_error: Option<&str>,
+    id_prefix: &str,
+) -> serenity::CreateInteractionResponseMessage {
+    let continue_label = if validation_error.is_some() {
+        "Try again"
+    } else {
+        "Continue"
+    };
+    let buttons = vec![
+        serenity::CreateButton::new(format!("{id_prefix}back"))
+            .label("Back")
+            .style(serenity::ButtonStyle::Secondary)
+            .disabled(step == 0),
+        serenity::CreateButton::new(format!("{id_prefix}continue"))
+            .label(continue_label)
+            .style(serenity::ButtonStyle::Primary),
+        serenity::CreateButton::new(format!("{id_prefix}cancel"))
+            .label("Cancel")
+            .style(serenity::ButtonStyle::Danger),
+    ];
+    // A single modal has no steps to go back to
+    let buttons = if steps == 1 {
+        buttons.into_iter().skip(1).collect()
+    } else {
+        buttons
+    };
+
+    let mut content = Vec::new();
+    if steps > 1 {
+        content.push(format!("Step {} of {}", step + 1, steps));
+    }
+    if let Some(validation_error) = validation_error {
+        content.push(format!("Please correct your input:\n{}", validation_error));
+    }
+    serenity::CreateInteractionResponseMessage::new()
+        .content(content.join("\n"))
+        .components(vec![serenity::CreateActionRow::Buttons(buttons)])
+        .ephemeral(true)
+}
+
+/// Underlying code for the modal wizard convenience functions which abstracts over the kind of
+/// interaction
+async fn execute_modal_wizard_generic<W: ModalWizard>(
+    ctx: &serenity::Context,
+    interaction: serenity::Interaction,
+    mut state: W::State,
+    options: ModalOptions,
+) -> Result<W, ModalError> {
+    let id_prefix = format!("{}-wizard-", interaction.id());
+    // Register the collector before sending the modal, so that no submission is missed
+    let mut events = serenity::collector::collect(&ctx.shard, {
+        let id_prefix = id_prefix.clone();
+        move |event| match event {
+            serenity::Event::InteractionCreate(event) => match &event.interaction {
+                serenity::Interaction::Component(x) if x.data.custom_id.starts_with(&id_prefix) => {
+                    Some(event.interaction.clone())
+                }
+                serenity::Interaction::Modal(x) if x.data.custom_id.starts_with(&id_prefix) => {
+                    Some(event.interaction.clone())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    });
+
+    // Step whose modal is currently open or is opened by the continue button
+    let mut step = 0;
+    // Whether the ephemeral message with the navigation buttons has been sent yet
+    let mut has_step_message = false;
+    // Most recent interaction of the user, passed to the timeout callback
+    let mut latest_interaction = interaction;
+
+    let modal = W::create_step(&mut state, step, format!("{id_prefix}step-{step}"));
+    create_response(ctx, &latest_interaction, modal).await?;
+
+    loop {
+        let event = match tokio::time::timeout(options.timeout, events.next()).await {
+            Ok(Some(event)) => event,
+            Ok(None) | Err(_) => {
+                if let Some(on_timeout) = options.on_timeout {
+                    on_timeout(ctx, &latest_interaction).await?;
+                }
+                return Err(ModalError::Timeout);
+            }
+        };
+
+        let response = match &event {
+            serenity::Interaction::Modal(submit) => {
+                // Ignore submissions of modals other than the current step, which can only be
+                // stale duplicates
+                if submit.data.custom_id != format!("{id_prefix}step-{step}") {
+                    continue;
+                }
+                W::parse_step(&mut state, step, submit.data.clone()).map_err(ModalError::Parse)?;
+                // Modals can't be opened in response to a modal submission, so invalid input is
+                // reported in the message, whose button then re-opens the pre-filled modal
+                let validation_error = W::validate_step(&state, step).err();
+                if validation_error.is_none() {
+                    step += 1;
+                }
+
+                if step == W::STEPS {
+                    // Close the modal, and remove the navigation buttons if they were shown
+                    let response = if has_step_message {
+                        serenity::CreateInteractionResponse::UpdateMessage(
+                            serenity::CreateInteractionResponseMessage::new()
+                                .content("Submitted")
+                                .components(vec![]),
+                        )
+                    } else {
+                        serenity::CreateInteractionResponse::Acknowledge
+                    };
+                    create_response(ctx, &event, response).await?;
+                    return W::finish(state).ok_or(ModalError::Parse("modal wizard step missing"));
+                }
+
+                let message =
+                    create_step_message(step, W::STEPS, validation_error.as_deref(), &id_prefix);
+                if has_step_message {
+                    serenity::CreateInteractionResponse::UpdateMessage(message)
+                } else {
+                    has_step_message = true;
+                    serenity::CreateInteractionResponse::Message(message)
+                }
+            }
+            serenity::Interaction::Component(press) => {
+                match press.data.custom_id.strip_prefix(&id_prefix) {
+                    Some("back") => {
+                        step = step.saturating_sub(1);
+                        serenity::CreateInteractionResponse::UpdateMessage(create_step_message(
+                            step,
+                            W::STEPS,
+                            None,
+                            &id_prefix,
+                        ))
+                    }
+                    Some("continue") => {
+                        W::create_step(&mut state, step, format!("{id_prefix}step-{step}"))
+                    }
+                    Some("cancel") => {
+                        let response = serenity::CreateInteractionResponse::UpdateMessage(
+                            serenity::CreateInteractionResponseMessage::new()
+                                .content("Cancelled")
+                                .components(vec![]),
+                        );
+                        create_response(ctx, &event, response).await?;
+                        return Err(ModalError::Cancelled);
+                    }
+                    _ => continue,
+                }
+            }
+            _ => continue,
+        };
+        create_response(ctx, &event, response).await?;
+        latest_interaction = event;
+    }
+}
+
+/// Shows a multi-step form made of multiple modals and waits for the user to fill in all steps.
+///
+/// The first modal is opened in response to the command. After each step, an ephemeral message with
+/// buttons is shown: "Continue" opens the next modal, "Back" goes back one step, and "Cancel"
+/// aborts the wizard with [`ModalError::Cancelled`]. When going back, modals are pre-filled with
+/// the previous submission.
+///
+/// If a step fails the validators given by `#[validate]` attributes (see [`Modal::validate()`]),
+/// the errors are shown in the message instead, and the step is re-opened with the previous input
+/// when the user presses "Try again".
+///
+/// [`ModalOptions::timeout`] applies to each step separately. On timeout,
+/// [`ModalOptions::on_timeout`] is called with the user's most recent interaction in the wizard.
+///
+/// Like with [`execute_modal`](super::execute_modal), the modal must be the first response to the
+/// command.
+///
+/// ```rust,no_run
+/// # use poise::serenity_prelude as serenity;
+/// #[derive(poise::Modal)]
+/// struct Profile { name: String, bio: Option<String> }
+///
+/// #[derive(poise::Modal)]
+/// struct Contact { email: String }
+///
+/// # async fn _test(ctx: poise::ApplicationContext<'_, (), serenity::Error>) -> Result<(), poise::ModalError> {
+/// let (profile, contact) =
+///     poise::execute_modal_wizard::<_, _, (Profile, Contact)>(ctx, Default::default()).await?;
+/// # Ok(()) }
+/// ```
+pub async fn execute_modal_wizard<U: Send + Sync, E, W: ModalWizard>(
+    ctx: crate::ApplicationContext<'_, U, E>,
+    options: ModalOptions,
+) -> Result<W, ModalError> {
+    let interaction = serenity::Interaction::Command(ctx.interaction.clone());
+    let result = execute_modal_wizard_generic(
+        ctx.serenity_context,
+        interaction,
+        Default::default(),
+        options,
+    )
+    .await;
+    if !matches!(result, Err(ModalError::Serenity(_))) {
+        ctx.has_sent_initial_response
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+    result
+}
+
+/// Like [`execute_modal_wizard`], but opens the first modal in response to a component interaction,
+/// e.g. a button press.
+pub async fn execute_modal_wizard_on_component_interaction<W: ModalWizard>(
+    ctx: impl AsRef<serenity::Context>,
+    interaction: serenity::ComponentInteraction,
+    options: ModalOptions,
+) -> Result<W, ModalError> {
+    execute_modal_wizard_generic(
+        ctx.as_ref(),
+        serenity::Interaction::Component(interaction),
+        Default::default(),
+        options,
+    )
+    .await
+}
+
+/// Shows the modal and waits for a response, re-opening it until the input passes the validators
+/// given by `#[validate]` attributes (see [`Modal::validate()`]).
+///
+/// Discord doesn't allow opening a modal in response to a modal submission. So when validation
+/// fails, an ephemeral message with the errors and a "Try again" button is shown, which re-opens
+/// the modal pre-filled with the previous input. The user can also press "Cancel", which returns
+/// [`ModalError::Cancelled`].
+///
+/// [`ModalOptions::timeout`] applies to each attempt separately. On timeout,
+/// [`ModalOptions::on_timeout`] is called with the user's most recent interaction.
+///
+/// ```rust,no_run
+/// # use poise::serenity_prelude as serenity;
+/// fn is_email(input: &str) -> Result<(), &'static str> {
+///     match input.split_once('@') {
+///         Some((user, domain)) if !user.is_empty() && domain.contains('.') => Ok(()),
+///         _ => Err("not a valid email address"),
+///     }
+/// }
+///
+/// #[derive(poise::Modal)]
+/// struct Contact {
+///     #[validate = "is_email"]
+///     email: String,
+/// }
+///
+/// # async fn _test(ctx: poise::ApplicationContext<'_, (), serenity::Error>) -> Result<(), poise::ModalError> {
+/// let contact = poise::execute_validated_modal::<_, _, Contact>(ctx, None, Default::default()).await?;
+/// # Ok(()) }
+/// ```
+pub async fn execute_validated_modal<U: Send + Sync, E, M: Modal + Send>(
+    ctx: crate::ApplicationContext<'_, U, E>,
+    defaults: Option<M>,
+    options: ModalOptions,
+) -> Result<M, ModalError> {
+    let interaction = serenity::Interaction::Command(ctx.interaction.clone());
+    let result = execute_modal_wizard_generic::<(M,)>(
+        ctx.serenity_context,
+        interaction,
+        (defaults,),
+        options,
+    )
+    .await;
+    if !matches!(result, Err(ModalError::Serenity(_))) {
+        ctx.has_sent_initial_response
+            .store(true, std::sync::atomic::Ordering::SeqCst);
+    }
+    result.map(|(modal,)| modal)
+}
+
+/// Like [`execute_validated_modal`], but opens the modal in response to a component interaction,
+/// e.g. a button press.
+pub async fn execute_validated_modal_on_component_interaction<M: Modal + Send>(
+    ctx: impl AsRef<serenity::Context>,
+    interaction: serenity::ComponentInteraction,
+    defaults: Option<M>,
+    options: ModalOptions,
+) -> Result<M, ModalError> {
+    let (modal,) = execute_modal_wizard_generic::<(M,)>(
+        ctx.as_ref(),
+        serenity::Interaction::Component(interaction),
+        (defaults,),
+        options,
+    )
+    .await?;
+    Ok(modal)
+}
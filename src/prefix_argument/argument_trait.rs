@@ -71,7 +71,7 @@ where
             pop_string(args).map_err(|_| (TooFewArguments::default().into(), None))?;
         let object = T::convert(ctx, msg.guild_id, Some(msg.channel_id), &string)
             .await
-            .map_err(|e| (e.into(), Some(string)))?;
+            .map_err(|e| (e.into(), Some(string.into_owned())))?;
 
         Ok((args.trim_start(), attachment_index, object))
     }
@@ -104,10 +104,17 @@ impl<'a> PopArgumentHack<'a, bool> for &PhantomData<bool> {
         let (args, string) =
             pop_string(args).map_err(|_| (TooFewArguments::default().into(), None))?;
 
-        let value = match string.to_ascii_lowercase().trim() {
-            "yes" | "y" | "true" | "t" | "1" | "enable" | "on" => true,
-            "no" | "n" | "false" | "f" | "0" | "disable" | "off" => false,
-            _ => return Err((InvalidBool::default().into(), Some(string))),
+        let is_any_of = |words: &[&str]| {
+            words
+                .iter()
+                .any(|word| string.trim().eq_ignore_ascii_case(word))
+        };
+        let value = if is_any_of(&["yes", "y", "true", "t", "1", "enable", "on"]) {
+            true
+        } else if is_any_of(&["no", "n", "false", "f", "0", "disable", "off"]) {
+            false
+        } else {
+            return Err((InvalidBool::default().into(), Some(string.into_owned())));
         };
 
         Ok((args.trim_start(), attachment_index, value))
@@ -1,4 +1,4 @@
-//! Parsing code for [`CodeBlock`], a prefix-specific command parameter type
+//! Parsing code for [`CodeBlock`], a command parameter type for Discord code blocks
 
 use super::*;
 use trim_in_place::TrimInPlace;
@@ -31,6 +31,9 @@ impl std::error::Error for CodeBlockError {}
 /// ```
 ///
 /// Can be used as a command parameter. For more information, see [`Self::pop_from`].
+///
+/// In slash commands, the backticks are optional because string options can't span multiple lines
+/// anyway: input without backticks is taken as code verbatim.
 #[derive(Default, Debug, PartialEq, Eq, Clone, Hash)]
 pub struct CodeBlock {
     /// The text inside the code block
@@ -127,6 +130,58 @@ impl<'a> PopArgument<'a> for CodeBlock {
     }
 }
 
+/// Parses a [`CodeBlock`] from a slash command string option. Backticks are optional here, and an
+/// unterminated opening fence is tolerated
+fn parse_lenient(input: &str) -> Result<CodeBlock, CodeBlockError> {
+    if let Ok((rest, code_block)) = pop_from(input) {
+        if rest.trim().is_empty() {
+            return Ok(code_block);
+        }
+    }
+
+    let input = input.trim();
+    let code = match input.strip_prefix("```") {
+        Some(code) if !code.contains("```") => code,
+        _ => match input.strip_prefix('`') {
+            Some(code) if !code.contains('`') => code,
+            _ => input,
+        },
+    };
+    if code.trim().is_empty() {
+        return Err(CodeBlockError::default());
+    }
+    Ok(CodeBlock {
+        code: code.to_owned(),
+        language: None,
+        __non_exhaustive: (),
+    })
+}
+
+#[async_trait::async_trait]
+impl crate::SlashArgument for CodeBlock {
+    async fn extract(
+        _: &serenity::Context,
+        _: &serenity::CommandInteraction,
+        value: &serenity::ResolvedValue<'_>,
+    ) -> Result<Self, crate::SlashArgError> {
+        match *value {
+            serenity::ResolvedValue::String(input) => {
+                parse_lenient(input).map_err(|e| crate::SlashArgError::Parse {
+                    error: e.into(),
+                    input: input.into(),
+                })
+            }
+            _ => Err(crate::SlashArgError::new_command_structure_mismatch(
+                "expected string",
+            )),
+        }
+    }
+
+    fn create(builder: serenity::CreateCommandOption) -> serenity::CreateCommandOption {
+        builder.kind(serenity::CommandOptionType::String)
+    }
+}
+
 #[cfg(test)]
 #[test]
 fn test_pop_code_block() {
@@ -162,4 +217,10 @@ fn test_pop_code_block() {
     assert!(pop_from("''").is_err());
     assert!(pop_from("``").is_err());
     assert!(pop_from("``````").is_err());
+
+    assert_eq!(parse_lenient("`a` b").unwrap().code, "`a` b");
+    assert_eq!(parse_lenient(" `a` ").unwrap().code, "a");
+    assert_eq!(parse_lenient("print(1)").unwrap().code, "print(1)");
+    assert_eq!(parse_lenient("```print(1)").unwrap().code, "print(1)");
+    assert!(parse_lenient(" ").is_err());
 }
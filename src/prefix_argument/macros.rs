@@ -1,6 +1,16 @@
 //! A macro that generates backtracking-capable argument parsing code, given a list of parameter
 //! types and attributes
 
+/// Expands to the number of the given tokens, i.e. the index of the parameter that
+/// [`_parse_prefix`] is currently parsing, given the tokens that were parsed before it
+#[doc(hidden)]
+#[macro_export]
+macro_rules! _prefix_param_index {
+    ($($preamble:tt)*) => {
+        <[&str]>::len(&[$(stringify!($preamble)),*])
+    };
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! _parse_prefix {
@@ -26,7 +36,7 @@ macro_rules! _parse_prefix {
                 $crate::_parse_prefix!($ctx $msg $args $attachment_index => [ $error $($preamble)* token ] $($rest)* );
                 // If the code gets here, parsing the rest of the argument has failed
             },
-            Err(e) => $error = e,
+            Err(e) => $error = (e.0, e.1, Some($crate::_prefix_param_index!($($preamble)*))),
         }
         let token: Option<$type> = None;
         // Parse the next arguments without changing the current arg string, thereby skipping the
@@ -46,7 +56,7 @@ macro_rules! _parse_prefix {
                 let token: Option<$type> = Some(token);
                 $crate::_parse_prefix!($ctx $msg $args $attachment_index => [ $error $($preamble)* token ] $($rest)* );
             },
-            Err(e) => $error = e,
+            Err(e) => $error = (e.0, e.1, Some($crate::_prefix_param_index!($($preamble)*))),
         }
     };
 
@@ -68,7 +78,7 @@ macro_rules! _parse_prefix {
                     let token = Some(token);
                     $crate::_parse_prefix!($ctx $msg $args $attachment_index => [ $error $($preamble)* token ]);
                 },
-                Err(e) => $error = (e.into(), Some(input.to_owned())),
+                Err(e) => $error = (e.into(), Some(input.to_owned()), Some($crate::_prefix_param_index!($($preamble)*))),
             }
         }
     };
@@ -119,7 +129,7 @@ macro_rules! _parse_prefix {
     ) => {
         let input = $args.trim_start();
         if input.is_empty() {
-            $error = ($crate::TooFewArguments::default().into(), None);
+            $error = ($crate::TooFewArguments::default().into(), None, Some($crate::_prefix_param_index!($($preamble)*)));
         } else {
             match <$type as $crate::serenity_prelude::ArgumentConvert>::convert(
                 $ctx, $msg.guild_id, Some($msg.channel_id), input
@@ -128,7 +138,7 @@ macro_rules! _parse_prefix {
                     let $args = "";
                     $crate::_parse_prefix!($ctx $msg $args $attachment_index => [ $error $($preamble)* token ]);
                 },
-                Err(e) => $error = (e.into(), Some(input.to_owned())),
+                Err(e) => $error = (e.into(), Some(input.to_owned()), Some($crate::_prefix_param_index!($($preamble)*))),
             }
         }
     };
@@ -145,7 +155,11 @@ macro_rules! _parse_prefix {
             // only allow backtracking if the flag didn't match: it's confusing for the user if they
             // precisely set the flag but it's ignored
             _ => {
-                $error = (concat!("Must use either `", $name, "` or nothing as a modifier").into(), None);
+                $error = (
+                    concat!("Must use either `", $name, "` or nothing as a modifier").into(),
+                    None,
+                    Some($crate::_prefix_param_index!($($preamble)*)),
+                );
                 $crate::_parse_prefix!($ctx $msg $args $attachment_index => [ $error $($preamble)* false ] $($rest)* );
             }
         }
@@ -160,7 +174,7 @@ macro_rules! _parse_prefix {
             Ok(($args, $attachment_index, token)) => {
                 $crate::_parse_prefix!($ctx $msg $args $attachment_index => [ $error $($preamble)* token ] $($rest)* );
             },
-            Err(e) => $error = e,
+            Err(e) => $error = (e.0, e.1, Some($crate::_prefix_param_index!($($preamble)*))),
         }
     };
 
@@ -217,7 +231,9 @@ assert_eq!(
 */
 #[macro_export]
 macro_rules! parse_prefix_args {
-    ($ctx:expr, $msg:expr, $args:expr, $attachment_index:expr => $(
+    // Like the public variant, but the error additionally contains the index of the parameter that
+    // failed to parse, if any. Used by the command macro to point out the parameter to the user
+    (@with_position $ctx:expr, $msg:expr, $args:expr, $attachment_index:expr => $(
         $( #[$attr:ident] )?
         ( $($type:tt)* )
     ),* $(,)? ) => {
@@ -229,8 +245,8 @@ macro_rules! parse_prefix_args {
             let args = $args;
             let attachment_index = $attachment_index;
 
-            let mut error: (Box<dyn std::error::Error + Send + Sync>, Option<String>)
-                = (Box::new($crate::TooManyArguments { __non_exhaustive: () }) as _, None);
+            let mut error: (Box<dyn std::error::Error + Send + Sync>, Option<String>, Option<usize>)
+                = (Box::new($crate::TooManyArguments { __non_exhaustive: () }) as _, None, None);
 
             $crate::_parse_prefix!(
                 ctx msg args attachment_index => [error]
@@ -241,6 +257,18 @@ macro_rules! parse_prefix_args {
             Err(error)
         }
     };
+    ($ctx:expr, $msg:expr, $args:expr, $attachment_index:expr => $(
+        $( #[$attr:ident] )?
+        ( $($type:tt)* )
+    ),* $(,)? ) => {
+        async {
+            $crate::parse_prefix_args!(@with_position $ctx, $msg, $args, $attachment_index => $(
+                $( #[$attr] )? ( $($type)* )
+            ),* )
+            .await
+            .map_err(|(error, input, _)| (error, input))
+        }
+    };
 }
 
 #[cfg(test)]
@@ -19,8 +19,11 @@ use crate::serenity_prelude as serenity;
 /// Pop a whitespace-separated word from the front of the arguments. Supports quotes and quote
 /// escaping.
 ///
-/// Leading whitespace will be trimmed; trailing whitespace is not consumed.
-fn pop_string(args: &str) -> Result<(&str, String), crate::TooFewArguments> {
+/// Leading whitespace will be trimmed; trailing whitespace is not consumed. The word borrows from
+/// the arguments, unless quotes or escapes had to be removed from it.
+pub(crate) fn pop_string(
+    args: &str,
+) -> Result<(&str, std::borrow::Cow<'_, str>), crate::TooFewArguments> {
     // TODO: consider changing the behavior to parse quotes literally if they're in the middle
     // of the string:
     // - `"hello world"` => `hello world`
@@ -32,11 +35,22 @@ fn pop_string(args: &str) -> Result<(&str, String), crate::TooFewArguments> {
         return Err(crate::TooFewArguments::default());
     }
 
-    let mut output = String::new();
+    // Most words contain no quotes or escapes and can be returned as they are
+    let word_end = args
+        .find(|c: char| c.is_whitespace() || c == '"' || c == '\\')
+        .unwrap_or(args.len());
+    if !args[word_end..].starts_with(['"', '\\']) {
+        return Ok((
+            &args[word_end..],
+            std::borrow::Cow::Borrowed(&args[..word_end]),
+        ));
+    }
+
+    let mut output = String::from(&args[..word_end]);
     let mut inside_string = false;
     let mut escaping = false;
 
-    let mut chars = args.chars();
+    let mut chars = args[word_end..].chars();
     // .clone().next() is poor man's .peek(), but we can't do peekable because then we can't
     // call as_str on the Chars iterator
     while let Some(c) = chars.clone().next() {
@@ -56,7 +70,127 @@ fn pop_string(args: &str) -> Result<(&str, String), crate::TooFewArguments> {
         chars.next();
     }
 
-    Ok((chars.as_str(), output))
+    Ok((chars.as_str(), std::borrow::Cow::Owned(output)))
+}
+
+/// Compares a parameter name to what the user typed. Case-insensitive, and dashes in the user
+/// input match underscores in the parameter name.
+fn name_matches(param_name: &str, input: &str) -> bool {
+    param_name.len() == input.len()
+        && param_name
+            .chars()
+            .zip(input.chars())
+            .all(|(a, b)| a.eq_ignore_ascii_case(&b) || (a == '_' && b == '-'))
+}
+
+/// Removes all `--flag` style words matching one of the given flag names from the arguments, no
+/// matter where they appear. Returns the remaining arguments and, for each flag name, whether it was
+/// present.
+///
+/// Flag names match case-insensitively, and underscores can be written as dashes (`--only-bots`
+/// for `only_bots`). Words inside quotes or code blocks are left alone. Because mobile keyboards
+/// like to autocorrect `--` into an em dash, `—flag` is accepted too.
+#[doc(hidden)]
+pub fn extract_flags(args: &str, flag_names: &[&str]) -> (String, Vec<bool>) {
+    let mut found = vec![false; flag_names.len()];
+    let mut output = String::with_capacity(args.len());
+    let mut inside_string = false;
+    let mut inside_code = false;
+
+    let mut rest = args;
+    while !rest.is_empty() {
+        let word_start = rest.len() - rest.trim_start().len();
+        let word_end = rest[word_start..]
+            .find(char::is_whitespace)
+            .map_or(rest.len(), |i| word_start + i);
+        let (piece, word) = (&rest[..word_end], &rest[word_start..word_end]);
+        rest = &rest[word_end..];
+
+        let flag = match inside_string || inside_code {
+            true => None,
+            false => word
+                .strip_prefix("--")
+                .or_else(|| word.strip_prefix('—'))
+                .and_then(|name| flag_names.iter().position(|flag| name_matches(flag, name))),
+        };
+        match flag {
+            Some(i) => found[i] = true,
+            None => output += piece,
+        }
+
+        let mut escaping = false;
+        for c in word.chars() {
+            match c {
+                _ if escaping => escaping = false,
+                '\\' => escaping = true,
+                '"' if !inside_code => inside_string = !inside_string,
+                '`' if !inside_string => inside_code = !inside_code,
+                _ => {}
+            }
+        }
+    }
+
+    (output, found)
+}
+
+/// Removes all `name=value` style arguments whose name matches one of the given parameter names
+/// from the arguments, no matter where they appear. Returns the remaining arguments and, for each
+/// parameter name, the raw value if it was present. Values may be quoted (`name="multi word"`), in
+/// which case the quotes are included in the returned value.
+///
+/// If no named arguments were found at all, returns None, so that the caller can fall back to
+/// positional parsing.
+#[doc(hidden)]
+pub fn extract_named_args<'a>(
+    args: &'a str,
+    param_names: &[&str],
+) -> Option<(String, Vec<Option<&'a str>>)> {
+    let mut values = vec![None; param_names.len()];
+    let mut output = String::with_capacity(args.len());
+    let mut found_any = false;
+
+    let mut rest = args;
+    while !rest.is_empty() {
+        let word_start = rest.len() - rest.trim_start().len();
+        let word = &rest[word_start..];
+
+        let named = word.split_once('=').and_then(|(name, value)| {
+            let index = param_names.iter().position(|p| name_matches(p, name))?;
+            // Quoted values extend until the closing quote, other values until the next whitespace
+            let value_len = match value.strip_prefix('"') {
+                Some(quoted) => {
+                    let mut escaping = false;
+                    let closing_quote = quoted.char_indices().find(|&(_, c)| {
+                        let is_end = c == '"' && !escaping;
+                        escaping = c == '\\' && !escaping;
+                        is_end
+                    });
+                    closing_quote.map_or(value.len(), |(i, _)| i + 2)
+                }
+                None => value.find(char::is_whitespace).unwrap_or(value.len()),
+            };
+            Some((index, &value[..value_len], name.len() + 1 + value_len))
+        });
+
+        let piece_len = match named {
+            Some((index, value, len)) => {
+                values[index] = Some(value);
+                found_any = true;
+                word_start + len
+            }
+            None => {
+                let len = word_start + word.find(char::is_whitespace).unwrap_or(word.len());
+                output += &rest[..len];
+                len
+            }
+        };
+        rest = &rest[piece_len..];
+    }
+
+    match found_any {
+        true => Some((output, values)),
+        false => None,
+    }
 }
 
 /// Error thrown if user passes too many arguments to a command
@@ -130,6 +264,11 @@ impl std::error::Error for InvalidBool {}
 fn test_pop_string() {
     // Test that trailing whitespace is not consumed
     assert_eq!(pop_string("AA BB").unwrap().0, " BB");
+    // Test that words without quotes or escapes aren't copied
+    assert!(matches!(
+        pop_string("AA BB").unwrap().1,
+        std::borrow::Cow::Borrowed("AA")
+    ));
 
     for &(string, arg) in &[
         (r#"AA BB"#, r#"AA"#),
@@ -144,3 +283,49 @@ fn test_pop_string() {
         assert_eq!(pop_string(string).unwrap().1, arg);
     }
 }
+
+#[cfg(test)]
+#[test]
+fn test_extract_flags() {
+    let flags = &["bots", "dry_run"];
+    for &(string, rest, found) in &[
+        ("50 --bots", "50", [true, false]),
+        ("--bots 50", " 50", [true, false]),
+        ("50 --BOTS spam --dry-run", "50 spam", [true, true]),
+        ("50 —dry_run", "50", [false, true]),
+        ("50 bots --bot", "50 bots --bot", [false, false]),
+        (
+            r#""hello --bots" --dry_run"#,
+            r#""hello --bots""#,
+            [false, true],
+        ),
+        ("`--bots` x", "`--bots` x", [false, false]),
+    ] {
+        assert_eq!(
+            extract_flags(string, flags),
+            (rest.to_owned(), found.to_vec())
+        );
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_extract_named_args() {
+    let params = &["user", "reason", "delete_days"];
+    assert_eq!(extract_named_args("ferris spam", params), None);
+    assert_eq!(
+        extract_named_args(r#"reason="being rude" user=ferris"#, params),
+        Some((
+            String::new(),
+            vec![Some("ferris"), Some(r#""being rude""#), None]
+        ))
+    );
+    assert_eq!(
+        extract_named_args("ferris delete-days=7 unknown=1", params),
+        Some(("ferris unknown=1".to_owned(), vec![None, None, Some("7")]))
+    );
+    assert_eq!(
+        extract_named_args(r#"reason="a \" b" x"#, params),
+        Some((" x".to_owned(), vec![None, Some(r#""a \" b""#), None]))
+    );
+}
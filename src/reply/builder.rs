@@ -19,6 +19,14 @@ pub struct CreateReply {
     pub allowed_mentions: Option<serenity::CreateAllowedMentions>,
     /// Whether this message is an inline reply.
     pub reply: bool,
+    /// Message flags, like [`serenity::MessageFlags::SUPPRESS_EMBEDS`]. See [`Self::flags`] for
+    /// how they behave on edits.
+    pub flags: Option<serenity::MessageFlags>,
+    /// Whether content longer than Discord's limit is split into multiple messages. `None` uses
+    /// [`crate::FrameworkOptions::split_long_replies`].
+    pub split_long_content: Option<bool>,
+    /// Poll attached to the message.
+    pub poll: Option<serenity::CreatePoll<::serenity::builder::create_poll::Ready>>,
     #[doc(hidden)]
     pub __non_exhaustive: (),
 }
@@ -70,6 +78,71 @@ impl CreateReply {
         self
     }
 
+    /// Set the message flags, for example [`serenity::MessageFlags::SUPPRESS_EMBEDS`].
+    ///
+    /// Flags that Discord doesn't allow for the kind of message are dropped or rejected by
+    /// Discord. [`Self::ephemeral`] takes precedence over [`serenity::MessageFlags::EPHEMERAL`].
+    ///
+    /// When a reply is edited, for example by edit tracking or [`crate::ReplyHandle::edit`],
+    /// Discord only allows changing [`serenity::MessageFlags::SUPPRESS_EMBEDS`], so all other
+    /// flags are ignored and keep the value from when the message was sent. Edits of an
+    /// application command's initial response can't change any flags, because serenity's
+    /// [`serenity::EditInteractionResponse`] has no way to set them.
+    ///
+    /// Only flags are supported. Discord's newer message layout (components v2, with containers
+    /// and sections instead of action rows) is opted into with a message flag too, but its
+    /// component builders don't exist in the serenity version poise builds against, so poise
+    /// can't send such messages yet.
+    pub fn flags(mut self, flags: serenity::MessageFlags) -> Self {
+        self.flags = Some(flags);
+        self
+    }
+
+    /// Toggles whether the message is sent silently, i.e. without push and desktop notifications
+    /// for the mentioned users, like messages starting with `@silent` in the Discord client.
+    ///
+    /// Sets or clears [`serenity::MessageFlags::SUPPRESS_NOTIFICATIONS`] in [`Self::flags`].
+    pub fn silent(mut self, silent: bool) -> Self {
+        let mut flags = self.flags.unwrap_or_else(serenity::MessageFlags::empty);
+        flags.set(serenity::MessageFlags::SUPPRESS_NOTIFICATIONS, silent);
+        self.flags = Some(flags);
+        self
+    }
+
+    /// Toggles whether content longer than Discord's limit of 2000 characters is split into
+    /// multiple messages instead of failing to send. Overrides
+    /// [`crate::FrameworkOptions::split_long_replies`].
+    ///
+    /// See [`crate::send_reply`] for how the message parts are sent.
+    pub fn split_long_content(mut self, split_long_content: bool) -> Self {
+        self.split_long_content = Some(split_long_content);
+        self
+    }
+
+    /// Attaches a native Discord poll to the message.
+    ///
+    /// Polls can't be edited after sending, so this has no effect when editing a message (for
+    /// example via edit tracking).
+    ///
+    /// ```rust
+    /// # use poise::serenity_prelude as serenity;
+    /// let poll = serenity::CreatePoll::new()
+    ///     .question("Best pizza topping?")
+    ///     .answers(vec![
+    ///         serenity::CreatePollAnswer::new().text("Pineapple"),
+    ///         serenity::CreatePollAnswer::new().text("Anything but pineapple"),
+    ///     ])
+    ///     .duration(std::time::Duration::from_secs(60 * 60 * 24));
+    /// let reply = poise::CreateReply::default().poll(poll);
+    /// ```
+    pub fn poll(
+        mut self,
+        poll: serenity::CreatePoll<::serenity::builder::create_poll::Ready>,
+    ) -> Self {
+        self.poll = Some(poll);
+        self
+    }
+
     /// Makes this message an inline reply to another message like [`serenity::Message::reply`]
     /// (prefix-only, because slash commands are always inline replies anyways).
     ///
@@ -97,6 +170,9 @@ impl CreateReply {
             ephemeral,
             allowed_mentions,
             reply: _, // can't reply to a message in interactions
+            flags,
+            split_long_content: _, // handled in send_reply
+            poll,
             __non_exhaustive: (),
         } = self;
 
@@ -109,9 +185,18 @@ impl CreateReply {
         if let Some(components) = components {
             builder = builder.components(components);
         }
+        // Must come before ephemeral, which only adds or removes its own flag
+        if let Some(flags) = flags {
+            builder = builder.flags(serenity::InteractionResponseFlags::from_bits_truncate(
+                flags.bits(),
+            ));
+        }
         if let Some(ephemeral) = ephemeral {
             builder = builder.ephemeral(ephemeral);
         }
+        if let Some(poll) = poll {
+            builder = builder.poll(poll);
+        }
 
         builder.add_files(attachments).embeds(embeds)
     }
@@ -129,6 +214,9 @@ impl CreateReply {
             ephemeral,
             allowed_mentions,
             reply: _,
+            flags,
+            split_long_content: _, // handled in send_reply
+            poll,
             __non_exhaustive: (),
         } = self;
 
@@ -142,9 +230,16 @@ impl CreateReply {
         if let Some(allowed_mentions) = allowed_mentions {
             builder = builder.allowed_mentions(allowed_mentions);
         }
+        // Must come before ephemeral, which only adds or removes its own flag
+        if let Some(flags) = flags {
+            builder = builder.flags(flags);
+        }
         if let Some(ephemeral) = ephemeral {
             builder = builder.ephemeral(ephemeral);
         }
+        if let Some(poll) = poll {
+            builder = builder.poll(poll);
+        }
 
         builder.add_files(attachments)
     }
@@ -162,6 +257,9 @@ impl CreateReply {
             ephemeral: _, // can't edit ephemerality in retrospect
             allowed_mentions,
             reply: _,
+            flags: _,              // not supported by serenity's builder, see Self::flags
+            split_long_content: _, // handled in send_reply
+            poll: _,               // polls can't be edited
             __non_exhaustive: (),
         } = self;
 
@@ -188,6 +286,9 @@ impl CreateReply {
             ephemeral: _, // not supported in prefix
             allowed_mentions,
             reply: _, // can't edit reference message afterwards
+            flags,
+            split_long_content: _, // handled in send_reply
+            poll: _,               // polls can't be edited
             __non_exhaustive: (),
         } = self;
 
@@ -205,6 +306,10 @@ impl CreateReply {
         if let Some(components) = components {
             builder = builder.components(components);
         }
+        // Discord only allows changing this flag on edits
+        if let Some(flags) = flags {
+            builder = builder.flags(flags & serenity::MessageFlags::SUPPRESS_EMBEDS);
+        }
 
         builder.embeds(embeds).attachments(attachments_builder)
     }
@@ -222,6 +327,9 @@ impl CreateReply {
             ephemeral: _, // not supported in prefix
             allowed_mentions,
             reply,
+            flags,
+            split_long_content: _, // handled in send_reply
+            poll,
             __non_exhaustive: (),
         } = self;
 
@@ -235,9 +343,15 @@ impl CreateReply {
         if let Some(components) = components {
             builder = builder.components(components);
         }
+        if let Some(flags) = flags {
+            builder = builder.flags(flags);
+        }
         if reply {
             builder = builder.reference_message(invocation_message);
         }
+        if let Some(poll) = poll {
+            builder = builder.poll(poll);
+        }
 
         for attachment in attachments {
             builder = builder.add_file(attachment);
@@ -6,6 +6,15 @@ pub use builder::*;
 mod send_reply;
 pub use send_reply::*;
 
+mod retry;
+pub use retry::*;
+
+mod split;
+pub use split::*;
+
+mod stream;
+pub use stream::*;
+
 use crate::serenity_prelude as serenity;
 use std::borrow::Cow;
 
@@ -159,3 +168,96 @@ impl ReplyHandle<'_> {
         Ok(())
     }
 }
+
+/// Owned counterpart of [`ReplyHandleInner`], stored in [`ReplyRegistry`]
+#[derive(Debug, Clone)]
+enum SentReply {
+    /// A message sent in a prefix command
+    Prefix(Box<serenity::Message>),
+    /// An application command response. `None` is the initial response
+    Application(Option<Box<serenity::Message>>),
+}
+
+impl SentReply {
+    /// Returns whether this refers to the given message. The initial response of an application
+    /// command never matches because its ID is unknown
+    fn is_message(&self, id: serenity::MessageId) -> bool {
+        match self {
+            Self::Prefix(msg) | Self::Application(Some(msg)) => msg.id == id,
+            Self::Application(None) => false,
+        }
+    }
+}
+
+/// Keeps track of all messages sent in response to a single command invocation, so they can be
+/// enumerated with [`crate::Context::replies`].
+///
+/// A new registry is created by the framework for every invocation.
+#[derive(Debug, Default)]
+pub struct ReplyRegistry {
+    /// Sent messages, in the order they were first sent
+    replies: parking_lot::Mutex<Vec<SentReply>>,
+}
+
+impl ReplyRegistry {
+    /// Creates an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a message sent in a prefix command. If the message was already recorded (because
+    /// it was edited to show the new reply), it's updated in place
+    pub(crate) fn register_prefix(&self, msg: &serenity::Message) {
+        let mut replies = self.replies.lock();
+        match replies.iter_mut().find(|reply| reply.is_message(msg.id)) {
+            Some(reply) => *reply = SentReply::Prefix(Box::new(msg.clone())),
+            None => replies.push(SentReply::Prefix(Box::new(msg.clone()))),
+        }
+    }
+
+    /// Records an application command response. `followup` is `None` for the initial response
+    pub(crate) fn register_application(&self, followup: Option<&serenity::Message>) {
+        let reply = SentReply::Application(followup.map(|msg| Box::new(msg.clone())));
+        self.replies.lock().push(reply);
+    }
+
+    /// Forgets the given messages, e.g. because they were deleted
+    pub(crate) fn remove(&self, ids: &[serenity::MessageId]) {
+        self.replies
+            .lock()
+            .retain(|reply| !ids.iter().any(|&id| reply.is_message(id)));
+    }
+
+    /// Returns the number of recorded messages
+    pub fn len(&self) -> usize {
+        self.replies.lock().len()
+    }
+
+    /// Returns whether no messages have been recorded
+    pub fn is_empty(&self) -> bool {
+        self.replies.lock().is_empty()
+    }
+
+    /// Creates handles for all recorded messages. Application command responses are skipped if no
+    /// interaction is given
+    pub(crate) fn handles<'a>(
+        &self,
+        http: &'a serenity::Http,
+        interaction: Option<&'a serenity::CommandInteraction>,
+    ) -> Vec<ReplyHandle<'a>> {
+        self.replies
+            .lock()
+            .iter()
+            .filter_map(|reply| {
+                Some(ReplyHandle(match reply.clone() {
+                    SentReply::Prefix(msg) => ReplyHandleInner::Prefix(msg),
+                    SentReply::Application(followup) => ReplyHandleInner::Application {
+                        http,
+                        interaction: interaction?,
+                        followup,
+                    },
+                }))
+            })
+            .collect()
+    }
+}
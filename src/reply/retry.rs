@@ -0,0 +1,154 @@
+//! Retrying of replies that failed because of transient Discord errors
+
+use crate::serenity_prelude as serenity;
+
+/// How replies and deferrals are retried when Discord fails temporarily, see
+/// [`crate::FrameworkOptions::reply_retry`]
+///
+/// serenity's HTTP client already waits for rate limits before sending requests and retries
+/// requests that hit a rate limit anyway. On top of that, this policy retries requests that
+/// failed with a server error (5xx), a connection error or a gateway error, which serenity
+/// returns right away. Other errors, like missing permissions, are never retried.
+///
+/// A request that failed with a server or connection error may still have been processed by
+/// Discord. Retrying edits and initial interaction responses is harmless in that case, but
+/// retrying new messages and followups could post them twice. Those are therefore only retried
+/// on rate limits (HTTP 429) that reach poise, which Discord guarantees weren't processed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// How often a request is retried before the error is returned
+    pub max_retries: u32,
+    /// How long to wait before the first retry. Doubles with every further retry
+    pub initial_backoff: std::time::Duration,
+    /// Upper limit of the time between two attempts
+    pub max_backoff: std::time::Duration,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: std::time::Duration::from_millis(500),
+            max_backoff: std::time::Duration::from_secs(5),
+            __non_exhaustive: (),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns how long to wait before the given retry (starting at 0)
+    fn backoff(&self, retry: u32) -> std::time::Duration {
+        let factor = 2_u32.saturating_pow(retry);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+
+    /// Runs the request, retrying it according to this policy if it fails with a transient error.
+    ///
+    /// Only use this for requests that can safely be repeated, like edits. See
+    /// [`Self::run_non_idempotent`] for requests that create something.
+    pub async fn run<T, F: std::future::Future<Output = Result<T, serenity::Error>>>(
+        &self,
+        request: impl FnMut() -> F,
+    ) -> Result<T, serenity::Error> {
+        self.run_if(request, is_transient).await
+    }
+
+    /// Runs the request, retrying it according to this policy only if it was rate limited. For
+    /// requests that would have a duplicate effect if they were processed by Discord despite
+    /// failing, like sending a message
+    pub async fn run_non_idempotent<
+        T,
+        F: std::future::Future<Output = Result<T, serenity::Error>>,
+    >(
+        &self,
+        request: impl FnMut() -> F,
+    ) -> Result<T, serenity::Error> {
+        self.run_if(request, is_rate_limited).await
+    }
+
+    /// Runs the request, retrying it according to this policy while it fails with an error that
+    /// `should_retry` accepts
+    async fn run_if<T, F: std::future::Future<Output = Result<T, serenity::Error>>>(
+        &self,
+        mut request: impl FnMut() -> F,
+        should_retry: fn(&serenity::Error) -> bool,
+    ) -> Result<T, serenity::Error> {
+        let mut retry = 0;
+        loop {
+            match request().await {
+                Err(error) if retry < self.max_retries && should_retry(&error) => {
+                    let backoff = self.backoff(retry);
+                    tracing::debug!("retrying request in {:?} after error: {}", backoff, error);
+                    tokio::time::sleep(backoff).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+/// Returns whether the error is likely to go away when the request is retried
+pub fn is_transient(error: &serenity::Error) -> bool {
+    match error {
+        serenity::Error::Http(serenity::HttpError::UnsuccessfulRequest(response)) => {
+            response.status_code.as_u16() == 429 || response.status_code.is_server_error()
+        }
+        serenity::Error::Http(serenity::HttpError::Request(_)) => true,
+        serenity::Error::Gateway(_) => true,
+        _ => false,
+    }
+}
+
+/// Returns whether the request was rejected because of a rate limit, which means that Discord
+/// didn't process it
+pub fn is_rate_limited(error: &serenity::Error) -> bool {
+    matches!(
+        error,
+        serenity::Error::Http(serenity::HttpError::UnsuccessfulRequest(response))
+            if response.status_code.as_u16() == 429
+    )
+}
+
+/// Runs the request with [`crate::FrameworkOptions::reply_retry`], or just once if that isn't set
+pub(crate) async fn with_retry<T, F: std::future::Future<Output = Result<T, serenity::Error>>>(
+    policy: Option<&RetryPolicy>,
+    mut request: impl FnMut() -> F,
+) -> Result<T, serenity::Error> {
+    match policy {
+        Some(policy) => policy.run(request).await,
+        None => request().await,
+    }
+}
+
+/// Like [`with_retry`], but for requests that must not be repeated after Discord may have
+/// processed them, see [`RetryPolicy::run_non_idempotent`]
+pub(crate) async fn with_retry_non_idempotent<
+    T,
+    F: std::future::Future<Output = Result<T, serenity::Error>>,
+>(
+    policy: Option<&RetryPolicy>,
+    mut request: impl FnMut() -> F,
+) -> Result<T, serenity::Error> {
+    match policy {
+        Some(policy) => policy.run_non_idempotent(request).await,
+        None => request().await,
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_backoff() {
+    let policy = RetryPolicy {
+        max_retries: 5,
+        initial_backoff: std::time::Duration::from_millis(100),
+        max_backoff: std::time::Duration::from_millis(500),
+        __non_exhaustive: (),
+    };
+    let backoffs = (0..5).map(|retry| policy.backoff(retry).as_millis());
+    assert_eq!(backoffs.collect::<Vec<_>>(), [100, 200, 400, 500, 500]);
+}
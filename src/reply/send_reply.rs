@@ -27,12 +27,83 @@ pub async fn send_reply<U, E>(
     ctx: crate::Context<'_, U, E>,
     builder: crate::CreateReply,
 ) -> Result<crate::ReplyHandle<'_>, serenity::Error> {
-    Ok(match ctx {
+    let split = builder
+        .split_long_content
+        .unwrap_or(ctx.framework().options().split_long_replies);
+    if split {
+        let mut handles = send_split_reply(ctx, builder).await?;
+        // send_split_reply always sends at least one message
+        return Ok(handles.pop().expect("no message was sent"));
+    }
+    send_reply_part(ctx, builder, true).await
+}
+
+/// Like [`send_reply`], but splits content longer than Discord's limit of 2000 characters into
+/// multiple messages (see [`split_message_content`](crate::split_message_content)) and returns
+/// handles to all of them, in order.
+///
+/// [`send_reply`] does this too if [`crate::CreateReply::split_long_content`] or
+/// [`crate::FrameworkOptions::split_long_replies`] is set, but only returns the handle of the last
+/// message.
+///
+/// All parts keep the reply's ephemerality, allowed mentions and flags. Embeds, attachments,
+/// components and polls are attached to the last message, and only the first message is an inline
+/// reply. In prefix commands with edit tracking, the continuation messages are deleted when the
+/// reply is replaced by a new one, or when the invoking message is deleted (with `track_deletion`).
+pub async fn send_split_reply<U, E>(
+    ctx: crate::Context<'_, U, E>,
+    mut builder: crate::CreateReply,
+) -> Result<Vec<crate::ReplyHandle<'_>>, serenity::Error> {
+    let mut chunks = match &builder.content {
+        Some(content) if content.chars().count() > crate::MESSAGE_CONTENT_LIMIT => {
+            crate::split_message_content(content, crate::MESSAGE_CONTENT_LIMIT)
+        }
+        _ => return Ok(vec![send_reply_part(ctx, builder, true).await?]),
+    };
+    let last_chunk = chunks.pop();
+
+    let mut handles = Vec::new();
+    for chunk in chunks {
+        let part = crate::CreateReply {
+            content: Some(chunk),
+            ephemeral: builder.ephemeral,
+            allowed_mentions: builder.allowed_mentions.clone(),
+            reply: builder.reply && handles.is_empty(),
+            flags: builder.flags,
+            ..Default::default()
+        };
+        let is_first = handles.is_empty();
+        handles.push(send_reply_part(ctx, part, is_first).await?);
+    }
+
+    builder.content = last_chunk;
+    builder.reply = builder.reply && handles.is_empty();
+    let is_first = handles.is_empty();
+    handles.push(send_reply_part(ctx, builder, is_first).await?);
+    Ok(handles)
+}
+
+/// Sends a single message. If `is_first` is false, the message is a continuation of a split reply
+/// and is sent as a new message in prefix commands, even if the command reuses its response
+async fn send_reply_part<U, E>(
+    ctx: crate::Context<'_, U, E>,
+    builder: crate::CreateReply,
+    is_first: bool,
+) -> Result<crate::ReplyHandle<'_>, serenity::Error> {
+    let handle = match ctx {
         crate::Context::Prefix(ctx) => super::ReplyHandle(super::ReplyHandleInner::Prefix(
-            crate::send_prefix_reply(ctx, builder).await?,
+            send_prefix_reply_inner(ctx, builder, is_first).await?,
         )),
         crate::Context::Application(ctx) => crate::send_application_reply(ctx, builder).await?,
-    })
+    };
+
+    let is_autocomplete = matches!(handle.0, super::ReplyHandleInner::Autocomplete);
+    if let Some(post_reply_callback) = ctx.framework().options().post_reply_callback {
+        if !is_autocomplete {
+            post_reply_callback(ctx, &handle).await;
+        }
+    }
+    Ok(handle)
 }
 
 /// Shorthand of [`send_reply`] for text-only messages
@@ -70,22 +141,28 @@ pub async fn send_application_reply<U, E>(
             let builder = builder
                 .to_slash_followup_response(serenity::CreateInteractionResponseFollowup::new());
 
-            ctx.interaction
-                .create_followup(ctx.serenity_context, builder)
-                .await?
+            let retry_policy = ctx.framework.options.reply_retry.as_ref();
+            let followup = super::with_retry_non_idempotent(retry_policy, || {
+                ctx.interaction
+                    .create_followup(ctx.serenity_context, builder.clone())
+            })
+            .await?;
+            ctx.replies.register_application(Some(&followup));
+            followup
         }))
     } else {
         let builder =
             builder.to_slash_initial_response(serenity::CreateInteractionResponseMessage::new());
 
-        ctx.interaction
-            .create_response(
-                ctx.serenity_context,
-                serenity::CreateInteractionResponse::Message(builder),
-            )
-            .await?;
+        let response = serenity::CreateInteractionResponse::Message(builder);
+        super::with_retry(ctx.framework.options.reply_retry.as_ref(), || {
+            ctx.interaction
+                .create_response(ctx.serenity_context, response.clone())
+        })
+        .await?;
         ctx.has_sent_initial_response
             .store(true, std::sync::atomic::Ordering::SeqCst);
+        ctx.replies.register_application(None);
 
         None
     };
@@ -101,6 +178,17 @@ pub async fn send_application_reply<U, E>(
 pub async fn send_prefix_reply<U, E>(
     ctx: crate::PrefixContext<'_, U, E>,
     builder: crate::CreateReply,
+) -> Result<Box<serenity::Message>, serenity::Error> {
+    send_prefix_reply_inner(ctx, builder, true).await
+}
+
+/// See [`send_prefix_reply`]. If `is_first` is false, the message continues a reply that was split
+/// into multiple messages: it's always sent as a new message and stored as an additional response
+/// in the edit tracker
+async fn send_prefix_reply_inner<U, E>(
+    ctx: crate::PrefixContext<'_, U, E>,
+    builder: crate::CreateReply,
+    is_first: bool,
 ) -> Result<Box<serenity::Message>, serenity::Error> {
     let builder = ctx.reply_builder(builder);
 
@@ -113,31 +201,53 @@ pub async fn send_prefix_reply<U, E>(
         None
     };
 
-    let existing_response = if ctx.command.reuse_response {
+    let reuse_response = ctx.command.reuse_response
+        || crate::original_command(ctx).is_some_and(|command| command.reuse_response);
+    let existing_response = if reuse_response && is_first {
         lock_edit_tracker()
             .as_mut()
             .and_then(|t| t.find_bot_response(ctx.msg.id))
-            .cloned()
     } else {
         None
     };
 
-    Ok(Box::new(if let Some(mut response) = existing_response {
-        response
-            .edit(ctx.serenity_context, {
-                // Reset the message. We don't want leftovers of the previous message (e.g. user
-                // sends a message with `.content("abc")` in a track_edits command, and the edited
-                // message happens to contain embeds, we don't want to keep those embeds)
-                // (*f = Default::default() won't do)
-                let b = serenity::EditMessage::new()
-                    .content("")
-                    .embeds(Vec::new())
-                    .components(Vec::new())
-                    .remove_all_attachments();
-
-                builder.to_prefix_edit(b)
-            })
-            .await?;
+    let response = if let Some(mut response) = existing_response {
+        // The previous reply's continuation messages would be out of place after the edit
+        let stale_responses = lock_edit_tracker()
+            .map(|mut t| t.take_additional_bot_responses(ctx.msg.id))
+            .unwrap_or_default();
+        for stale_response in &stale_responses {
+            if let Err(e) = stale_response.delete(ctx.serenity_context).await {
+                tracing::warn!("failed to delete stale bot response: {}", e);
+            }
+        }
+        ctx.replies
+            .remove(&stale_responses.iter().map(|m| m.id).collect::<Vec<_>>());
+
+        let edit = {
+            // Reset the message. We don't want leftovers of the previous message (e.g. user
+            // sends a message with `.content("abc")` in a track_edits command, and the edited
+            // message happens to contain embeds, we don't want to keep those embeds)
+            // (*f = Default::default() won't do)
+            let b = serenity::EditMessage::new()
+                .content("")
+                .embeds(Vec::new())
+                .components(Vec::new())
+                .remove_all_attachments();
+
+            builder.to_prefix_edit(b)
+        };
+        let retry_policy = ctx.framework.options.reply_retry.as_ref();
+        let edited = super::with_retry(retry_policy, || {
+            let mut response = response.clone();
+            let edit = edit.clone();
+            async move {
+                response.edit(ctx.serenity_context, edit).await?;
+                Ok(response)
+            }
+        })
+        .await?;
+        response = edited;
 
         // If the entry still exists after the await, update it to the new contents
         // We don't check ctx.command.reuse_response because it's true anyways in this branch
@@ -147,17 +257,37 @@ pub async fn send_prefix_reply<U, E>(
 
         response
     } else {
-        let new_response = ctx
-            .msg
-            .channel_id
-            .send_message(ctx.serenity_context, builder.to_prefix(ctx.msg.into()))
-            .await?;
+        let message = builder.to_prefix(ctx.msg.into());
+        let retry_policy = ctx.framework.options.reply_retry.as_ref();
+        let new_response = super::with_retry_non_idempotent(retry_policy, || {
+            ctx.msg
+                .channel_id
+                .send_message(ctx.serenity_context, message.clone())
+        })
+        .await?;
         // We don't check ctx.command.reuse_response because we need to store bot responses for
         // track_deletion too
         if let Some(track_edits) = &mut lock_edit_tracker() {
-            track_edits.set_bot_response(ctx.msg, new_response.clone(), ctx.command.track_deletion);
+            // Only a response that later replies are edited into replaces the tracked response.
+            // Otherwise, all responses are tracked so that they can be deleted together
+            if is_first && reuse_response {
+                track_edits.set_bot_response(
+                    ctx.msg,
+                    new_response.clone(),
+                    ctx.command.track_deletion,
+                );
+            } else {
+                track_edits.add_bot_response(
+                    ctx.msg,
+                    new_response.clone(),
+                    ctx.command.track_deletion,
+                );
+            }
         }
 
         new_response
-    }))
+    };
+
+    ctx.replies.register_prefix(&response);
+    Ok(Box::new(response))
 }
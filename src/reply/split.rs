@@ -0,0 +1,136 @@
+//! Splitting of message content that exceeds Discord's length limit
+
+/// Maximum length of a message's content in characters
+pub const MESSAGE_CONTENT_LIMIT: usize = 2000;
+
+/// Finds the opening line of the code block that is still open at the end of `text`, if any
+fn open_code_fence(text: &str) -> Option<&str> {
+    let mut open_fence = None;
+    for line in text.lines() {
+        if line.trim_start().starts_with("```") {
+            open_fence = match open_fence {
+                // A line with only a fence closes the block; anything else opens a new one
+                Some(_) if line.trim() == "```" => None,
+                Some(fence) => Some(fence),
+                None => Some(line.trim_start()),
+            };
+        }
+    }
+    open_fence
+}
+
+/// Returns the byte index after at most `limit` characters of `text`, preferring to cut after
+/// whitespace
+fn hard_split_point(text: &str, limit: usize) -> usize {
+    let max = text
+        .char_indices()
+        .nth(limit)
+        .map_or(text.len(), |(i, _)| i);
+    if max == text.len() {
+        return max;
+    }
+    match text[..max].rfind(char::is_whitespace) {
+        Some(i) if i > 0 => i + text[i..].chars().next().map_or(0, char::len_utf8),
+        _ => max,
+    }
+}
+
+/// Splits message content into chunks of at most `limit` characters.
+///
+/// Content is split at line breaks where possible, and lines that are too long on their own are
+/// split at whitespace or, failing that, anywhere. If a split falls into a code block, the block is
+/// closed at the end of one chunk and reopened (with the same language tag) at the start of the
+/// next, so that formatting survives.
+///
+/// ```rust
+/// let content = "first line\nsecond line";
+/// assert_eq!(poise::split_message_content(content, 15), ["first line", "second line"]);
+/// ```
+pub fn split_message_content(content: &str, limit: usize) -> Vec<String> {
+    /// Room reserved at the end of each chunk to close a code block
+    const FENCE_CLOSE: &str = "\n```";
+
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_len = 0;
+    // Whether the current chunk contains more than a reopened code fence
+    let mut has_content = false;
+    let mut lines = content.split('\n').map(str::to_owned).collect::<Vec<_>>();
+    lines.reverse();
+
+    while let Some(line) = lines.pop() {
+        let line_len = line.chars().count();
+        // The newline that joins the line to the chunk
+        let separator = usize::from(!current.is_empty());
+        let is_fence = line.trim_start().starts_with("```");
+        let reserve = match open_code_fence(&current) {
+            Some(_) if line.trim() == "```" => 0,
+            Some(_) => FENCE_CLOSE.len(),
+            None if is_fence => FENCE_CLOSE.len(),
+            None => 0,
+        };
+        let available = limit.saturating_sub(current_len + separator + reserve);
+
+        if line_len <= available || !has_content {
+            let line = if line_len <= available {
+                line
+            } else {
+                // The line doesn't fit even into an otherwise empty chunk
+                let split = hard_split_point(&line, available.max(1));
+                lines.push(line[split..].to_owned());
+                line[..split].to_owned()
+            };
+            if separator == 1 {
+                current.push('\n');
+            }
+            current_len += separator + line.chars().count();
+            current.push_str(&line);
+            has_content = true;
+            continue;
+        }
+
+        // Finish the current chunk and start a new one with this line
+        let reopen = open_code_fence(&current).map(str::to_owned);
+        if reopen.is_some() {
+            current.push_str(FENCE_CLOSE);
+        }
+        chunks.push(std::mem::take(&mut current));
+        current_len = 0;
+        has_content = false;
+        lines.push(line);
+        if let Some(fence) = reopen {
+            current_len = fence.chars().count();
+            current = fence;
+        }
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+#[cfg(test)]
+#[test]
+fn test_split_message_content() {
+    assert_eq!(split_message_content("", 10), Vec::<String>::new());
+    assert_eq!(split_message_content("short", 10), ["short"]);
+    assert_eq!(
+        split_message_content("aaa\nbbb\nccc", 7),
+        ["aaa\nbbb", "ccc"]
+    );
+    assert_eq!(
+        split_message_content("one two three", 8),
+        ["one two ", "three"]
+    );
+    assert_eq!(
+        split_message_content("abcdefghij", 4),
+        ["abcd", "efgh", "ij"]
+    );
+
+    let chunks = split_message_content("```rs\nlet a;\nlet b;\n```", 16);
+    assert_eq!(chunks, ["```rs\nlet a;\n```", "```rs\nlet b;\n```"]);
+    for chunk in split_message_content(&"word ".repeat(1000), MESSAGE_CONTENT_LIMIT) {
+        assert!(chunk.chars().count() <= MESSAGE_CONTENT_LIMIT);
+    }
+}
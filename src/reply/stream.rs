@@ -0,0 +1,122 @@
+//! Progressively edited replies for commands that produce output incrementally
+
+use crate::serenity_prelude as serenity;
+
+/// A reply that is progressively edited as content arrives, created by
+/// [`crate::Context::stream_reply`].
+///
+/// Content added with [`Self::push_str`] is shown by editing the reply, at most once per
+/// [`Self::interval`] to stay clear of rate limits. When the content grows beyond Discord's limit
+/// of 2000 characters, the message is finalized and the rest continues in a new message (see
+/// [`crate::split_message_content`], which also keeps code blocks intact).
+///
+/// Call [`Self::finish`] at the end, otherwise the last pushed content may never be shown.
+///
+/// ```rust,no_run
+/// # async fn _test(ctx: poise::Context<'_, (), poise::serenity_prelude::Error>) -> Result<(), poise::serenity_prelude::Error> {
+/// let mut reply = ctx.stream_reply();
+/// for line in ["Compiling...", "Running tests...", "Done!"] {
+///     reply.push_str(line).await?;
+///     reply.push_str("\n").await?;
+/// }
+/// reply.finish().await?;
+/// # Ok(()) }
+/// ```
+pub struct StreamingReply<'a, U, E> {
+    /// Context that the messages are sent in
+    ctx: crate::Context<'a, U, E>,
+    /// Messages that are finished because they're full
+    finished: Vec<crate::ReplyHandle<'a>>,
+    /// Message that is currently being written, if it was sent yet
+    live: Option<crate::ReplyHandle<'a>>,
+    /// Content of the message that is currently being written, whether or not it was sent yet
+    current: String,
+    /// Whether [`Self::current`] has changed since it was last sent
+    dirty: bool,
+    /// When the current content was last sent
+    last_flush: Option<std::time::Instant>,
+    /// Minimum time between edits
+    interval: std::time::Duration,
+}
+
+impl<'a, U, E> StreamingReply<'a, U, E> {
+    /// Creates a streaming reply in the given context. Nothing is sent until content is pushed
+    pub fn new(ctx: crate::Context<'a, U, E>) -> Self {
+        Self {
+            ctx,
+            finished: Vec::new(),
+            live: None,
+            current: String::new(),
+            dirty: false,
+            last_flush: None,
+            interval: std::time::Duration::from_secs(1),
+        }
+    }
+
+    /// Sets the minimum time between edits of the reply. Defaults to one second
+    pub fn interval(mut self, interval: std::time::Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Appends text to the reply. The reply is updated right away if [`Self::interval`] has
+    /// passed since the last update, otherwise on a later call or [`Self::finish`]
+    pub async fn push_str(&mut self, text: &str) -> Result<(), serenity::Error> {
+        if text.is_empty() {
+            return Ok(());
+        }
+        self.current.push_str(text);
+        self.dirty = true;
+
+        if self.current.chars().count() > crate::MESSAGE_CONTENT_LIMIT {
+            let mut chunks =
+                crate::split_message_content(&self.current, crate::MESSAGE_CONTENT_LIMIT);
+            // The last chunk stays open for more content
+            let rest = chunks.pop().unwrap_or_default();
+            for chunk in chunks {
+                self.send_current(chunk).await?;
+                // The next chunk goes into a new message
+                self.finished.extend(self.live.take());
+            }
+            self.current = rest;
+            self.dirty = !self.current.is_empty();
+        }
+
+        let interval_passed = self
+            .last_flush
+            .map_or(true, |last_flush| last_flush.elapsed() >= self.interval);
+        if interval_passed {
+            self.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Updates the reply with all content pushed so far, ignoring [`Self::interval`]
+    pub async fn flush(&mut self) -> Result<(), serenity::Error> {
+        if !self.dirty || self.current.trim().is_empty() {
+            return Ok(());
+        }
+        self.send_current(self.current.clone()).await?;
+        self.dirty = false;
+        self.last_flush = Some(std::time::Instant::now());
+        Ok(())
+    }
+
+    /// Flushes the remaining content and returns handles to all sent messages, in order
+    pub async fn finish(mut self) -> Result<Vec<crate::ReplyHandle<'a>>, serenity::Error> {
+        self.flush().await?;
+        self.finished.extend(self.live);
+        Ok(self.finished)
+    }
+
+    /// Shows the given content in the message that is currently being written, sending it if
+    /// necessary
+    async fn send_current(&mut self, content: String) -> Result<(), serenity::Error> {
+        let reply = crate::CreateReply::default().content(content);
+        match &self.live {
+            Some(handle) => handle.edit(self.ctx, reply).await?,
+            None => self.live = Some(self.ctx.send(reply).await?),
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,146 @@
+//! Recurring and delayed background tasks, see [`crate::FrameworkOptions::scheduled_tasks`]
+
+use crate::{serenity_prelude as serenity, BoxFuture};
+
+/// A background task that is started by [`crate::Framework`] once the bot is ready and the user
+/// data has been set up, and stopped when the shards shut down.
+///
+/// ```rust
+/// # use poise::serenity_prelude as serenity;
+/// # type Data = (); type Error = Box<dyn std::error::Error + Send + Sync>;
+/// let rotate_status = poise::ScheduledTask::every(
+///     "rotate status",
+///     std::time::Duration::from_secs(300),
+///     |ctx, _framework, _data| {
+///         Box::pin(async move {
+///             ctx.set_activity(Some(serenity::ActivityData::playing("with commands")));
+///             Ok(())
+///         })
+///     },
+/// );
+///
+/// poise::FrameworkOptions::<Data, Error> {
+///     scheduled_tasks: vec![rotate_status],
+///     ..Default::default()
+/// };
+/// ```
+#[derive(derivative::Derivative)]
+#[derivative(Debug(bound = ""))]
+pub struct ScheduledTask<U, E> {
+    /// Name of the task, used in logs and errors
+    pub name: String,
+    /// How long to wait after the bot is ready before the first run
+    pub delay: std::time::Duration,
+    /// Time between the end of one run and the start of the next, or None to run only once
+    pub interval: Option<std::time::Duration>,
+    /// The task itself
+    #[derivative(Debug = "ignore")]
+    pub action: for<'a> fn(
+        &'a serenity::Context,
+        crate::FrameworkContext<'a, U, E>,
+        &'a U,
+    ) -> BoxFuture<'a, Result<(), E>>,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl<U, E> ScheduledTask<U, E> {
+    /// Creates a task that runs right after the bot is ready and then repeatedly, waiting
+    /// `interval` after each run
+    pub fn every(
+        name: impl Into<String>,
+        interval: std::time::Duration,
+        action: for<'a> fn(
+            &'a serenity::Context,
+            crate::FrameworkContext<'a, U, E>,
+            &'a U,
+        ) -> BoxFuture<'a, Result<(), E>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            delay: std::time::Duration::ZERO,
+            interval: Some(interval),
+            action,
+            __non_exhaustive: (),
+        }
+    }
+
+    /// Creates a task that runs once, `delay` after the bot is ready
+    pub fn after(
+        name: impl Into<String>,
+        delay: std::time::Duration,
+        action: for<'a> fn(
+            &'a serenity::Context,
+            crate::FrameworkContext<'a, U, E>,
+            &'a U,
+        ) -> BoxFuture<'a, Result<(), E>>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            delay,
+            interval: None,
+            action,
+            __non_exhaustive: (),
+        }
+    }
+
+    /// Sets how long to wait after the bot is ready before the first run
+    pub fn delay(mut self, delay: std::time::Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+}
+
+/// Runs a single task until it's done, passing its errors to [`crate::FrameworkOptions::on_error`]
+async fn run_task<U, E>(
+    task: &ScheduledTask<U, E>,
+    ctx: &serenity::Context,
+    framework: crate::FrameworkContext<'_, U, E>,
+) {
+    tokio::time::sleep(task.delay).await;
+    loop {
+        if let Err(error) = (task.action)(ctx, framework, framework.user_data).await {
+            let error = crate::FrameworkError::ScheduledTask {
+                error,
+                task_name: &task.name,
+                ctx,
+                framework,
+            };
+            error.handle(framework.options).await;
+        }
+
+        match task.interval {
+            Some(interval) => tokio::time::sleep(interval).await,
+            None => break,
+        }
+    }
+}
+
+/// Completes once all shards have been shut down
+async fn shards_shut_down(shard_manager: &serenity::ShardManager) {
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+        if shard_manager.runners.lock().await.is_empty() {
+            break;
+        }
+    }
+}
+
+/// Runs all [`crate::FrameworkOptions::scheduled_tasks`] concurrently until they're done or the
+/// shards shut down
+pub(crate) async fn run_scheduled_tasks<U, E>(
+    ctx: &serenity::Context,
+    framework: crate::FrameworkContext<'_, U, E>,
+) {
+    let tasks = &framework.options.scheduled_tasks;
+    if tasks.is_empty() {
+        return;
+    }
+
+    let tasks = futures_util::future::join_all(
+        tasks
+            .iter()
+            .map(|task| Box::pin(run_task(task, ctx, framework))),
+    );
+    futures_util::future::select(tasks, Box::pin(shards_shut_down(framework.shard_manager))).await;
+}
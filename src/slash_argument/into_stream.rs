@@ -39,3 +39,17 @@ macro_rules! into_stream {
         }
     };
 }
+
+/// Calls an inline autocomplete closure from `#[autocomplete = |ctx, partial| ...]`. The closure
+/// is taken last so that its parameter types are known when its body is type-checked
+#[doc(hidden)]
+pub fn call_autocomplete_closure<'a, U, E, F, R>(
+    ctx: crate::Context<'a, U, E>,
+    partial: &'a str,
+    callback: F,
+) -> R
+where
+    F: FnOnce(crate::Context<'a, U, E>, &'a str) -> R,
+{
+    callback(ctx, partial)
+}
@@ -0,0 +1,187 @@
+//! Parsing code for `Vec<T>` slash command parameters marked with `#[list]`, which are represented
+//! as a single string option containing a list because Discord has no variadic options
+
+use super::SlashArgError;
+use std::marker::PhantomData;
+
+use crate::serenity_prelude as serenity;
+
+/// Error thrown when an element of a `Vec<T>` slash command parameter can't be parsed
+#[derive(Debug)]
+pub struct ListElementError {
+    /// 1-based position of the element in the list
+    pub position: usize,
+    /// Error that occurred while parsing the element
+    pub error: Box<dyn std::error::Error + Send + Sync>,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+impl std::fmt::Display for ListElementError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Item {} of the list is invalid: {}",
+            self.position, self.error
+        )
+    }
+}
+impl std::error::Error for ListElementError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&*self.error)
+    }
+}
+
+/// Splits the string option of a `Vec<T>` slash command parameter into its elements.
+///
+/// If the input contains a comma, elements are separated by commas, so that elements can contain
+/// spaces without quoting (`New York, Berlin`). Otherwise, they're separated by whitespace like
+/// prefix command arguments, with quotes for grouping. Empty elements are skipped.
+#[doc(hidden)]
+pub fn split_slash_list(input: &str) -> Vec<String> {
+    if input.contains(',') {
+        return input
+            .split(',')
+            .map(|element| element.trim().trim_matches('"').to_owned())
+            .filter(|element| !element.is_empty())
+            .collect();
+    }
+
+    let mut elements = Vec::new();
+    let mut rest = input;
+    while let Ok((new_rest, element)) = crate::prefix_argument::pop_string(rest) {
+        elements.push(element.into_owned());
+        rest = new_rest;
+    }
+    elements
+}
+
+/// Parses a single element of a `Vec<T>` slash command parameter from a string.
+///
+/// Uses the auto-deref specialization hack to pick the best parser for the type: the variant
+/// names for [`crate::ChoiceParameter`] types, [`serenity::ArgumentConvert`] (which includes
+/// [`std::str::FromStr`]) for most types, and the [`crate::SlashArgument`] implementation for
+/// types that are registered as string options, like [`crate::Timezone`].
+#[doc(hidden)]
+#[async_trait::async_trait]
+pub trait SlashListElementHack<T>: Sized {
+    async fn extract_element(
+        self,
+        ctx: &serenity::Context,
+        interaction: &serenity::CommandInteraction,
+        element: &str,
+    ) -> Result<T, Box<dyn std::error::Error + Send + Sync>>;
+}
+
+#[async_trait::async_trait]
+impl<T: crate::ChoiceParameter + Send + Sync> SlashListElementHack<T> for &&PhantomData<T> {
+    async fn extract_element(
+        self,
+        _: &serenity::Context,
+        _: &serenity::CommandInteraction,
+        element: &str,
+    ) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
+        T::from_name(element).ok_or_else(|| crate::InvalidChoice::default().into())
+    }
+}
+
+#[async_trait::async_trait]
+impl<T> SlashListElementHack<T> for &PhantomData<T>
+where
+    T: serenity::ArgumentConvert + Send + Sync,
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    async fn extract_element(
+        self,
+        ctx: &serenity::Context,
+        interaction: &serenity::CommandInteraction,
+        element: &str,
+    ) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
+        T::convert(
+            ctx,
+            interaction.guild_id,
+            Some(interaction.channel_id),
+            element,
+        )
+        .await
+        .map_err(Into::into)
+    }
+}
+
+#[async_trait::async_trait]
+impl<T: crate::SlashArgument + Send + Sync> SlashListElementHack<T> for PhantomData<T> {
+    async fn extract_element(
+        self,
+        ctx: &serenity::Context,
+        interaction: &serenity::CommandInteraction,
+        element: &str,
+    ) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
+        let value = serenity::ResolvedValue::String(element);
+        T::extract(ctx, interaction, &value)
+            .await
+            .map_err(|e| match e {
+                SlashArgError::Parse { error, .. } => error,
+                e => e.into(),
+            })
+    }
+}
+
+/// Parses the string option of a `Vec<T>` slash command parameter into a list of `T`. Pass the
+/// element type as the first argument.
+///
+/// An invocation of this macro is generated by `crate::command`, so you usually don't need it
+/// directly.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! extract_slash_list {
+    ($target:ty, $ctx:expr, $interaction:expr, $value:expr) => {
+        async {
+            use $crate::SlashListElementHack as _;
+
+            let (ctx, interaction) = ($ctx, $interaction);
+            let input = match *$value {
+                $crate::serenity_prelude::ResolvedValue::String(input) => input,
+                _ => {
+                    return Err($crate::SlashArgError::new_command_structure_mismatch(
+                        "expected string for list parameter",
+                    ))
+                }
+            };
+
+            let mut elements = Vec::<$target>::new();
+            for (i, element) in $crate::split_slash_list(input).into_iter().enumerate() {
+                let parsed = (&&std::marker::PhantomData::<$target>)
+                    .extract_element(ctx, interaction, &element)
+                    .await;
+                match parsed {
+                    Ok(value) => elements.push(value),
+                    Err(error) => {
+                        return Err($crate::SlashArgError::new_parse(
+                            $crate::ListElementError {
+                                position: i + 1,
+                                error,
+                                __non_exhaustive: (),
+                            }
+                            .into(),
+                            element,
+                        ))
+                    }
+                }
+            }
+            Ok::<_, $crate::SlashArgError>(elements)
+        }
+    };
+}
+
+#[cfg(test)]
+#[test]
+fn test_split_slash_list() {
+    for &(input, elements) in &[
+        ("", &[][..]),
+        ("1 2  3", &["1", "2", "3"][..]),
+        ("1, 2,3,", &["1", "2", "3"][..]),
+        ("New York, Berlin", &["New York", "Berlin"][..]),
+        (r#""New York" Berlin"#, &["New York", "Berlin"][..]),
+    ] {
+        assert_eq!(split_slash_list(input), elements, "{input}");
+    }
+}
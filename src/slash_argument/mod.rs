@@ -6,6 +6,9 @@ pub use slash_macro::*;
 mod slash_trait;
 pub use slash_trait::*;
 
+mod list;
+pub use list::*;
+
 mod context_menu;
 pub use context_menu::*;
 
@@ -45,6 +45,10 @@ impl SlashArgError {
         Self::CommandStructureMismatch { description }
     }
 
+    pub fn new_parse(error: Box<dyn std::error::Error + Send + Sync>, input: String) -> Self {
+        Self::Parse { error, input }
+    }
+
     pub fn to_framework_error<U, E>(
         self,
         ctx: crate::ApplicationContext<'_, U, E>,
@@ -57,16 +61,19 @@ impl SlashArgError {
                 ctx: ctx.into(),
                 error,
                 input: Some(input),
+                parameter: None,
             },
             Self::Invalid(description) => crate::FrameworkError::ArgumentParse {
                 ctx: ctx.into(),
                 error: description.into(),
                 input: None,
+                parameter: None,
             },
             Self::Http(error) => crate::FrameworkError::ArgumentParse {
                 ctx: ctx.into(),
                 error: error.into(),
                 input: None,
+                parameter: None,
             },
             Self::__NonExhaustive => unreachable!(),
         }
@@ -146,6 +153,30 @@ macro_rules! _parse_slash {
         }
     };
 
+    // Extract #[list] Vec<T>, which is entered as a single string and split up
+    ($ctx:ident, $interaction:ident, $args:ident => $name:literal: LIST Vec<$type:ty $(,)*>) => {
+        if let Some(arg) = $args.iter().find(|arg| arg.name == $name) {
+            $crate::extract_slash_list!($type, $ctx, $interaction, &arg.value).await?
+        } else {
+            vec![]
+        }
+    };
+
+    // Extract Vec<Attachment>, which is spread across multiple optional attachment options
+    ($ctx:ident, $interaction:ident, $args:ident => $name:literal: ATTACHMENTS [$($option_name:literal),*]) => {
+        {
+            let mut attachments = Vec::new();
+            $(
+                if let Some(attachment) = $crate::_parse_slash!(
+                    $ctx, $interaction, $args => $option_name: Option<$crate::serenity_prelude::Attachment>
+                ) {
+                    attachments.push(attachment);
+                }
+            )*
+            attachments
+        }
+    };
+
     // Extract #[flag]
     ($ctx:ident, $interaction:ident, $args:ident => $name:literal: FLAG) => {
         $crate::_parse_slash!($ctx, $interaction, $args => $name: Option<bool>)
@@ -35,6 +35,16 @@ pub trait SlashArgument: Sized {
     fn choices() -> Vec<crate::CommandParameterChoice> {
         Vec::new()
     }
+
+    /// If this type comes with built-in autocomplete suggestions, returns a function that produces
+    /// them from the user's partial input. Used for parameters without an explicit
+    /// `#[autocomplete]` callback. For your own types, mark the parameter with
+    /// `#[type_autocomplete]` to use this; poise's own types are picked up automatically.
+    ///
+    /// Don't call this method directly! Use [`crate::slash_argument_autocomplete!`]
+    fn autocomplete() -> Option<fn(&str) -> Vec<serenity::AutocompleteChoice>> {
+        None
+    }
 }
 
 /// Implemented for all types that can be used as a function parameter in a slash command.
@@ -56,6 +66,10 @@ pub trait SlashArgumentHack<T>: Sized {
     fn choices(self) -> Vec<crate::CommandParameterChoice> {
         Vec::new()
     }
+
+    fn autocomplete(self) -> Option<fn(&str) -> Vec<serenity::AutocompleteChoice>> {
+        None
+    }
 }
 
 /// Full version of [`crate::SlashArgument::extract`].
@@ -89,6 +103,17 @@ macro_rules! slash_argument_choices {
     }};
 }
 
+/// Full version of [`crate::SlashArgument::autocomplete`].
+///
+/// Uses specialization to get full coverage of types. Pass the type as the first argument
+#[macro_export]
+macro_rules! slash_argument_autocomplete {
+    ($target:ty) => {{
+        use $crate::SlashArgumentHack as _;
+        (&&std::marker::PhantomData::<$target>).autocomplete()
+    }};
+}
+
 /// Handles arbitrary types that can be parsed from string.
 #[async_trait::async_trait]
 impl<T> SlashArgumentHack<T> for PhantomData<T>
@@ -180,6 +205,10 @@ impl<T: SlashArgument + Sync> SlashArgumentHack<T> for &PhantomData<T> {
     fn choices(self) -> Vec<crate::CommandParameterChoice> {
         <T as SlashArgument>::choices()
     }
+
+    fn autocomplete(self) -> Option<fn(&str) -> Vec<serenity::AutocompleteChoice>> {
+        <T as SlashArgument>::autocomplete()
+    }
 }
 
 /// Versatile macro to implement `SlashArgumentHack` for simple types
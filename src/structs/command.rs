@@ -62,6 +62,11 @@ pub struct Command<U, E> {
     pub cooldowns: std::sync::Mutex<crate::CooldownTracker>,
     /// Configuration for the [`crate::CooldownTracker`]
     pub cooldown_config: std::sync::RwLock<crate::CooldownConfig>,
+    /// How many invocations of this command may run at the same time. Further invocations fail
+    /// with [`crate::FrameworkError::AlreadyRunning`]
+    pub max_concurrent: crate::ConcurrencyLimits,
+    /// Tracks running invocations for [`Self::max_concurrent`]. Mainly for framework internal use
+    pub concurrency: std::sync::Mutex<crate::ConcurrencyTracker>,
     /// After the first response, whether to post subsequent responses as edits to the initial
     /// message
     ///
@@ -93,12 +98,31 @@ pub struct Command<U, E> {
     pub dm_only: bool,
     /// If true, the command may only run in NSFW channels
     pub nsfw_only: bool,
+    /// Whether the command can be invoked. Can be flipped at runtime, e.g. to turn off a broken
+    /// command without redeploying, see [`Self::set_enabled`]. Invocations of a disabled command
+    /// or of its subcommands fail with [`crate::FrameworkError::CommandDisabled`]. The command
+    /// stays registered on Discord.
+    ///
+    /// True by default
+    #[derivative(Default(value = "std::sync::atomic::AtomicBool::new(true)"))]
+    pub enabled: std::sync::atomic::AtomicBool,
     /// Command-specific override for [`crate::FrameworkOptions::on_error`]
     #[derivative(Debug = "ignore")]
     pub on_error: Option<fn(crate::FrameworkError<'_, U, E>) -> BoxFuture<'_, ()>>,
+    /// Command-specific handler for [`crate::FrameworkError::ArgumentParse`]. Takes precedence
+    /// over [`Self::on_error`]
+    #[derivative(Debug = "ignore")]
+    pub on_parse_error: Option<fn(crate::FrameworkError<'_, U, E>) -> BoxFuture<'_, ()>>,
+    /// Command-specific handler for [`crate::FrameworkError::CommandCheckFailed`]. Takes
+    /// precedence over [`Self::on_error`]
+    #[derivative(Debug = "ignore")]
+    pub on_check_failure: Option<fn(crate::FrameworkError<'_, U, E>) -> BoxFuture<'_, ()>>,
     /// If any of these functions returns false, this command will not be executed.
     #[derivative(Debug = "ignore")]
     pub checks: Vec<fn(crate::Context<'_, U, E>) -> BoxFuture<'_, Result<bool, E>>>,
+    /// Messages for users who are denied by the check at the same index in [`Self::checks`], see
+    /// [`crate::FrameworkError::CommandCheckFailed::message`]. Missing entries mean no message
+    pub check_failure_messages: Vec<Option<String>>,
     /// List of parameters for this command
     ///
     /// Used for registering and parsing slash commands. Can also be used in help commands
@@ -112,8 +136,15 @@ pub struct Command<U, E> {
     pub aliases: Vec<String>,
     /// Whether to rerun the command if an existing invocation message is edited (prefix-only)
     pub invoke_on_edit: bool,
-    /// Whether to delete the bot response if an existing invocation message is deleted (prefix-only)
+    /// Whether to delete the bot responses if an existing invocation message is deleted
+    /// (prefix-only)
+    ///
+    /// This includes all replies sent through poise. Messages sent by other means can be added with
+    /// [`crate::EditTracker::add_bot_response`].
     pub track_deletion: bool,
+    /// If set, edits only rerun the command within this duration after the invocation message was
+    /// sent, even if [`crate::EditTracker`] tracks messages for longer (prefix-only)
+    pub edit_tracking_window: Option<std::time::Duration>,
     /// Whether to broadcast a typing indicator while executing this commmand (prefix-only)
     pub broadcast_typing: bool,
 
@@ -122,6 +153,11 @@ pub struct Command<U, E> {
     pub context_menu_name: Option<String>,
     /// Whether responses to this command should be ephemeral by default (application-only)
     pub ephemeral: bool,
+    /// Command-specific override for [`crate::FrameworkOptions::auto_defer`] (application-only)
+    pub auto_defer: Option<std::time::Duration>,
+    /// ID that Discord assigned to the top-level slash command this command belongs to. Filled
+    /// in by [`crate::builtins::set_application_command_ids`] when the commands are registered.
+    pub application_command_id: std::sync::Mutex<Option<serenity::CommandId>>,
 
     // Like #[non_exhaustive], but #[poise::command] still needs to be able to create an instance
     #[doc(hidden)]
@@ -135,7 +171,77 @@ impl<U, E> PartialEq for Command<U, E> {
 }
 impl<U, E> Eq for Command<U, E> {}
 
+impl<U, E> AsRef<Command<U, E>> for Command<U, E> {
+    fn as_ref(&self) -> &Self {
+        self
+    }
+}
+
 impl<U, E> Command<U, E> {
+    /// Returns whether the command can currently be invoked, see [`Self::enabled`]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Enables or disables the command at runtime, see [`Self::enabled`]
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled
+            .store(enabled, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Copies the category and cooldowns of this command to all its subcommands (recursively) that
+    /// don't set their own. Checks don't need to be copied: the checks of parent commands already
+    /// run before a subcommand. Called by [`crate::command_group`]
+    pub fn share_with_subcommands(&mut self) {
+        let config = self.cooldown_config.read().unwrap().clone();
+        for subcommand in &mut self.subcommands {
+            if subcommand.category.is_none() {
+                subcommand.category.clone_from(&self.category);
+            }
+            let subconfig = subcommand.cooldown_config.get_mut().unwrap();
+            for (cooldown, shared) in [
+                (&mut subconfig.global, config.global),
+                (&mut subconfig.user, config.user),
+                (&mut subconfig.guild, config.guild),
+                (&mut subconfig.channel, config.channel),
+                (&mut subconfig.member, config.member),
+            ] {
+                if cooldown.is_none() {
+                    *cooldown = shared;
+                }
+            }
+            subcommand.share_with_subcommands();
+        }
+    }
+
+    /// Returns a clickable mention of this slash command, like `</ban:123456789>`.
+    ///
+    /// Returns None if the command wasn't registered through poise yet, see
+    /// [`Self::application_command_id`].
+    pub fn mention(&self) -> Option<String> {
+        let id = (*self.application_command_id.lock().unwrap())?;
+        Some(format!("</{}:{}>", self.qualified_name, id))
+    }
+
+    /// Returns the usage of this command in a prefix invocation, like `ban <user> [reason]`.
+    /// Required parameters are shown in angle brackets and optional ones in square brackets
+    pub fn usage(&self) -> String {
+        let mut usage = self.qualified_name.clone();
+        for param in &self.parameters {
+            usage += " ";
+            usage += &Self::format_parameter(param);
+        }
+        usage
+    }
+
+    /// Formats a parameter for [`Self::usage`], like `<user>` or `[reason]`
+    pub fn format_parameter(param: &crate::CommandParameter<U, E>) -> String {
+        match param.required {
+            true => format!("<{}>", param.name),
+            false => format!("[{}]", param.name),
+        }
+    }
+
     /// Serializes this Command into an application command option, which is the form which Discord
     /// requires subcommands to be in
     fn create_as_subcommand(&self) -> Option<serenity::CreateCommandOption> {
@@ -0,0 +1,113 @@
+//! Lookup table for finding commands by name without scanning all of them, see [`CommandIndex`]
+
+use std::collections::HashMap;
+
+/// Lookup table from command names to commands, used by prefix and slash dispatch instead of
+/// comparing the invoked name against every command and alias.
+///
+/// Maps the ASCII-lowercase names, aliases and context menu names of a list of commands to their
+/// positions in the list, with one nested index per command for its subcommands. Lookups still
+/// compare the names of the found commands, so the index only narrows down the candidates.
+///
+/// Only [`crate::CommandSet`] builds an index, together with the list of commands it belongs to.
+/// Since neither can be changed afterwards, the index always fits its commands.
+#[derive(Debug, Default)]
+pub(crate) struct CommandIndex {
+    /// Positions of the commands, in order, by lowercase name, alias or context menu name
+    positions: HashMap<String, Vec<usize>>,
+    /// Indices of the subcommands of each command
+    subcommands: Vec<CommandIndex>,
+}
+
+/// Returns the ASCII-lowercase name, only allocating if it has uppercase letters
+fn lowercase(name: &str) -> std::borrow::Cow<'_, str> {
+    match name.bytes().any(|b| b.is_ascii_uppercase()) {
+        true => name.to_ascii_lowercase().into(),
+        false => name.into(),
+    }
+}
+
+impl CommandIndex {
+    /// Builds the index of the given commands and their subcommands
+    pub(crate) fn new<U, E>(commands: &[impl AsRef<crate::Command<U, E>>]) -> Self {
+        let mut positions = HashMap::<String, Vec<usize>>::new();
+        for (i, command) in commands.iter().enumerate() {
+            let command = command.as_ref();
+            let names = std::iter::once(&command.name)
+                .chain(&command.aliases)
+                .chain(&command.context_menu_name);
+            for name in names {
+                let command_positions = positions.entry(name.to_ascii_lowercase()).or_default();
+                if command_positions.last() != Some(&i) {
+                    command_positions.push(i);
+                }
+            }
+        }
+        Self {
+            positions,
+            subcommands: commands
+                .iter()
+                .map(|command| Self::new(&command.as_ref().subcommands))
+                .collect(),
+        }
+    }
+
+    /// Returns the commands which may be called `name`, in order, each with the index of its
+    /// subcommands. `index` must have been built from `commands`; without an index, all commands
+    /// are returned
+    pub(crate) fn candidates<'a, 'b, U, E>(
+        index: Option<&'b Self>,
+        commands: &'a [impl AsRef<crate::Command<U, E>>],
+        name: &str,
+    ) -> Vec<(&'a crate::Command<U, E>, Option<&'b Self>)> {
+        match index {
+            Some(index) => index
+                .positions
+                .get(&*lowercase(name))
+                .into_iter()
+                .flatten()
+                .map(|&i| (commands[i].as_ref(), Some(&index.subcommands[i])))
+                .collect(),
+            None => commands
+                .iter()
+                .map(|command| (command.as_ref(), None))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_command_index() {
+    let commands = vec![
+        crate::Command::<(), ()> {
+            name: "ping".into(),
+            aliases: vec!["p".into(), "PING".into()],
+            ..Default::default()
+        },
+        crate::Command {
+            name: "config".into(),
+            subcommands: vec![crate::Command {
+                name: "set".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        },
+    ];
+    let index = CommandIndex::new(&commands);
+
+    let names = |index, name| {
+        CommandIndex::candidates(index, &commands, name)
+            .into_iter()
+            .map(|(command, _)| command.name.as_str())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(Some(&index), "Ping"), ["ping"]);
+    assert_eq!(names(Some(&index), "p"), ["ping"]);
+    assert!(names(Some(&index), "set").is_empty());
+    assert_eq!(names(None, "set"), ["ping", "config"]);
+
+    let (_, subcommand_index) = CommandIndex::candidates(Some(&index), &commands, "config")[0];
+    let subcommands = CommandIndex::candidates(subcommand_index, &commands[1].subcommands, "SET");
+    assert_eq!(subcommands.len(), 1);
+}
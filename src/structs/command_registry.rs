@@ -0,0 +1,122 @@
+//! Swapping the commands of a running framework, see [`CommandRegistry`]
+
+use std::sync::{Arc, RwLock};
+
+/// Holds the current [`crate::CommandSet`] of a framework and replaces it when commands are added
+/// or removed at runtime.
+///
+/// Dispatch [loads](Self::load) the current commands once per event, so changes apply to events
+/// received afterwards. Invocations that are already running keep using the commands they
+/// started with, and commands that were removed are dropped once no invocation uses them anymore.
+///
+/// [`crate::Framework`] creates the registry on construction. Commands are changed with
+/// [`crate::Framework::add_command`] and friends, or from within commands with
+/// [`crate::FrameworkContext::add_command`] and friends.
+#[derive(derivative::Derivative)]
+#[derivative(Debug(bound = ""))]
+pub struct CommandRegistry<U, E> {
+    /// The commands that new events are dispatched to
+    current: RwLock<Arc<crate::CommandSet<U, E>>>,
+}
+
+impl<U, E> CommandRegistry<U, E> {
+    /// Moves the commands out of [`crate::FrameworkOptions::commands`], fills in their
+    /// [qualified names](crate::Command::qualified_name) and applies
+    /// `FrameworkOptions::translations` (`i18n` feature).
+    ///
+    /// [`crate::Framework`] does this on construction. Use this when dispatching events manually.
+    pub fn new(options: &mut crate::FrameworkOptions<U, E>) -> Self {
+        let commands = std::mem::take(&mut options.commands);
+        let commands = crate::CommandSet::from_prepared(prepare_commands(options, commands));
+        Self {
+            current: RwLock::new(Arc::new(commands)),
+        }
+    }
+
+    /// Returns the current commands
+    pub fn load(&self) -> Arc<crate::CommandSet<U, E>> {
+        self.current.read().unwrap().clone()
+    }
+
+    /// Adds a command, replacing an existing top-level command with the same name
+    pub(crate) fn add_command(
+        &self,
+        options: &crate::FrameworkOptions<U, E>,
+        command: crate::Command<U, E>,
+    ) {
+        let mut current = self.current.write().unwrap();
+        let mut commands = current.to_vec();
+        commands.retain(|existing| existing.name != command.name);
+        commands.extend(prepare_commands(options, vec![command]));
+        *current = Arc::new(crate::CommandSet::from_prepared(commands));
+    }
+
+    /// Removes the top-level command with the given name and returns it, if it exists
+    pub(crate) fn remove_command(&self, name: &str) -> Option<Arc<crate::Command<U, E>>> {
+        let mut current = self.current.write().unwrap();
+        let mut commands = current.to_vec();
+        let position = commands.iter().position(|command| command.name == name)?;
+        let command = commands.remove(position);
+        *current = Arc::new(crate::CommandSet::from_prepared(commands));
+        Some(command)
+    }
+
+    /// Replaces all commands and returns the previous ones
+    pub(crate) fn replace_commands(
+        &self,
+        options: &crate::FrameworkOptions<U, E>,
+        commands: Vec<crate::Command<U, E>>,
+    ) -> Arc<crate::CommandSet<U, E>> {
+        let commands = crate::CommandSet::from_prepared(prepare_commands(options, commands));
+        std::mem::replace(&mut *self.current.write().unwrap(), Arc::new(commands))
+    }
+}
+
+/// Fills in qualified names and applies `FrameworkOptions::translations` (`i18n` feature), and
+/// wraps the commands in [`Arc`]s
+#[cfg_attr(not(feature = "i18n"), allow(unused_variables))]
+fn prepare_commands<U, E>(
+    options: &crate::FrameworkOptions<U, E>,
+    mut commands: Vec<crate::Command<U, E>>,
+) -> Vec<Arc<crate::Command<U, E>>> {
+    crate::set_qualified_names(&mut commands);
+    #[cfg(feature = "i18n")]
+    if let Some(translations) = &options.translations {
+        translations.apply_to_commands(&mut commands);
+    }
+    commands.into_iter().map(Arc::new).collect()
+}
+
+#[cfg(test)]
+#[test]
+fn test_command_registry() {
+    let command = |name: &str| crate::Command::<(), String> {
+        name: name.into(),
+        ..Default::default()
+    };
+    let mut options = crate::FrameworkOptions {
+        commands: vec![command("ping"), command("help")],
+        ..Default::default()
+    };
+    let registry = CommandRegistry::new(&mut options);
+    assert!(options.commands.is_empty());
+
+    let before = registry.load();
+    registry.add_command(&options, command("ping"));
+    registry.add_command(&options, command("stats"));
+    let removed = registry.remove_command("help").unwrap();
+    assert_eq!(removed.name, "help");
+    assert!(registry.remove_command("help").is_none());
+
+    // Loaded sets aren't affected by later changes
+    let names = |set: &crate::CommandSet<(), String>| {
+        set.iter()
+            .map(|command| command.name.clone())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(names(&before), ["ping", "help"]);
+    let after = registry.load();
+    assert_eq!(names(&after), ["ping", "stats"]);
+    assert_ne!(before.generation(), after.generation());
+    assert!(crate::find_command(&after, "stats", false, &mut Vec::new()).is_some());
+}
@@ -0,0 +1,115 @@
+//! The commands of a framework, see [`CommandSet`]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Source of [`CommandSet::generation`]
+static NEXT_GENERATION: AtomicU64 = AtomicU64::new(1);
+
+/// The top-level commands used by dispatch, together with a lookup table of their names.
+///
+/// Commands are held behind [`Arc`], so building a new set when commands are added or removed
+/// (see [`crate::Framework::add_command`]) only copies pointers to the unchanged commands. A set
+/// can't be changed once built; it derefs to the slice of its commands.
+#[derive(derivative::Derivative)]
+#[derivative(Default(bound = ""), Debug(bound = ""))]
+pub struct CommandSet<U, E> {
+    /// The commands, in order
+    commands: Vec<Arc<crate::Command<U, E>>>,
+    /// Lookup table of [`Self::commands`] for dispatch
+    #[derivative(Debug = "ignore")]
+    index: crate::CommandIndex,
+    /// Unique ID of this set, see [`Self::generation`]
+    generation: u64,
+}
+
+impl<U, E> CommandSet<U, E> {
+    /// Creates a set of the given commands and fills in their
+    /// [qualified names](crate::Command::qualified_name).
+    ///
+    /// To also apply `FrameworkOptions::translations` (`i18n` feature), use
+    /// [`crate::CommandRegistry::new`] instead.
+    pub fn new(mut commands: Vec<crate::Command<U, E>>) -> Self {
+        crate::set_qualified_names(&mut commands);
+        Self::from_prepared(commands.into_iter().map(Arc::new).collect())
+    }
+
+    /// Creates a set of commands whose qualified names are already filled in
+    pub(crate) fn from_prepared(commands: Vec<Arc<crate::Command<U, E>>>) -> Self {
+        let index = crate::CommandIndex::new(&commands);
+        Self {
+            commands,
+            index,
+            generation: NEXT_GENERATION.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the lookup table of the commands
+    pub(crate) fn index(&self) -> &crate::CommandIndex {
+        &self.index
+    }
+
+    /// Returns a number that is different for every set of commands, so it changes whenever the
+    /// commands of a framework are changed. Caches of data derived from the commands can use it
+    /// to notice changes, like [`crate::builtins::HelpCache`]. 0 for an empty default set
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Searches the command tree for the command with the given
+    /// [qualified name](crate::Command::qualified_name), like `settings reset`
+    pub fn command_by_qualified_name(&self, qualified_name: &str) -> Option<&crate::Command<U, E>> {
+        /// Recursive helper that also searches subcommands
+        fn find<'a, U, E>(
+            commands: &'a [impl AsRef<crate::Command<U, E>>],
+            qualified_name: &str,
+        ) -> Option<&'a crate::Command<U, E>> {
+            commands.iter().find_map(|command| {
+                let command: &crate::Command<U, E> = command.as_ref();
+                if command.qualified_name == qualified_name {
+                    return Some(command);
+                }
+                find(&command.subcommands, qualified_name)
+            })
+        }
+        find(&self.commands, qualified_name)
+    }
+
+    /// Enables or disables the command with the given qualified name at runtime, see
+    /// [`crate::Command::enabled`]. Returns false if there's no such command.
+    ///
+    /// ```rust,no_run
+    /// # type Error = Box<dyn std::error::Error + Send + Sync>;
+    /// # type Context<'a> = poise::Context<'a, (), Error>;
+    /// # async fn _f(ctx: Context<'_>) {
+    /// // Turn off a misbehaving command until it's fixed
+    /// ctx.framework().commands().set_command_enabled("leaderboard", false);
+    /// # }
+    /// ```
+    pub fn set_command_enabled(&self, qualified_name: &str, enabled: bool) -> bool {
+        match self.command_by_qualified_name(qualified_name) {
+            Some(command) => {
+                command.set_enabled(enabled);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+impl<U, E> std::ops::Deref for CommandSet<U, E> {
+    type Target = [Arc<crate::Command<U, E>>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.commands
+    }
+}
+
+impl<'a, U, E> IntoIterator for &'a CommandSet<U, E> {
+    type Item = &'a Arc<crate::Command<U, E>>;
+    type IntoIter = std::slice::Iter<'a, Arc<crate::Command<U, E>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.commands.iter()
+    }
+}
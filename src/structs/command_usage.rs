@@ -0,0 +1,140 @@
+//! Opt-in counting of command invocations, see [`crate::FrameworkOptions::command_usage`]
+
+use std::collections::HashMap;
+
+use crate::serenity_prelude as serenity;
+
+/// Usage numbers of a single command, see [`CommandUsage`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CommandStats {
+    /// How often the command was run. Invocations that failed checks or cooldowns aren't counted
+    pub invocations: u64,
+    /// How many of [`Self::invocations`] returned an error
+    pub failures: u64,
+    /// Invocations per guild. Invocations in DMs aren't included
+    pub per_guild: HashMap<serenity::GuildId, u64>,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+/// Counts invocations per command and per guild, once set as
+/// [`crate::FrameworkOptions::command_usage`].
+///
+/// Keep a clone of the [`std::sync::Arc`] to read the numbers from outside of commands, for
+/// example to export them to a metrics system. See [`crate::builtins::command_stats`] for a
+/// command that displays them.
+///
+/// ```rust
+/// let command_usage = std::sync::Arc::new(poise::CommandUsage::new());
+/// let options = poise::FrameworkOptions::<(), poise::serenity_prelude::Error> {
+///     command_usage: Some(command_usage.clone()),
+///     ..Default::default()
+/// };
+///
+/// // Later, e.g. in a background task
+/// for (command, invocations) in command_usage.top(10) {
+///     println!("{}: {}", command, invocations);
+/// }
+/// ```
+#[derive(Debug, Default)]
+pub struct CommandUsage {
+    /// Stats by [`crate::Command::qualified_name`]
+    commands: parking_lot::Mutex<HashMap<String, CommandStats>>,
+}
+
+impl CommandUsage {
+    /// Creates a counter without any recorded invocations
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts an invocation of the command with the given qualified name
+    pub(crate) fn record(
+        &self,
+        qualified_name: &str,
+        guild_id: Option<serenity::GuildId>,
+        success: bool,
+    ) {
+        let mut commands = self.commands.lock();
+        let stats = match commands.get_mut(qualified_name) {
+            Some(stats) => stats,
+            None => commands.entry(qualified_name.to_owned()).or_default(),
+        };
+        stats.invocations += 1;
+        if !success {
+            stats.failures += 1;
+        }
+        if let Some(guild_id) = guild_id {
+            *stats.per_guild.entry(guild_id).or_default() += 1;
+        }
+    }
+
+    /// Returns the stats of a command by its [`crate::Command::qualified_name`], or None if it
+    /// hasn't been invoked yet
+    pub fn get(&self, qualified_name: &str) -> Option<CommandStats> {
+        self.commands.lock().get(qualified_name).cloned()
+    }
+
+    /// Returns a copy of the stats of all commands that have been invoked, by qualified name
+    pub fn snapshot(&self) -> HashMap<String, CommandStats> {
+        self.commands.lock().clone()
+    }
+
+    /// Returns the `n` most invoked commands with their invocation counts, most invoked first
+    pub fn top(&self, n: usize) -> Vec<(String, u64)> {
+        self.top_by(n, |stats| stats.invocations)
+    }
+
+    /// Returns the `n` most invoked commands in the given guild with their invocation counts in
+    /// that guild, most invoked first
+    pub fn top_in_guild(&self, guild_id: serenity::GuildId, n: usize) -> Vec<(String, u64)> {
+        self.top_by(n, |stats| {
+            stats.per_guild.get(&guild_id).copied().unwrap_or(0)
+        })
+    }
+
+    /// Returns the `n` commands with the highest nonzero count, ties sorted by name
+    fn top_by(&self, n: usize, count: impl Fn(&CommandStats) -> u64) -> Vec<(String, u64)> {
+        let mut top = self
+            .commands
+            .lock()
+            .iter()
+            .map(|(name, stats)| (name.clone(), count(stats)))
+            .filter(|(_, count)| *count > 0)
+            .collect::<Vec<_>>();
+        top.sort_by(|(a_name, a), (b_name, b)| b.cmp(a).then_with(|| a_name.cmp(b_name)));
+        top.truncate(n);
+        top
+    }
+
+    /// Removes all recorded invocations
+    pub fn reset(&self) {
+        self.commands.lock().clear();
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_command_usage() {
+    let guild_id = serenity::GuildId::new(1);
+    let usage = CommandUsage::new();
+    usage.record("ping", Some(guild_id), true);
+    usage.record("ping", None, false);
+    usage.record("config set", Some(guild_id), true);
+    usage.record("config set", Some(guild_id), true);
+    usage.record("help", None, true);
+
+    let ping = usage.get("ping").unwrap();
+    assert_eq!((ping.invocations, ping.failures), (2, 1));
+    assert_eq!(
+        usage.top(2),
+        [("config set".to_owned(), 2), ("ping".to_owned(), 2)]
+    );
+    assert_eq!(
+        usage.top_in_guild(guild_id, 5),
+        [("config set".to_owned(), 2), ("ping".to_owned(), 1)]
+    );
+
+    usage.reset();
+    assert!(usage.snapshot().is_empty());
+}
@@ -0,0 +1,60 @@
+//! Handlers for message component interactions, routed by custom ID
+
+use crate::{serenity_prelude as serenity, BoxFuture};
+
+/// Context passed to a [`ComponentHandler`]
+pub struct ComponentContext<'a, U, E> {
+    /// Serenity's context, like HTTP or cache
+    pub serenity_context: &'a serenity::Context,
+    /// The component interaction that was routed to the handler
+    pub interaction: &'a serenity::ComponentInteraction,
+    /// Useful if you need the list of commands or the framework options
+    pub framework: crate::FrameworkContext<'a, U, E>,
+    /// Your custom user data
+    pub data: &'a U,
+    /// Rest of the custom ID after the handler's [`ComponentHandler::custom_id_prefix`]. For
+    /// example `42` for the custom ID `role_menu:42` and the prefix `role_menu:`
+    pub custom_id_suffix: &'a str,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl<U, E> Copy for ComponentContext<'_, U, E> {}
+impl<U, E> Clone for ComponentContext<'_, U, E> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// Handles every component interaction whose custom ID starts with a given prefix, see
+/// [`crate::FrameworkOptions::component_handlers`].
+///
+/// Because handlers are matched by custom ID alone, they keep working for messages that were sent
+/// before the bot restarted, and don't need to know which command created the message. Encode any
+/// state you need into the rest of the custom ID.
+#[derive(derivative::Derivative)]
+#[derivative(Debug(bound = ""))]
+pub struct ComponentHandler<U, E> {
+    /// Custom ID prefix that this handler is responsible for, e.g. `role_menu:`
+    pub custom_id_prefix: String,
+    /// Called with every matching component interaction
+    #[derivative(Debug = "ignore")]
+    pub action: for<'a> fn(ComponentContext<'a, U, E>) -> BoxFuture<'a, Result<(), E>>,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl<U, E> ComponentHandler<U, E> {
+    /// Creates a handler for component interactions whose custom ID starts with
+    /// `custom_id_prefix`
+    pub fn new(
+        custom_id_prefix: impl Into<String>,
+        action: for<'a> fn(ComponentContext<'a, U, E>) -> BoxFuture<'a, Result<(), E>>,
+    ) -> Self {
+        Self {
+            custom_id_prefix: custom_id_prefix.into(),
+            action,
+            __non_exhaustive: (),
+        }
+    }
+}
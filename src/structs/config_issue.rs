@@ -0,0 +1,274 @@
+//! Sanity checks of the framework configuration, see [`crate::FrameworkOptions::validate`]
+
+/// A problem in the framework configuration, as found by [`crate::FrameworkOptions::validate`].
+///
+/// Commands are identified by their qualified name, e.g. `settings reset`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigIssue {
+    /// The application commands break Discord's rules and can't be registered
+    #[non_exhaustive]
+    ApplicationCommand {
+        /// The violated rule
+        violation: crate::builtins::CommandViolation,
+    },
+    /// Several prefix commands on the same level share a name or alias. Only the first one can be
+    /// invoked with it
+    #[non_exhaustive]
+    DuplicatePrefixName {
+        /// The name or alias in question
+        name: String,
+        /// Qualified names of the commands that share the name
+        commands: Vec<String>,
+    },
+    /// A command that's only a context menu command has subcommands, which can't be invoked
+    /// because context menus have no hierarchy
+    #[non_exhaustive]
+    SubcommandsOnContextMenuCommand {
+        /// The command in question
+        command: String,
+    },
+    /// A command has options that contradict each other, so it can never run or the option has
+    /// no effect
+    #[non_exhaustive]
+    ConflictingOptions {
+        /// The command in question
+        command: String,
+        /// Which options conflict and why
+        reason: &'static str,
+    },
+    /// There are prefix commands, but no prefix is configured in
+    /// [`crate::PrefixFrameworkOptions`], so they can't be invoked
+    #[non_exhaustive]
+    NoPrefix {},
+    #[doc(hidden)]
+    __NonExhaustive(std::convert::Infallible),
+}
+
+impl ConfigIssue {
+    /// Returns true if the issue breaks the bot, e.g. commands that can't be registered or can
+    /// never run, and false if it's likely unintended but harmless
+    pub fn is_error(&self) -> bool {
+        match self {
+            Self::ApplicationCommand { .. } | Self::ConflictingOptions { .. } => true,
+            Self::DuplicatePrefixName { .. }
+            | Self::SubcommandsOnContextMenuCommand { .. }
+            | Self::NoPrefix {} => false,
+            Self::__NonExhaustive(unreachable) => match *unreachable {},
+        }
+    }
+}
+
+impl std::fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ApplicationCommand { violation } => violation.fmt(f),
+            Self::DuplicatePrefixName { name, commands } => write!(
+                f,
+                "prefix commands {} share the name or alias `{}`",
+                commands
+                    .iter()
+                    .map(|command| format!("`{}`", command))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                name
+            ),
+            Self::SubcommandsOnContextMenuCommand { command } => write!(
+                f,
+                "context menu command `{}` has subcommands, which can't be invoked",
+                command
+            ),
+            Self::ConflictingOptions { command, reason } => write!(f, "`{}`: {}", command, reason),
+            Self::NoPrefix {} => {
+                write!(f, "there are prefix commands, but no prefix is configured")
+            }
+            Self::__NonExhaustive(unreachable) => match *unreachable {},
+        }
+    }
+}
+
+impl std::error::Error for ConfigIssue {}
+
+/// Reports prefix names and aliases that are shared by several of the given sibling commands,
+/// then checks their subcommands
+fn check_prefix_names<U, E>(
+    commands: &[impl AsRef<crate::Command<U, E>>],
+    case_insensitive: bool,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    let mut names = indexmap::IndexMap::<String, Vec<String>>::new();
+    for command in commands {
+        let command: &crate::Command<U, E> = command.as_ref();
+        if command.prefix_action.is_none() && command.subcommands.is_empty() {
+            continue;
+        }
+        for name in std::iter::once(&command.name).chain(&command.aliases) {
+            let name = match case_insensitive {
+                true => name.to_lowercase(),
+                false => name.clone(),
+            };
+            let sharing_commands = names.entry(name).or_default();
+            if !sharing_commands.contains(&command.qualified_name) {
+                sharing_commands.push(command.qualified_name.clone());
+            }
+        }
+    }
+    for (name, commands) in names {
+        if commands.len() > 1 {
+            issues.push(ConfigIssue::DuplicatePrefixName { name, commands });
+        }
+    }
+
+    for command in commands {
+        check_prefix_names(&command.as_ref().subcommands, case_insensitive, issues);
+    }
+}
+
+/// Checks a command and its subcommands for issues that don't depend on other commands
+fn check_command<U, E>(
+    command: &crate::Command<U, E>,
+    options: &crate::FrameworkOptions<U, E>,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    let is_context_menu_only = command.context_menu_action.is_some()
+        && command.prefix_action.is_none()
+        && command.slash_action.is_none();
+    if is_context_menu_only && !command.subcommands.is_empty() {
+        issues.push(ConfigIssue::SubcommandsOnContextMenuCommand {
+            command: command.qualified_name.clone(),
+        });
+    }
+
+    let mut conflict = |reason| {
+        issues.push(ConfigIssue::ConflictingOptions {
+            command: command.qualified_name.clone(),
+            reason,
+        })
+    };
+    if command.guild_only && command.dm_only {
+        conflict("guild_only and dm_only together forbid every channel");
+    }
+    if command.subcommand_required && command.subcommands.is_empty() {
+        conflict("subcommand_required is set, but there are no subcommands");
+    }
+    if command.invoke_on_edit && options.prefix_options.edit_tracker.is_none() {
+        conflict("invoke_on_edit has no effect without an edit tracker");
+    }
+    if command.owners_only && !options.initialize_owners && options.owners.is_empty() {
+        conflict("owners_only is set, but there are no owners");
+    }
+
+    for subcommand in &command.subcommands {
+        check_command(subcommand, options, issues);
+    }
+}
+
+/// Returns whether any of the commands or their subcommands is a prefix command
+fn has_prefix_commands<U, E>(commands: &[impl AsRef<crate::Command<U, E>>]) -> bool {
+    commands
+        .iter()
+        .map(AsRef::as_ref)
+        .any(|command| command.prefix_action.is_some() || has_prefix_commands(&command.subcommands))
+}
+
+impl<U, E> crate::FrameworkOptions<U, E> {
+    /// Checks the configuration for mistakes that would otherwise only show up when registering
+    /// commands or at dispatch, if at all: application commands that break Discord's rules (see
+    /// [`crate::builtins::validate_application_commands`]), duplicate prefix command names and
+    /// aliases, subcommands on context menu commands, contradicting command options and prefix
+    /// commands without a prefix.
+    ///
+    /// Checks [`Self::commands`], so call this before creating the framework, which moves the
+    /// commands out. Subcommands are reported by their qualified names only once those have been
+    /// filled in, see [`crate::set_qualified_names`]. [`crate::FrameworkBuilder::build`] checks
+    /// the commands of the framework and logs the issues found. Use [`ConfigIssue::is_error`] to
+    /// refuse to start on serious issues:
+    ///
+    /// ```rust
+    /// # type Error = Box<dyn std::error::Error + Send + Sync>;
+    /// # let options = poise::FrameworkOptions::<(), Error>::default();
+    /// let issues = options.validate();
+    /// for issue in &issues {
+    ///     eprintln!("{}", issue);
+    /// }
+    /// assert!(!issues.iter().any(|issue| issue.is_error()));
+    /// ```
+    pub fn validate(&self) -> Vec<ConfigIssue> {
+        self.validate_commands(&self.commands)
+    }
+
+    /// Like [`Self::validate`], but checks the given commands instead of [`Self::commands`]
+    pub(crate) fn validate_commands(
+        &self,
+        commands: &[impl AsRef<crate::Command<U, E>>],
+    ) -> Vec<ConfigIssue> {
+        let mut issues = crate::builtins::validate_application_commands(commands)
+            .into_iter()
+            .map(|violation| ConfigIssue::ApplicationCommand { violation })
+            .collect::<Vec<_>>();
+
+        check_prefix_names(
+            commands,
+            self.prefix_options.case_insensitive_commands,
+            &mut issues,
+        );
+        for command in commands {
+            check_command(command.as_ref(), self, &mut issues);
+        }
+
+        let prefix = &self.prefix_options;
+        let has_prefix = prefix.prefix.is_some()
+            || !prefix.additional_prefixes.is_empty()
+            || prefix.dynamic_prefix.is_some()
+            || prefix.stripped_dynamic_prefix.is_some()
+            || prefix.mention_as_prefix;
+        if !has_prefix && has_prefix_commands(commands) {
+            issues.push(ConfigIssue::NoPrefix {});
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_validate() {
+    let options = crate::FrameworkOptions::<(), String> {
+        commands: vec![
+            crate::Command {
+                name: "ping".into(),
+                qualified_name: "ping".into(),
+                prefix_action: Some(|_| Box::pin(async { Ok(()) })),
+                ..Default::default()
+            },
+            crate::Command {
+                name: "pong".into(),
+                qualified_name: "pong".into(),
+                aliases: vec!["Ping".into()],
+                prefix_action: Some(|_| Box::pin(async { Ok(()) })),
+                guild_only: true,
+                dm_only: true,
+                ..Default::default()
+            },
+        ],
+        prefix_options: crate::PrefixFrameworkOptions {
+            case_insensitive_commands: true,
+            mention_as_prefix: false,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert_eq!(
+        options.validate(),
+        [
+            ConfigIssue::DuplicatePrefixName {
+                name: "ping".into(),
+                commands: vec!["ping".into(), "pong".into()],
+            },
+            ConfigIssue::ConflictingOptions {
+                command: "pong".into(),
+                reason: "guild_only and dm_only together forbid every channel",
+            },
+            ConfigIssue::NoPrefix {},
+        ]
+    );
+}
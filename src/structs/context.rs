@@ -160,6 +160,24 @@ context_methods! {
         crate::send_reply(self, builder).await
     }
 
+    /// Shorthand of [`crate::send_split_reply`]
+    ///
+    /// Note: panics when called in an autocomplete context!
+    await (send_split self builder)
+    (pub async fn send_split(
+        self,
+        builder: crate::CreateReply,
+    ) -> Result<Vec<crate::ReplyHandle<'a>>, serenity::Error>) {
+        crate::send_split_reply(self, builder).await
+    }
+
+    /// Creates a reply that is progressively edited as content is pushed to it. See
+    /// [`crate::StreamingReply`]
+    (stream_reply self)
+    (pub fn stream_reply(self) -> crate::StreamingReply<'a, U, E>) {
+        crate::StreamingReply::new(self)
+    }
+
     /// Return the stored [`serenity::Context`] within the underlying context type.
     (serenity_context self)
     (pub fn serenity_context(self) -> &'a serenity::Context) {
@@ -290,6 +308,59 @@ context_methods! {
         }
     }
 
+    /// Returns when the framework received the gateway event that triggered this command
+    /// invocation. Unlike [`Self::created_at`], this is a local monotonic clock reading, so it's
+    /// suited for measuring latency.
+    (received_at self)
+    (pub fn received_at(self) -> std::time::Instant) {
+        self.timing().received_at()
+    }
+
+    /// Returns how much time has passed since the framework received the gateway event that
+    /// triggered this command invocation.
+    (elapsed self)
+    (pub fn elapsed(self) -> std::time::Duration) {
+        self.received_at().elapsed()
+    }
+
+    /// Returns how long the command itself ran, not including checks and
+    /// [`crate::FrameworkOptions::pre_command`]. Only available once the command has returned,
+    /// e.g. in [`crate::FrameworkOptions::post_command`] or in the error handler.
+    ///
+    /// ```rust
+    /// # type Error = Box<dyn std::error::Error + Send + Sync>;
+    /// poise::FrameworkOptions::<(), Error> {
+    ///     post_command: |ctx| {
+    ///         Box::pin(async move {
+    ///             let execution_time = ctx.execution_time().unwrap_or_default();
+    ///             if execution_time > std::time::Duration::from_secs(2) {
+    ///                 println!("{} took {:?}", ctx.command().qualified_name, execution_time);
+    ///             }
+    ///         })
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// ```
+    (execution_time self)
+    (pub fn execution_time(self) -> Option<std::time::Duration>) {
+        self.timing().execution_time()
+    }
+
+    /// Returns whether this command was invoked through a prefix, as a slash command or through
+    /// a context menu
+    (invocation_kind self)
+    (pub fn invocation_kind(self) -> crate::InvocationKind) {
+        match self {
+            Self::Prefix(_) => crate::InvocationKind::Prefix,
+            Self::Application(ctx) => match ctx.interaction.data.kind {
+                serenity::CommandType::User | serenity::CommandType::Message => {
+                    crate::InvocationKind::ContextMenu
+                }
+                _ => crate::InvocationKind::Slash,
+            },
+        }
+    }
+
     /// Get the author of the command message or application command.
     (author self)
     (pub fn author(self) -> &'a serenity::User) {
@@ -340,6 +411,17 @@ context_methods! {
         }
     }
 
+    /// Returns a clickable mention of the slash command with the given qualified name, for
+    /// example `ctx.command_mention("settings reset")`. See [`crate::Command::mention`].
+    (command_mention self name)
+    (pub fn command_mention(self, name: &str) -> Option<String>) {
+        let qualified_name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+        self.framework()
+            .commands()
+            .command_by_qualified_name(&qualified_name)?
+            .mention()
+    }
+
     /// Returns a reference to the command.
     (command self)
     (pub fn command(self) -> &'a crate::Command<U, E>) {
@@ -477,6 +559,20 @@ context_methods! {
         .ok()
     }
 
+    /// Returns handles to all messages sent in response to this command invocation so far, in the
+    /// order they were sent. Useful to edit or delete all messages of a multi-message response.
+    ///
+    /// Messages sent directly via serenity instead of [`Self::send`] and friends aren't included.
+    (replies self)
+    (pub fn replies(self) -> Vec<crate::ReplyHandle<'a>>) {
+        match self {
+            Context::Application(ctx) => ctx
+                .replies
+                .handles(&ctx.serenity_context.http, Some(ctx.interaction)),
+            Context::Prefix(ctx) => ctx.replies.handles(&ctx.serenity_context.http, None),
+        }
+    }
+
     /// If available, returns the locale (selected language) of the invoking user
     (locale self)
     (pub fn locale(self) -> Option<&'a str>) {
@@ -486,6 +582,56 @@ context_methods! {
         }
     }
 
+    /// Returns the locale that responses should be in: the one returned by
+    /// [`crate::FrameworkOptions::locale_provider`] if set, otherwise [`Self::locale`]
+    (effective_locale self)
+    (pub fn effective_locale(self) -> Option<Cow<'a, str>>) {
+        if let Some(locale_provider) = self.framework().options().locale_provider {
+            if let Some(locale) = locale_provider(self) {
+                return Some(Cow::Owned(locale));
+            }
+        }
+        self.locale().map(Cow::Borrowed)
+    }
+
+    /// Returns where the command was invoked: in a guild, in a DM with the bot, or in a group DM or
+    /// a DM between other users (only possible for user-installed apps).
+    ///
+    /// Prefix commands are always invoked in a guild or a DM with the bot. Returns None if Discord
+    /// didn't send this information.
+    (interaction_context self)
+    (pub fn interaction_context(self) -> Option<serenity::InteractionContext>) {
+        match self {
+            Context::Application(ctx) => ctx.interaction.context,
+            Context::Prefix(ctx) => Some(match ctx.msg.guild_id {
+                Some(_) => serenity::InteractionContext::Guild,
+                None => serenity::InteractionContext::BotDm,
+            }),
+        }
+    }
+
+    /// Returns the installations that authorized this command: the guild and/or the user that
+    /// installed the app. Empty for prefix commands.
+    (authorizing_integration_owners self)
+    (pub fn authorizing_integration_owners(self) -> &'a [serenity::AuthorizingIntegrationOwner]) {
+        match self {
+            Context::Application(ctx) => &ctx.interaction.authorizing_integration_owners.0,
+            Context::Prefix(_) => &[],
+        }
+    }
+
+    /// Returns true if the command is only available here because the invoking user installed the
+    /// app, i.e. the bot isn't a member of the guild or channel. In that case, the bot can't rely
+    /// on the cache or on its own permissions.
+    (is_user_install self)
+    (pub fn is_user_install(self) -> bool) {
+        let owners = self.authorizing_integration_owners();
+        !owners.is_empty()
+            && owners
+                .iter()
+                .all(|owner| matches!(owner, serenity::AuthorizingIntegrationOwner::UserInstall(_)))
+    }
+
     /// Builds a [`crate::CreateReply`] by combining the builder closure with the defaults that were
     /// pre-configured in poise.
     ///
@@ -537,6 +683,51 @@ context_methods! {
     }
 }
 
+impl<U, E> Context<'_, U, E> {
+    /// Returns the value of type `T` from [`crate::FrameworkOptions::data_map`], if any. Unlike
+    /// [`Self::data`], this lets plugins and modules keep their own state without being part of
+    /// the user data type
+    pub fn data_get<T: std::any::Any + Send + Sync>(self) -> Option<std::sync::Arc<T>> {
+        self.framework().options().data_map.get::<T>()
+    }
+}
+
+#[cfg(feature = "i18n")]
+impl<U, E> Context<'_, U, E> {
+    /// Translates a message from [`crate::FrameworkOptions::translations`] into the locale of the
+    /// invoking user (see [`Self::effective_locale`]), falling back to the fallback locale of the
+    /// translations.
+    ///
+    /// `key` is a Fluent message ID, optionally followed by `.attribute`. If the message can't be
+    /// found or no translations are configured, a warning is logged and the key itself is
+    /// returned.
+    ///
+    /// ```rust,no_run
+    /// # async fn _f(ctx: poise::Context<'_, (), ()>) {
+    /// let mut args = poise::i18n::FluentArgs::new();
+    /// args.set("name", ctx.author().name.as_str());
+    /// let greeting = ctx.t("greeting", Some(&args));
+    /// # }
+    /// ```
+    pub fn t(self, key: &str, args: Option<&crate::i18n::FluentArgs<'_>>) -> String {
+        let (id, attribute) = match key.split_once('.') {
+            Some((id, attribute)) => (id, Some(attribute)),
+            None => (key, None),
+        };
+        self.framework()
+            .options()
+            .translations
+            .as_ref()
+            .and_then(|translations| {
+                translations.get(self.effective_locale().as_deref(), id, attribute, args)
+            })
+            .unwrap_or_else(|| {
+                tracing::warn!("unknown translation key `{}`", key);
+                key.to_owned()
+            })
+    }
+}
+
 impl<'a, U, E> Context<'a, U, E> {
     /// Actual implementation of rerun() that returns `FrameworkError` for implementation convenience
     async fn rerun_inner(self) -> Result<(), crate::FrameworkError<'a, U, E>> {
@@ -600,6 +791,25 @@ impl<'a, U, E> Context<'a, U, E> {
             Context::Prefix(ctx) => ctx.invocation_data,
         }
     }
+
+    /// Returns the timing record of this invocation
+    fn timing(self) -> &'a crate::InvocationTiming {
+        match self {
+            Context::Application(ctx) => ctx.timing,
+            Context::Prefix(ctx) => ctx.timing,
+        }
+    }
+
+    /// Summarizes the invocation for [`crate::FrameworkOptions::post_command_result`], once the
+    /// command has returned
+    pub(crate) fn outcome(self, success: bool) -> crate::CommandOutcome {
+        crate::CommandOutcome {
+            success,
+            duration: self.execution_time().unwrap_or_default(),
+            kind: self.invocation_kind(),
+            __non_exhaustive: (),
+        }
+    }
 }
 
 /// Forwards for serenity::Context's impls. With these, poise's Context types can be passed in as-is
@@ -0,0 +1,101 @@
+//! Type-keyed storage for state besides the user data, see [`DataMap`]
+
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// A concurrent map that stores at most one value per type, available to commands through
+/// [`crate::Context::data_get`] once set up in [`crate::FrameworkOptions::data_map`].
+///
+/// This supplements the user data (`U`): plugins and separately compiled modules can keep their
+/// own state here without the bot having to include it in its user data type. Values are shared
+/// as [`Arc`]s; use interior mutability (e.g. a `Mutex`) for state that changes.
+///
+/// ```rust
+/// struct MusicQueue(std::sync::Mutex<Vec<String>>);
+///
+/// let options = poise::FrameworkOptions::<(), poise::serenity_prelude::Error>::default();
+/// options.data_map.insert(MusicQueue(Default::default()));
+///
+/// // Later, e.g. in a command with `ctx.data_get::<MusicQueue>()`
+/// let queue = options.data_map.get::<MusicQueue>().unwrap();
+/// queue.0.lock().unwrap().push("never gonna give you up".into());
+/// ```
+#[derive(Default)]
+pub struct DataMap {
+    /// Values with their type name, for debugging
+    values: parking_lot::RwLock<HashMap<TypeId, (&'static str, Arc<dyn Any + Send + Sync>)>>,
+}
+
+impl std::fmt::Debug for DataMap {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let values = self.values.read();
+        f.debug_set()
+            .entries(values.values().map(|(type_name, _)| type_name))
+            .finish()
+    }
+}
+
+/// Casts a value taken from the map back to its type. The map only stores values under their
+/// own [`TypeId`], so this can't fail
+fn downcast<T: Any + Send + Sync>(value: Arc<dyn Any + Send + Sync>) -> Arc<T> {
+    value
+        .downcast()
+        .unwrap_or_else(|_| unreachable!("values are stored under their own type ID"))
+}
+
+impl DataMap {
+    /// Creates an empty map
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores a value, replacing and returning the existing value of the same type
+    pub fn insert<T: Any + Send + Sync>(&self, value: T) -> Option<Arc<T>> {
+        let value = (std::any::type_name::<T>(), Arc::new(value) as _);
+        let previous = self.values.write().insert(TypeId::of::<T>(), value);
+        previous.map(|(_, previous)| downcast(previous))
+    }
+
+    /// Returns the value of the given type, if any
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let values = self.values.read();
+        let (_, value) = values.get(&TypeId::of::<T>())?;
+        Some(downcast(value.clone()))
+    }
+
+    /// Returns the value of the given type, storing the result of `init` first if there's none
+    pub fn get_or_insert_with<T: Any + Send + Sync>(&self, init: impl FnOnce() -> T) -> Arc<T> {
+        let mut values = self.values.write();
+        let (_, value) = values
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| (std::any::type_name::<T>(), Arc::new(init())));
+        downcast(value.clone())
+    }
+
+    /// Removes and returns the value of the given type, if any
+    pub fn remove<T: Any + Send + Sync>(&self) -> Option<Arc<T>> {
+        let (_, value) = self.values.write().remove(&TypeId::of::<T>())?;
+        Some(downcast(value))
+    }
+
+    /// Returns whether there's a value of the given type
+    pub fn contains<T: Any + Send + Sync>(&self) -> bool {
+        self.values.read().contains_key(&TypeId::of::<T>())
+    }
+}
+
+#[cfg(test)]
+#[test]
+fn test_data_map() {
+    let map = DataMap::new();
+    assert_eq!(map.insert(5_u32), None);
+    assert_eq!(map.insert(6_u32).as_deref(), Some(&5));
+    assert_eq!(map.get::<u32>().as_deref(), Some(&6));
+    assert_eq!(map.get::<u64>(), None);
+    assert_eq!(*map.get_or_insert_with(|| String::from("a")), "a");
+    assert_eq!(*map.get_or_insert_with(|| String::from("b")), "a");
+    assert!(map.contains::<String>());
+    assert_eq!(map.remove::<String>().as_deref().map(|s| &**s), Some("a"));
+    assert!(!map.contains::<String>());
+}
@@ -0,0 +1,167 @@
+//! Localizable texts of the user-facing errors in [`crate::builtins::on_error`]
+
+use std::collections::HashMap;
+
+/// Texts that [`crate::builtins::on_error`] responds with for framework errors caused by the
+/// invoking user. Placeholders in curly braces are filled in by the error handler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorMessageSet {
+    /// [`crate::FrameworkError::CooldownHit`]. Placeholder: `{seconds}`
+    pub cooldown_hit: String,
+    /// [`crate::FrameworkError::AlreadyRunning`] with [`crate::ConcurrencyScope::User`]
+    pub already_running_user: String,
+    /// [`crate::FrameworkError::AlreadyRunning`] with any other scope
+    pub already_running: String,
+    /// [`crate::FrameworkError::MissingBotPermissions`]. Placeholder: `{permissions}`
+    pub missing_bot_permissions: String,
+    /// [`crate::FrameworkError::MissingUserPermissions`]. Placeholders: `{command}`,
+    /// `{permissions}`
+    pub missing_user_permissions: String,
+    /// [`crate::FrameworkError::MissingUserPermissions`] if the user permissions couldn't be
+    /// retrieved. Placeholder: `{command}`
+    pub missing_user_permissions_unknown: String,
+    /// [`crate::FrameworkError::NotAnOwner`]
+    pub not_an_owner: String,
+    /// [`crate::FrameworkError::GuildOnly`]
+    pub guild_only: String,
+    /// [`crate::FrameworkError::DmOnly`]
+    pub dm_only: String,
+    /// [`crate::FrameworkError::NsfwOnly`]
+    pub nsfw_only: String,
+    /// [`crate::FrameworkError::CommandDisabled`]
+    pub command_disabled: String,
+    /// [`crate::FrameworkError::SubcommandRequired`]. Placeholder: `{subcommands}`
+    pub subcommand_required: String,
+    /// [`crate::FrameworkError::ArgumentParse`] if the input and the parameter are known.
+    /// Placeholders: `{parameter}`, `{input}`, `{error}`
+    pub argument_parse: String,
+    /// [`crate::FrameworkError::ArgumentParse`] if only the input is known. Placeholders:
+    /// `{input}`, `{error}`
+    pub argument_parse_unknown_parameter: String,
+    /// Shown below [`crate::FrameworkError::ArgumentParse`] errors if the command has no
+    /// [`crate::Command::help_text`]
+    pub invalid_arguments_help: String,
+    /// [`crate::FrameworkError::UnknownCommand`] if there's a similar command. Placeholders:
+    /// `{command}`, `{suggestion}`, both including the prefix.
+    ///
+    /// The invoking user's locale is unknown at this point, so only [`ErrorMessages::default`]
+    /// is used for this text.
+    pub unknown_command_suggestion: String,
+    /// [`crate::FrameworkError::CommandPanic`]
+    pub internal_error: String,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl Default for ErrorMessageSet {
+    fn default() -> Self {
+        Self {
+            cooldown_hit: "You're too fast. Please wait {seconds} seconds before retrying".into(),
+            already_running_user:
+                "You're already running this command. Please wait for it to finish".into(),
+            already_running: "This command is already running. Please wait for it to finish".into(),
+            missing_bot_permissions:
+                "Command cannot be executed because the bot is lacking permissions: {permissions}"
+                    .into(),
+            missing_user_permissions: "You're lacking permissions for `{command}`: {permissions}"
+                .into(),
+            missing_user_permissions_unknown:
+                "You may be lacking permissions for `{command}`. Not executing for safety".into(),
+            not_an_owner: "Only bot owners can call this command".into(),
+            guild_only: "You cannot run this command in DMs.".into(),
+            dm_only: "You cannot run this command outside DMs.".into(),
+            nsfw_only: "You cannot run this command outside NSFW channels.".into(),
+            command_disabled: "This command is currently disabled.".into(),
+            subcommand_required: "You must specify one of the following subcommands: {subcommands}"
+                .into(),
+            argument_parse: "Cannot parse `{parameter}` from `{input}`: {error}".into(),
+            argument_parse_unknown_parameter: "Cannot parse `{input}` as argument: {error}".into(),
+            invalid_arguments_help: "Please check the help menu for usage information".into(),
+            unknown_command_suggestion: "Unknown command `{command}`, did you mean `{suggestion}`?"
+                .into(),
+            internal_error: "An unexpected internal error has occurred.".into(),
+            __non_exhaustive: (),
+        }
+    }
+}
+
+/// Message catalog for the user-facing errors in [`crate::builtins::on_error`], keyed by locale.
+/// See [`crate::FrameworkOptions::error_messages`].
+///
+/// ```rust
+/// let mut error_messages = poise::ErrorMessages::default();
+/// error_messages.localized.insert(
+///     "de".into(),
+///     poise::ErrorMessageSet {
+///         guild_only: "Dieser Befehl funktioniert nicht in DMs.".into(),
+///         ..Default::default()
+///     },
+/// );
+/// assert_eq!(
+///     error_messages.get(Some("de")).guild_only,
+///     "Dieser Befehl funktioniert nicht in DMs."
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ErrorMessages {
+    /// Used if the locale is unknown (prefix commands without
+    /// [`crate::FrameworkOptions::locale_provider`]) or has no entry in [`Self::localized`]
+    pub default: ErrorMessageSet,
+    /// Messages per Discord locale, like `de` or `en-US`
+    pub localized: HashMap<String, ErrorMessageSet>,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl ErrorMessages {
+    /// Returns the messages for the given locale. Falls back to the language without region
+    /// (`es` for `es-ES`) and then to [`Self::default`]
+    pub fn get(&self, locale: Option<&str>) -> &ErrorMessageSet {
+        let Some(locale) = locale else {
+            return &self.default;
+        };
+        let language = locale.split('-').next().unwrap_or(locale);
+        self.localized
+            .get(locale)
+            .or_else(|| self.localized.get(language))
+            .unwrap_or(&self.default)
+    }
+}
+
+/// Replaces `{name}` placeholders in the template with the given values
+pub(crate) fn fill_placeholders(template: &str, values: &[(&str, &str)]) -> String {
+    let mut text = template.to_owned();
+    for (name, value) in values {
+        text = text.replace(&format!("{{{}}}", name), value);
+    }
+    text
+}
+
+#[cfg(test)]
+#[test]
+fn test_error_messages() {
+    let mut error_messages = ErrorMessages::default();
+    error_messages.localized.insert(
+        "es-ES".into(),
+        ErrorMessageSet {
+            dm_only: "es-ES".into(),
+            ..Default::default()
+        },
+    );
+    error_messages.localized.insert(
+        "es".into(),
+        ErrorMessageSet {
+            dm_only: "es".into(),
+            ..Default::default()
+        },
+    );
+
+    assert_eq!(error_messages.get(Some("es-ES")).dm_only, "es-ES");
+    assert_eq!(error_messages.get(Some("es-419")).dm_only, "es");
+    assert_eq!(error_messages.get(Some("de")), &error_messages.default);
+    assert_eq!(error_messages.get(None), &error_messages.default);
+    assert_eq!(
+        fill_placeholders("wait {seconds}s, {seconds}!", &[("seconds", "5")]),
+        "wait 5s, 5!"
+    );
+}
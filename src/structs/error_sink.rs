@@ -0,0 +1,209 @@
+//! Forwarding of command errors to Discord, see [`crate::FrameworkOptions::error_sink`]
+
+use crate::serenity_prelude as serenity;
+
+/// Where to forward command errors and panics to, so that they show up on Discord and not only
+/// in the logs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorSink {
+    /// Sends error reports into this channel
+    #[non_exhaustive]
+    Channel {
+        /// The channel to send reports into. The bot needs permission to send embeds there
+        channel_id: serenity::ChannelId,
+    },
+    /// Executes this webhook with error reports
+    #[non_exhaustive]
+    Webhook {
+        /// ID of the webhook
+        webhook_id: serenity::WebhookId,
+        /// Token of the webhook
+        token: String,
+    },
+    #[doc(hidden)]
+    __NonExhaustive(std::convert::Infallible),
+}
+
+impl ErrorSink {
+    /// Sends error reports into the given channel
+    pub fn channel(channel_id: serenity::ChannelId) -> Self {
+        Self::Channel { channel_id }
+    }
+
+    /// Executes the webhook with the given URL, like
+    /// `https://discord.com/api/webhooks/{id}/{token}`, with error reports.
+    ///
+    /// Returns None if the URL isn't a Discord webhook URL. The URL is only parsed here, so no
+    /// request is made to look the webhook up.
+    pub fn webhook(url: &str) -> Option<Self> {
+        let (webhook_id, token) = parse_webhook_url(url)?;
+        Some(Self::Webhook {
+            webhook_id,
+            token: token.to_owned(),
+        })
+    }
+
+    /// Forwards the error to this sink, together with the invocation context: guild, user,
+    /// command and arguments.
+    ///
+    /// Only [`crate::FrameworkError::Command`] and [`crate::FrameworkError::CommandPanic`] are
+    /// reported; other errors are ignored. Failures to send the report are logged.
+    ///
+    /// [`crate::FrameworkError::handle`] calls this automatically for
+    /// [`crate::FrameworkOptions::error_sink`], whichever error handler is configured. Call it
+    /// yourself only for errors that you pass to an error handler directly.
+    ///
+    /// The returned future doesn't borrow the error, so it can be awaited after the error has
+    /// been moved elsewhere.
+    pub fn report<U, E: std::fmt::Display>(
+        &self,
+        error: &crate::FrameworkError<'_, U, E>,
+    ) -> impl std::future::Future<Output = ()> + Send + '_ {
+        self.report_with(error, |error| error.to_string())
+    }
+
+    /// Like [`Self::report`], but renders command errors with the given function
+    pub(crate) fn report_with<U, E>(
+        &self,
+        error: &crate::FrameworkError<'_, U, E>,
+        format_error: fn(&E) -> String,
+    ) -> impl std::future::Future<Output = ()> + Send + '_ {
+        let report = create_report(error, format_error);
+        async move {
+            if let Some((http, embed)) = report {
+                self.send(&http, embed).await;
+            }
+        }
+    }
+
+    /// Sends a report created by [`create_report`], logging failures
+    async fn send(&self, http: &serenity::Http, embed: serenity::CreateEmbed) {
+        let result = match self {
+            Self::Channel { channel_id } => channel_id
+                .send_message(http, serenity::CreateMessage::new().embed(embed))
+                .await
+                .map(drop),
+            Self::Webhook { webhook_id, token } => http
+                .execute_webhook(
+                    *webhook_id,
+                    None,
+                    token,
+                    false,
+                    Vec::new(),
+                    &serenity::ExecuteWebhook::new().embed(embed),
+                )
+                .await
+                .map(drop),
+            Self::__NonExhaustive(unreachable) => match *unreachable {},
+        };
+        if let Err(e) = result {
+            tracing::warn!("failed to report error to error sink: {}", e);
+        }
+    }
+}
+
+/// Extracts webhook ID and token from a URL like `https://discord.com/api/webhooks/{id}/{token}`
+fn parse_webhook_url(url: &str) -> Option<(serenity::WebhookId, &str)> {
+    let (_, path) = url.split_once("/webhooks/")?;
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+    let (webhook_id, token) = path.split_once('/')?;
+    let token = token.trim_end_matches('/');
+    if token.is_empty() || token.contains('/') {
+        return None;
+    }
+    let webhook_id = webhook_id.parse::<u64>().ok().filter(|&id| id != 0)?;
+    Some((serenity::WebhookId::new(webhook_id), token))
+}
+
+/// Renders text so that it can be put into a code block: breaks up backticks with zero-width
+/// spaces, so that they can't close the block early
+fn escape_code_block(text: &str) -> String {
+    text.replace('`', "`\u{200B}")
+}
+
+/// Renders the error report embed, or returns None if the error shouldn't be reported
+fn create_report<U, E>(
+    error: &crate::FrameworkError<'_, U, E>,
+    format_error: fn(&E) -> String,
+) -> Option<(std::sync::Arc<serenity::Http>, serenity::CreateEmbed)> {
+    let (ctx, title, text) = match error {
+        crate::FrameworkError::Command { error, ctx } => {
+            (*ctx, "Command error", format_error(error))
+        }
+        crate::FrameworkError::CommandPanic { payload, ctx } => (
+            *ctx,
+            "Command panic",
+            payload
+                .clone()
+                .unwrap_or_else(|| "<non-string payload>".to_owned()),
+        ),
+        _ => return None,
+    };
+
+    let guild = match ctx.guild_id() {
+        Some(guild_id) => guild_id.to_string(),
+        None => "DM".to_owned(),
+    };
+    let embed = serenity::CreateEmbed::new()
+        .title(title)
+        .color((237, 66, 69))
+        // Discord limits embed descriptions to 4096 characters
+        .description(format!(
+            "```\n{}\n```",
+            escape_code_block(&text)
+                .chars()
+                .take(4000)
+                .collect::<String>()
+        ))
+        .field(
+            "Command",
+            format!("`{}`", ctx.command().qualified_name),
+            true,
+        )
+        .field(
+            "User",
+            format!("{} ({})", ctx.author().name, ctx.author().id),
+            true,
+        )
+        .field("Guild", guild, true)
+        .field(
+            "Invocation",
+            format!(
+                "```\n{}\n```",
+                escape_code_block(&ctx.invocation_string())
+                    .chars()
+                    .take(1000)
+                    .collect::<String>()
+            ),
+            false,
+        )
+        .timestamp(serenity::Timestamp::now());
+
+    Some((ctx.serenity_context().http.clone(), embed))
+}
+
+#[cfg(test)]
+#[test]
+fn test_parse_webhook_url() {
+    assert_eq!(
+        parse_webhook_url("https://discord.com/api/webhooks/123/abc-DEF_ghi"),
+        Some((serenity::WebhookId::new(123), "abc-DEF_ghi")),
+    );
+    assert_eq!(
+        parse_webhook_url("https://canary.discord.com/api/v10/webhooks/123/abc/?wait=true"),
+        Some((serenity::WebhookId::new(123), "abc")),
+    );
+    assert_eq!(
+        parse_webhook_url("https://discord.com/api/webhooks/123"),
+        None
+    );
+    assert_eq!(
+        parse_webhook_url("https://discord.com/api/webhooks/0/abc"),
+        None
+    );
+    assert_eq!(
+        parse_webhook_url("https://discord.com/api/webhooks/x/abc"),
+        None
+    );
+    assert_eq!(parse_webhook_url("https://example.com/"), None);
+}
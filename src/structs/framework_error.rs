@@ -74,6 +74,9 @@ pub enum FrameworkError<'a, U, E> {
         error: Box<dyn std::error::Error + Send + Sync>,
         /// If applicable, the input on which parsing failed
         input: Option<String>,
+        /// Name of the parameter that failed to parse, if known. See [`crate::Command::usage`] to
+        /// show it in context
+        parameter: Option<String>,
         /// General context
         ctx: crate::Context<'a, U, E>,
     },
@@ -97,6 +100,15 @@ pub enum FrameworkError<'a, U, E> {
         /// General context
         ctx: crate::Context<'a, U, E>,
     },
+    /// Command was invoked while too many invocations of it were already running, see
+    /// [`crate::Command::max_concurrent`]
+    #[non_exhaustive]
+    AlreadyRunning {
+        /// Which limit was reached
+        scope: crate::ConcurrencyScope,
+        /// General context
+        ctx: crate::Context<'a, U, E>,
+    },
     /// Command was invoked but the bot is lacking the permissions specified in
     /// [`crate::Command::required_permissions`]
     #[non_exhaustive]
@@ -140,12 +152,21 @@ pub enum FrameworkError<'a, U, E> {
         /// General context
         ctx: crate::Context<'a, U, E>,
     },
+    /// The command or one of its parent commands is disabled, see [`crate::Command::enabled`]
+    #[non_exhaustive]
+    CommandDisabled {
+        /// General context
+        ctx: crate::Context<'a, U, E>,
+    },
     /// Provided pre-command check either errored, or returned false, so command execution aborted
     #[non_exhaustive]
     CommandCheckFailed {
         /// If execution wasn't aborted because of an error but because it successfully returned
         /// false, this field is None
         error: Option<E>,
+        /// Message for the user from [`crate::Command::check_failure_messages`], if the failed
+        /// check has one
+        message: Option<&'a str>,
         /// General context
         ctx: crate::Context<'a, U, E>,
     },
@@ -183,6 +204,9 @@ pub enum FrameworkError<'a, U, E> {
         invocation_data: &'a tokio::sync::Mutex<Box<dyn std::any::Any + Send + Sync>>,
         /// Which event triggered the message parsing routine
         trigger: crate::MessageDispatchTrigger,
+        /// Name of a command that is similar to the unrecognized one, if
+        /// [`crate::PrefixFrameworkOptions::suggest_unknown_commands`] is set and there is one
+        suggestion: Option<String>,
     },
     /// The command name from the interaction is unrecognized
     #[non_exhaustive]
@@ -210,6 +234,29 @@ pub enum FrameworkError<'a, U, E> {
         /// The interaction in question
         msg: &'a serenity::Message,
     },
+    /// User code threw an error in a [`crate::ScheduledTask`]
+    #[non_exhaustive]
+    ScheduledTask {
+        /// The error thrown by user code
+        error: E,
+        /// [`crate::ScheduledTask::name`] of the task
+        task_name: &'a str,
+        /// Serenity's Context
+        #[derivative(Debug = "ignore")]
+        ctx: &'a serenity::Context,
+        /// Framework context
+        #[derivative(Debug = "ignore")]
+        framework: crate::FrameworkContext<'a, U, E>,
+    },
+    /// User code threw an error in a [`crate::ComponentHandler`]
+    #[non_exhaustive]
+    ComponentHandler {
+        /// The error thrown by user code
+        error: E,
+        /// Context of the component interaction
+        #[derivative(Debug = "ignore")]
+        ctx: crate::ComponentContext<'a, U, E>,
+    },
     // #[non_exhaustive] forbids struct update syntax for ?? reason
     #[doc(hidden)]
     __NonExhaustive(std::convert::Infallible),
@@ -227,17 +274,21 @@ impl<'a, U, E> FrameworkError<'a, U, E> {
             Self::ArgumentParse { ctx, .. } => ctx.serenity_context(),
             Self::CommandStructureMismatch { ctx, .. } => ctx.serenity_context,
             Self::CooldownHit { ctx, .. } => ctx.serenity_context(),
+            Self::AlreadyRunning { ctx, .. } => ctx.serenity_context(),
             Self::MissingBotPermissions { ctx, .. } => ctx.serenity_context(),
             Self::MissingUserPermissions { ctx, .. } => ctx.serenity_context(),
             Self::NotAnOwner { ctx, .. } => ctx.serenity_context(),
             Self::GuildOnly { ctx, .. } => ctx.serenity_context(),
             Self::DmOnly { ctx, .. } => ctx.serenity_context(),
             Self::NsfwOnly { ctx, .. } => ctx.serenity_context(),
+            Self::CommandDisabled { ctx, .. } => ctx.serenity_context(),
             Self::CommandCheckFailed { ctx, .. } => ctx.serenity_context(),
             Self::DynamicPrefix { ctx, .. } => ctx.serenity_context,
             Self::UnknownCommand { ctx, .. } => ctx,
             Self::UnknownInteraction { ctx, .. } => ctx,
             Self::NonCommandMessage { ctx, .. } => ctx,
+            Self::ComponentHandler { ctx, .. } => ctx.serenity_context,
+            Self::ScheduledTask { ctx, .. } => ctx,
             Self::__NonExhaustive(unreachable) => match unreachable {},
         }
     }
@@ -251,33 +302,180 @@ impl<'a, U, E> FrameworkError<'a, U, E> {
             Self::ArgumentParse { ctx, .. } => ctx,
             Self::CommandStructureMismatch { ctx, .. } => crate::Context::Application(ctx),
             Self::CooldownHit { ctx, .. } => ctx,
+            Self::AlreadyRunning { ctx, .. } => ctx,
             Self::MissingBotPermissions { ctx, .. } => ctx,
             Self::MissingUserPermissions { ctx, .. } => ctx,
             Self::NotAnOwner { ctx, .. } => ctx,
             Self::GuildOnly { ctx, .. } => ctx,
             Self::DmOnly { ctx, .. } => ctx,
             Self::NsfwOnly { ctx, .. } => ctx,
+            Self::CommandDisabled { ctx, .. } => ctx,
             Self::CommandCheckFailed { ctx, .. } => ctx,
             Self::Setup { .. }
             | Self::EventHandler { .. }
             | Self::UnknownCommand { .. }
             | Self::UnknownInteraction { .. }
             | Self::NonCommandMessage { .. }
+            | Self::ComponentHandler { .. }
+            | Self::ScheduledTask { .. }
             | Self::DynamicPrefix { .. } => return None,
             Self::__NonExhaustive(unreachable) => match unreachable {},
         })
     }
 
-    /// Calls the appropriate `on_error` function (command-specific or global) with this error
+    /// Returns where and by whom the invocation that caused this error was triggered, as far as
+    /// known. Unlike [`Self::ctx`], this works for all variants, including unknown commands and
+    /// component interactions.
+    pub fn invocation_details(&self) -> InvocationDetails {
+        match self {
+            Self::Command { ctx, .. }
+            | Self::SubcommandRequired { ctx }
+            | Self::CommandPanic { ctx, .. }
+            | Self::ArgumentParse { ctx, .. }
+            | Self::CooldownHit { ctx, .. }
+            | Self::AlreadyRunning { ctx, .. }
+            | Self::MissingBotPermissions { ctx, .. }
+            | Self::MissingUserPermissions { ctx, .. }
+            | Self::NotAnOwner { ctx, .. }
+            | Self::GuildOnly { ctx, .. }
+            | Self::DmOnly { ctx, .. }
+            | Self::NsfwOnly { ctx, .. }
+            | Self::CommandDisabled { ctx, .. }
+            | Self::CommandCheckFailed { ctx, .. } => InvocationDetails::from_context(*ctx),
+            Self::CommandStructureMismatch { ctx, .. } => {
+                InvocationDetails::from_context(crate::Context::Application(*ctx))
+            }
+            Self::DynamicPrefix { msg, .. }
+            | Self::UnknownCommand { msg, .. }
+            | Self::NonCommandMessage { msg, .. } => InvocationDetails::from_message(msg),
+            Self::UnknownInteraction { interaction, .. } => InvocationDetails {
+                guild_id: interaction.guild_id,
+                channel_id: Some(interaction.channel_id),
+                user_id: Some(interaction.user.id),
+                command_name: None,
+                invocation_string: Some(format!("/{}", interaction.data.name)),
+                __non_exhaustive: (),
+            },
+            Self::ComponentHandler { ctx, .. } => InvocationDetails {
+                guild_id: ctx.interaction.guild_id,
+                channel_id: Some(ctx.interaction.channel_id),
+                user_id: Some(ctx.interaction.user.id),
+                command_name: None,
+                invocation_string: Some(ctx.interaction.data.custom_id.clone()),
+                __non_exhaustive: (),
+            },
+            Self::Setup { .. } | Self::EventHandler { .. } | Self::ScheduledTask { .. } => {
+                InvocationDetails::default()
+            }
+            Self::__NonExhaustive(unreachable) => match *unreachable {},
+        }
+    }
+
+    /// Returns the name of this error variant, like `"CooldownHit"`. Useful for logs and metrics
+    pub fn variant_name(&self) -> &'static str {
+        match self {
+            Self::Setup { .. } => "Setup",
+            Self::EventHandler { .. } => "EventHandler",
+            Self::Command { .. } => "Command",
+            Self::SubcommandRequired { .. } => "SubcommandRequired",
+            Self::CommandPanic { .. } => "CommandPanic",
+            Self::ArgumentParse { .. } => "ArgumentParse",
+            Self::CommandStructureMismatch { .. } => "CommandStructureMismatch",
+            Self::CooldownHit { .. } => "CooldownHit",
+            Self::AlreadyRunning { .. } => "AlreadyRunning",
+            Self::MissingBotPermissions { .. } => "MissingBotPermissions",
+            Self::MissingUserPermissions { .. } => "MissingUserPermissions",
+            Self::NotAnOwner { .. } => "NotAnOwner",
+            Self::GuildOnly { .. } => "GuildOnly",
+            Self::DmOnly { .. } => "DmOnly",
+            Self::NsfwOnly { .. } => "NsfwOnly",
+            Self::CommandDisabled { .. } => "CommandDisabled",
+            Self::CommandCheckFailed { .. } => "CommandCheckFailed",
+            Self::DynamicPrefix { .. } => "DynamicPrefix",
+            Self::UnknownCommand { .. } => "UnknownCommand",
+            Self::UnknownInteraction { .. } => "UnknownInteraction",
+            Self::NonCommandMessage { .. } => "NonCommandMessage",
+            Self::ComponentHandler { .. } => "ComponentHandler",
+            Self::ScheduledTask { .. } => "ScheduledTask",
+            Self::__NonExhaustive(unreachable) => match *unreachable {},
+        }
+    }
+
+    /// Calls the appropriate `on_error` function (command-specific or global) with this error.
+    ///
+    /// Argument parse errors and check failures go to [`crate::Command::on_parse_error`] and
+    /// [`crate::Command::on_check_failure`] first, if set.
+    ///
+    /// Before that, command errors and panics are reported to
+    /// [`crate::FrameworkOptions::error_sink`], if set.
     pub async fn handle(self, framework_options: &crate::FrameworkOptions<U, E>) {
+        crate::metrics::record_error(&self);
+        // The report doesn't borrow the error, which may not be Sync
+        let report = framework_options
+            .error_sink
+            .as_ref()
+            .map(|error_sink| error_sink.report_with(&self, framework_options.error_sink_format));
+        if let Some(report) = report {
+            report.await;
+        }
+        let specific_handler = |command: &crate::Command<U, E>| match self {
+            Self::ArgumentParse { .. } => command.on_parse_error,
+            Self::CommandCheckFailed { .. } => command.on_check_failure,
+            _ => None,
+        };
         let on_error = self
             .ctx()
-            .and_then(|c| c.command().on_error)
+            .and_then(|c| specific_handler(c.command()).or(c.command().on_error))
             .unwrap_or(framework_options.on_error);
         on_error(self).await;
     }
 }
 
+/// Where and by whom the invocation that caused a [`FrameworkError`] was triggered, see
+/// [`FrameworkError::invocation_details`]. Fields are None if unknown or not applicable
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InvocationDetails {
+    /// Guild of the invocation, None in DMs
+    pub guild_id: Option<serenity::GuildId>,
+    /// Channel of the invocation
+    pub channel_id: Option<serenity::ChannelId>,
+    /// User who triggered the invocation
+    pub user_id: Option<serenity::UserId>,
+    /// [`crate::Command::qualified_name`] of the invoked command, if it was recognized
+    pub command_name: Option<String>,
+    /// The invocation as the user typed it (see [`crate::Context::invocation_string`]), the
+    /// message content, or the custom ID of a component interaction
+    pub invocation_string: Option<String>,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl InvocationDetails {
+    /// Details of a recognized command invocation
+    fn from_context<U, E>(ctx: crate::Context<'_, U, E>) -> Self {
+        Self {
+            guild_id: ctx.guild_id(),
+            channel_id: Some(ctx.channel_id()),
+            user_id: Some(ctx.author().id),
+            command_name: Some(ctx.command().qualified_name.clone()),
+            invocation_string: Some(ctx.invocation_string()),
+            __non_exhaustive: (),
+        }
+    }
+
+    /// Details of a message that wasn't recognized as a command invocation (yet)
+    fn from_message(msg: &serenity::Message) -> Self {
+        Self {
+            guild_id: msg.guild_id,
+            channel_id: Some(msg.channel_id),
+            user_id: Some(msg.author.id),
+            command_name: None,
+            invocation_string: Some(msg.content.clone()),
+            __non_exhaustive: (),
+        }
+    }
+}
+
 /// Support functions for the macro, which can't create these #[non_exhaustive] enum variants
 #[doc(hidden)]
 impl<'a, U, E> FrameworkError<'a, U, E> {
@@ -290,7 +488,21 @@ impl<'a, U, E> FrameworkError<'a, U, E> {
         input: Option<String>,
         error: Box<dyn std::error::Error + Send + Sync>,
     ) -> Self {
-        Self::ArgumentParse { error, input, ctx }
+        Self::new_argument_parse_for(ctx, input, error, None)
+    }
+
+    pub fn new_argument_parse_for(
+        ctx: crate::Context<'a, U, E>,
+        input: Option<String>,
+        error: Box<dyn std::error::Error + Send + Sync>,
+        parameter: Option<&str>,
+    ) -> Self {
+        Self::ArgumentParse {
+            error,
+            input,
+            parameter: parameter.map(str::to_owned),
+            ctx,
+        }
     }
 
     pub fn new_command_structure_mismatch(
@@ -338,6 +550,19 @@ impl<U, E: std::fmt::Display> std::fmt::Display for FrameworkError<'_, U, E> {
             Self::ArgumentParse {
                 error: _,
                 input,
+                parameter: Some(parameter),
+                ctx,
+            } => write!(
+                f,
+                "failed to parse argument `{}` in command `{}` on input {:?}",
+                parameter,
+                full_command_name!(ctx),
+                input
+            ),
+            Self::ArgumentParse {
+                error: _,
+                input,
+                parameter: None,
                 ctx,
             } => write!(
                 f,
@@ -360,6 +585,12 @@ impl<U, E: std::fmt::Display> std::fmt::Display for FrameworkError<'_, U, E> {
                 full_command_name!(ctx),
                 remaining_cooldown
             ),
+            Self::AlreadyRunning { scope, ctx } => write!(
+                f,
+                "too many running invocations of command `{}` ({:?} limit)",
+                full_command_name!(ctx),
+                scope
+            ),
             Self::MissingBotPermissions {
                 missing_permissions,
                 ctx,
@@ -398,7 +629,10 @@ impl<U, E: std::fmt::Display> std::fmt::Display for FrameworkError<'_, U, E> {
                 "nsfw-only command `{}` cannot run in non-nsfw channels",
                 full_command_name!(ctx)
             ),
-            Self::CommandCheckFailed { error: _, ctx } => write!(
+            Self::CommandDisabled { ctx } => {
+                write!(f, "command `{}` is disabled", full_command_name!(ctx))
+            }
+            Self::CommandCheckFailed { ctx, .. } => write!(
                 f,
                 "pre-command check for command `{}` either denied access or errored",
                 full_command_name!(ctx)
@@ -427,6 +661,16 @@ impl<U, E: std::fmt::Display> std::fmt::Display for FrameworkError<'_, U, E> {
                     msg.channel_id, msg.id
                 )
             }
+            Self::ScheduledTask { task_name, .. } => {
+                write!(f, "error in scheduled task `{}`", task_name)
+            }
+            Self::ComponentHandler { ctx, .. } => {
+                write!(
+                    f,
+                    "error in component handler for custom ID `{}`",
+                    ctx.interaction.data.custom_id
+                )
+            }
             Self::__NonExhaustive(unreachable) => match *unreachable {},
         }
     }
@@ -445,17 +689,21 @@ impl<'a, U: std::fmt::Debug, E: std::error::Error + 'static> std::error::Error
             Self::ArgumentParse { error, .. } => Some(&**error),
             Self::CommandStructureMismatch { .. } => None,
             Self::CooldownHit { .. } => None,
+            Self::AlreadyRunning { .. } => None,
             Self::MissingBotPermissions { .. } => None,
             Self::MissingUserPermissions { .. } => None,
             Self::NotAnOwner { .. } => None,
             Self::GuildOnly { .. } => None,
             Self::DmOnly { .. } => None,
             Self::NsfwOnly { .. } => None,
+            Self::CommandDisabled { .. } => None,
             Self::CommandCheckFailed { error, .. } => error.as_ref().map(|x| x as _),
             Self::DynamicPrefix { error, .. } => Some(error),
             Self::UnknownCommand { .. } => None,
             Self::UnknownInteraction { .. } => None,
             Self::NonCommandMessage { error, .. } => Some(error),
+            Self::ComponentHandler { error, .. } => Some(error),
+            Self::ScheduledTask { error, .. } => Some(error),
             Self::__NonExhaustive(unreachable) => match *unreachable {},
         }
     }
@@ -7,16 +7,90 @@ use crate::{serenity_prelude as serenity, BoxFuture};
 #[derivative(Debug(bound = ""))]
 pub struct FrameworkOptions<U, E> {
     /// List of commands in the framework
+    ///
+    /// [`crate::Framework`] moves the commands out of here when it's created, so this is empty
+    /// afterwards. Use [`crate::Framework::commands`] or [`crate::FrameworkContext::commands`] to
+    /// access them.
     pub commands: Vec<crate::Command<U, E>>,
+    /// If set, localized names and descriptions of all commands, parameters and choices are
+    /// filled in from these translations on startup. See [`crate::i18n`] for the key naming
+    /// scheme.
+    #[cfg(feature = "i18n")]
+    pub translations: Option<crate::i18n::Translations>,
     /// Provide a callback to be invoked when any user code yields an error.
     #[derivative(Debug = "ignore")]
     pub on_error: fn(crate::FrameworkError<'_, U, E>) -> BoxFuture<'_, ()>,
+    /// If set, command errors and panics are additionally forwarded to this channel or webhook.
+    /// This happens in [`crate::FrameworkError::handle`] before [`Self::on_error`] is called, so it
+    /// works with any error handler.
+    pub error_sink: Option<crate::ErrorSink>,
+    /// Renders command errors for the reports sent to [`Self::error_sink`]. Uses the
+    /// [`Display`](std::fmt::Display) implementation of the error by default.
+    #[derivative(Debug = "ignore")]
+    pub error_sink_format: fn(&E) -> String,
+    /// Texts of the user-facing errors sent by [`crate::builtins::on_error`], per locale
+    pub error_messages: crate::ErrorMessages,
+    /// If set, overrides the locale that Discord reports for the invoking user, for example with a
+    /// language configured per guild. Returning None falls back to the user's locale.
+    ///
+    /// Used by [`crate::Context::effective_locale`], and thereby by [`crate::builtins::help`], the
+    /// error messages of [`crate::builtins::on_error`] and `Context::t` (`i18n` feature).
+    ///
+    /// ```rust
+    /// # use poise::serenity_prelude as serenity;
+    /// # type Error = Box<dyn std::error::Error + Send + Sync>;
+    /// struct Data {
+    ///     guild_languages: std::sync::RwLock<std::collections::HashMap<serenity::GuildId, String>>,
+    /// }
+    ///
+    /// # let _: poise::FrameworkOptions<Data, Error> = poise::FrameworkOptions {
+    /// locale_provider: Some(|ctx| {
+    ///     let guild_id = ctx.guild_id()?;
+    ///     ctx.data().guild_languages.read().unwrap().get(&guild_id).cloned()
+    /// }),
+    /// # ..Default::default()
+    /// # };
+    /// ```
+    #[derivative(Debug = "ignore")]
+    pub locale_provider: Option<fn(crate::Context<'_, U, E>) -> Option<String>>,
+    /// If set, invocations of every command are counted, see [`crate::CommandUsage`]
+    ///
+    /// None (no counting) by default.
+    pub command_usage: Option<std::sync::Arc<crate::CommandUsage>>,
+    /// State keyed by type, in addition to the user data. Accessible from commands with
+    /// [`crate::Context::data_get`]. See [`crate::DataMap`]
+    ///
+    /// Empty by default.
+    pub data_map: crate::DataMap,
     /// Called before every command
     #[derivative(Debug = "ignore")]
     pub pre_command: fn(crate::Context<'_, U, E>) -> BoxFuture<'_, ()>,
     /// Called after every command if it was successful (returned Ok)
     #[derivative(Debug = "ignore")]
     pub post_command: fn(crate::Context<'_, U, E>) -> BoxFuture<'_, ()>,
+    /// Called after every command, whether it succeeded or not, with a summary of the outcome.
+    /// Runs before [`Self::post_command`] or [`Self::on_error`].
+    ///
+    /// ```rust
+    /// # type Error = Box<dyn std::error::Error + Send + Sync>;
+    /// poise::FrameworkOptions::<(), Error> {
+    ///     post_command_result: |ctx, outcome| {
+    ///         Box::pin(async move {
+    ///             println!(
+    ///                 "{} ({:?}) {} after {:?}",
+    ///                 ctx.command().qualified_name,
+    ///                 outcome.kind,
+    ///                 if outcome.success { "succeeded" } else { "failed" },
+    ///                 outcome.duration,
+    ///             );
+    ///         })
+    ///     },
+    ///     ..Default::default()
+    /// };
+    /// ```
+    #[derivative(Debug = "ignore")]
+    pub post_command_result:
+        fn(crate::Context<'_, U, E>, crate::CommandOutcome) -> BoxFuture<'_, ()>,
     /// Provide a callback to be invoked before every command. The command will only be executed
     /// if the callback returns true.
     ///
@@ -27,7 +101,13 @@ pub struct FrameworkOptions<U, E> {
     pub skip_checks_for_owners: bool,
     /// Default set of allowed mentions to use for all responses
     ///
+    /// Applied to every reply sent or edited via [`crate::Context::send`] and friends that doesn't
+    /// set its own [`crate::CreateReply::allowed_mentions`], so user input echoed by a command
+    /// can't ping `@everyone` or roles. Set to `None` to fall back to Discord's default of
+    /// allowing all mentions.
+    ///
     /// By default, user pings are allowed and role pings and everyone pings are filtered
+    #[doc(alias = "default_allowed_mentions")]
     pub allowed_mentions: Option<serenity::CreateAllowedMentions>,
     /// Invoked before every message sent using [`crate::Context::say`] or [`crate::Context::send`]
     ///
@@ -35,6 +115,35 @@ pub struct FrameworkOptions<U, E> {
     #[derivative(Debug = "ignore")]
     pub reply_callback:
         Option<fn(crate::Context<'_, U, E>, crate::CreateReply) -> crate::CreateReply>,
+    /// Invoked after every message successfully sent using [`crate::Context::say`] or
+    /// [`crate::Context::send`], with a handle to the sent message
+    ///
+    /// Allows you to log message IDs, add reactions, or register messages for later cleanup in a
+    /// central place. If a reply is split into multiple messages, this is called for each of them.
+    #[derivative(Debug = "ignore")]
+    pub post_reply_callback: Option<
+        for<'a> fn(crate::Context<'a, U, E>, &'a crate::ReplyHandle<'a>) -> BoxFuture<'a, ()>,
+    >,
+    /// If `true`, reply content longer than Discord's limit of 2000 characters is split into
+    /// multiple messages instead of failing to send. Can be overridden per reply with
+    /// [`crate::CreateReply::split_long_content`].
+    ///
+    /// False by default.
+    pub split_long_replies: bool,
+    /// If set, replies and deferrals that fail because of rate limits, Discord server errors or
+    /// connection problems are retried according to this policy before the error is returned.
+    /// New messages and followups are only retried on rate limits, so that they aren't posted
+    /// twice. See [`crate::RetryPolicy`] for details.
+    ///
+    /// None (no retries) by default.
+    pub reply_retry: Option<crate::RetryPolicy>,
+    /// If set, application commands that haven't responded after this duration are deferred
+    /// automatically (see [`crate::Context::defer`]), so that slow commands don't fail with
+    /// "Unknown interaction" because Discord's three second window for the initial response
+    /// expired. Can be overridden per command with [`crate::Command::auto_defer`].
+    ///
+    /// None by default.
+    pub auto_defer: Option<std::time::Duration>,
     /// If `true`, disables automatic cooldown handling before every command invocation.
     ///
     /// Useful for implementing custom cooldown behavior. See [`crate::Command::cooldowns`] and
@@ -45,6 +154,10 @@ pub struct FrameworkOptions<U, E> {
     ///
     /// **If `cache` feature is disabled, this has no effect!**
     pub require_cache_for_guild_check: bool,
+    /// Layers wrapped around the execution of every command, outermost first. See
+    /// [`crate::CommandMiddleware`].
+    #[derivative(Debug = "ignore")]
+    pub middleware: Vec<Box<dyn crate::CommandMiddleware<U, E>>>,
     /// Called on every Discord event. Can be used to react to non-command events, like messages
     /// deletions or guild updates.
     #[derivative(Debug = "ignore")]
@@ -55,11 +168,41 @@ pub struct FrameworkOptions<U, E> {
         // TODO: redundant with framework
         &'a U,
     ) -> BoxFuture<'a, Result<(), E>>,
+    /// Background tasks that are started once the bot is ready, see [`crate::ScheduledTask`]
+    pub scheduled_tasks: Vec<crate::ScheduledTask<U, E>>,
+    /// Handlers for specific event types, created with [`crate::events::on`]. They run
+    /// concurrently with each other and with [`Self::event_handler`].
+    #[derivative(Debug = "ignore")]
+    pub event_handlers: Vec<crate::events::TypedEventHandler<U, E>>,
+    /// Handlers for message component interactions (buttons, select menus), routed by custom ID
+    /// prefix. If several prefixes match, the longest one wins.
+    ///
+    /// ```rust
+    /// # type Data = (); type Error = Box<dyn std::error::Error + Send + Sync>;
+    /// async fn role_menu(ctx: poise::ComponentContext<'_, Data, Error>) -> Result<(), Error> {
+    ///     let role_id: u64 = ctx.custom_id_suffix.parse()?;
+    ///     // ...
+    ///     Ok(())
+    /// }
+    ///
+    /// poise::FrameworkOptions::<Data, Error> {
+    ///     component_handlers: vec![poise::ComponentHandler::new("role_menu:", |ctx| {
+    ///         Box::pin(role_menu(ctx))
+    ///     })],
+    ///     ..Default::default()
+    /// };
+    /// ```
+    pub component_handlers: Vec<crate::ComponentHandler<U, E>>,
     /// Renamed to [`Self::event_handler`]!
     #[deprecated = "renamed to event_handler"]
     pub listener: (),
     /// Prefix command specific options.
     pub prefix_options: crate::PrefixFrameworkOptions<U, E>,
+    /// How long [`crate::Framework::shutdown`] waits for running commands and event handlers to
+    /// finish before disconnecting anyway.
+    ///
+    /// 30 seconds by default.
+    pub shutdown_grace_period: std::time::Duration,
     /// User IDs which are allowed to use owners_only commands
     pub owners: std::collections::HashSet<serenity::UserId>,
     /// If true, [`Self::owners`] is automatically initialized with the results of
@@ -103,8 +246,15 @@ where
             },
             event_handler: |_, _, _, _| Box::pin(async { Ok(()) }),
             listener: (),
+            event_handlers: Vec::new(),
+            scheduled_tasks: Vec::new(),
+            component_handlers: Vec::new(),
             pre_command: |_| Box::pin(async {}),
             post_command: |_| Box::pin(async {}),
+            post_command_result: |_, _| Box::pin(async {}),
+            command_usage: None,
+            data_map: crate::DataMap::new(),
+            middleware: Vec::new(),
             command_check: None,
             skip_checks_for_owners: false,
             allowed_mentions: Some(
@@ -115,9 +265,20 @@ where
                     .replied_user(true),
             ),
             reply_callback: None,
+            post_reply_callback: None,
+            split_long_replies: false,
+            reply_retry: None,
+            auto_defer: None,
             manual_cooldowns: false,
+            error_sink: None,
+            error_sink_format: |error| error.to_string(),
+            #[cfg(feature = "i18n")]
+            translations: None,
+            error_messages: crate::ErrorMessages::default(),
+            locale_provider: None,
             require_cache_for_guild_check: false,
             prefix_options: Default::default(),
+            shutdown_grace_period: std::time::Duration::from_secs(30),
             owners: Default::default(),
             initialize_owners: true,
             __non_exhaustive: (),
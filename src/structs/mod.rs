@@ -17,3 +17,39 @@ pub use slash::*;
 
 mod framework_error;
 pub use framework_error::*;
+
+mod component;
+pub use component::*;
+
+mod timing;
+pub use timing::*;
+
+mod outcome;
+pub use outcome::*;
+
+mod command_index;
+pub use command_index::*;
+
+mod command_set;
+pub use command_set::*;
+
+mod command_registry;
+pub use command_registry::*;
+
+mod command_usage;
+pub use command_usage::*;
+
+mod config_issue;
+pub use config_issue::*;
+
+mod data_map;
+pub use data_map::*;
+
+mod shard_stats;
+pub use shard_stats::*;
+
+mod error_sink;
+pub use error_sink::*;
+
+mod error_messages;
+pub use error_messages::*;
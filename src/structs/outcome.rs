@@ -0,0 +1,28 @@
+//! Result summary of a command invocation, passed to
+//! [`crate::FrameworkOptions::post_command_result`]
+
+/// How a command was invoked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InvocationKind {
+    /// Invoked through a message with a prefix
+    Prefix,
+    /// Invoked as a slash command
+    Slash,
+    /// Invoked through a user or message context menu
+    ContextMenu,
+}
+
+/// Outcome of a command invocation, see [`crate::FrameworkOptions::post_command_result`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CommandOutcome {
+    /// Whether the command returned Ok. If not, the error is passed to
+    /// [`crate::FrameworkOptions::on_error`] afterwards
+    pub success: bool,
+    /// How long the command ran, see [`crate::Context::execution_time`]
+    pub duration: std::time::Duration,
+    /// How the command was invoked
+    pub kind: InvocationKind,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
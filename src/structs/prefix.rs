@@ -48,6 +48,11 @@ pub struct PrefixContext<'a, U, E> {
     pub data: &'a U,
     /// Custom user data carried across a single command invocation
     pub invocation_data: &'a tokio::sync::Mutex<Box<dyn std::any::Any + Send + Sync>>,
+    /// Messages sent in response to this command invocation, see [`crate::Context::replies`]
+    pub replies: &'a crate::ReplyRegistry,
+    /// When this command invocation was received and how long it ran, see
+    /// [`crate::Context::elapsed`]
+    pub timing: &'a crate::InvocationTiming,
     /// How this command invocation was triggered
     pub trigger: MessageDispatchTrigger,
     /// The function that is called to execute the actual command
@@ -83,6 +88,22 @@ pub enum Prefix {
     __NonExhaustive,
 }
 
+/// How to pick a prefix when several of them match a message, see
+/// [`PrefixFrameworkOptions::prefix_priority`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PrefixPriority {
+    /// Use the longest matching prefix, e.g. `!!` over `!` for the message `!!help`. If several
+    /// matching prefixes are equally long, the first one in definition order is used
+    #[default]
+    LongestMatch,
+    /// Use the first matching prefix in definition order: [`PrefixFrameworkOptions::dynamic_prefix`],
+    /// [`PrefixFrameworkOptions::prefix`], then [`PrefixFrameworkOptions::additional_prefixes`] in
+    /// list order
+    FirstMatch,
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
 /// Prefix-specific framework configuration
 #[derive(derivative::Derivative)]
 #[derivative(Debug(bound = ""))]
@@ -143,15 +164,64 @@ pub struct PrefixFrameworkOptions<U, E> {
     /// This is the case if the message edit happens before a command has sent a response, or if the
     /// command does not send a response at all.
     pub ignore_edits_if_not_yet_responded: bool,
+    /// Whether a message that is edited to invoke a different command (e.g. `~ping` into `~help`)
+    /// follows the edit tracking settings of the command it originally invoked.
+    ///
+    /// If set, the new command runs if either command has [`crate::Command::invoke_on_edit`]
+    /// set, and its reply is edited into the previous response if either command has
+    /// [`crate::Command::reuse_response`] set. Otherwise, only the new command's settings count.
+    ///
+    /// Note: only has an effect if [`Self::edit_tracker`] is set.
+    pub redispatch_edits: bool,
 
     /// Whether commands in messages emitted by this bot itself should be executed as well.
     pub execute_self_messages: bool,
     /// Whether to ignore messages from bots for command invoking. Default `true`
+    ///
+    /// See [`Self::allowed_bots`] and [`Self::allow_bot_message`] to make exceptions.
     pub ignore_bots: bool,
+    /// Bots that may invoke commands even if [`Self::ignore_bots`] is set, e.g. bridge bots. For
+    /// webhooks, this is the webhook ID
+    pub allowed_bots: Vec<serenity::UserId>,
+    /// Called for messages from bots if [`Self::ignore_bots`] is set and the bot isn't in
+    /// [`Self::allowed_bots`]. If it returns true, the message may invoke commands anyway
+    #[derivative(Debug = "ignore")]
+    pub allow_bot_message: Option<fn(&serenity::Message) -> bool>,
     /// Whether to ignore commands contained within thread creation messages. Default `true`
     pub ignore_thread_creation: bool,
-    /// Whether command names should be compared case-insensitively.
+    /// Whether command names should be compared case-insensitively, so that `~Help`, `~HELP` and
+    /// `~help` all invoke the same command. Applies to aliases and subcommand names too. Default
+    /// `true`
+    ///
+    /// Only ASCII letters are compared case-insensitively, which doesn't need to allocate
+    /// lowercase copies of the names. See [`crate::find_command`].
     pub case_insensitive_commands: bool,
+    /// If set, messages that don't start with a prefix can still invoke a command inside a span
+    /// enclosed by these opening and closing delimiters, e.g. `("{{", "}}")` to make
+    /// `have you tried {{docs Vec::push}}?` invoke the `docs` command with the argument
+    /// `Vec::push`. Only the first such span in a message is used. Default `None`
+    pub inline_invocation_delimiters: Option<(String, String)>,
+    /// Whether to reply with the command's help, including the list of its subcommands, when a
+    /// command with [`crate::Command::subcommand_required`] is invoked without a subcommand (e.g.
+    /// `~config`). Otherwise, [`crate::FrameworkError::SubcommandRequired`] is raised. Default
+    /// `false`
+    pub help_on_missing_subcommand: bool,
+    /// Whether to look for a similarly named command when a message has the prefix but no command
+    /// matches. The closest one is passed to [`crate::FrameworkError::UnknownCommand`] as
+    /// `suggestion`, and [`crate::builtins::on_error`] replies with "Unknown command `bna`, did
+    /// you mean `ban`?". Handle the error yourself to customize or suppress that reply.
+    /// Default `false`
+    pub suggest_unknown_commands: bool,
+    /// Whether [`Self::prefix`], [`Self::additional_prefixes`] (except regexes) and
+    /// [`Self::dynamic_prefix`] should be matched case-insensitively, so that `Bot help` works
+    /// with the prefix `bot `. Default `false`
+    pub case_insensitive_prefix: bool,
+    /// Which prefix to use if several of [`Self::dynamic_prefix`], [`Self::prefix`] and
+    /// [`Self::additional_prefixes`] match. Defaults to the longest one.
+    ///
+    /// [`Self::stripped_dynamic_prefix`] and [`Self::mention_as_prefix`] are only tried if none of
+    /// those match.
+    pub prefix_priority: PrefixPriority,
     /// Callback for all non-command messages. Useful if you want to run code on any message that
     /// is not a command
     pub non_command_message: Option<
@@ -186,10 +256,18 @@ impl<U, E> Default for PrefixFrameworkOptions<U, E> {
             edit_tracker: None,
             execute_untracked_edits: true,
             ignore_edits_if_not_yet_responded: false,
+            redispatch_edits: false,
             execute_self_messages: false,
             ignore_bots: true,
+            allowed_bots: Vec::new(),
+            allow_bot_message: None,
             ignore_thread_creation: true,
             case_insensitive_commands: true,
+            suggest_unknown_commands: false,
+            help_on_missing_subcommand: false,
+            inline_invocation_delimiters: None,
+            case_insensitive_prefix: false,
+            prefix_priority: PrefixPriority::LongestMatch,
             non_command_message: None,
             // help_when_mentioned: true,
             // help_commmand: None,
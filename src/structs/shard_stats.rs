@@ -0,0 +1,76 @@
+//! Typed status of the shards of the bot, see [`ShardStats`]
+
+use crate::serenity_prelude as serenity;
+
+/// Status of a single shard that's run by this process, as returned by
+/// [`crate::FrameworkContext::shard_stats`]
+///
+/// To be notified when a shard connects, resumes or disconnects, register a handler for
+/// [`crate::events::ShardStageUpdate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShardStats {
+    /// ID of the shard
+    pub id: serenity::ShardId,
+    /// Connection stage of the shard, e.g. [`serenity::ConnectionStage::Connected`]
+    pub stage: serenity::ConnectionStage,
+    /// Round-trip time of the last heartbeat, i.e. how long Discord took to acknowledge it. None
+    /// until the shard has received its first heartbeat acknowledgement.
+    ///
+    /// Serenity doesn't expose when the last heartbeat was sent or acknowledged, so there's no
+    /// timestamp for it.
+    pub latency: Option<std::time::Duration>,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl ShardStats {
+    /// Returns whether the shard is connected and receiving events
+    pub fn is_connected(&self) -> bool {
+        self.stage == serenity::ConnectionStage::Connected
+    }
+}
+
+/// Returns the status of all shards of the shard manager, sorted by shard ID. Used by
+/// [`crate::FrameworkContext::shard_stats`] and [`crate::Framework::shard_stats`]
+pub async fn shard_stats(shard_manager: &serenity::ShardManager) -> Vec<ShardStats> {
+    let mut shards = shard_manager
+        .runners
+        .lock()
+        .await
+        .iter()
+        .map(|(&id, runner)| ShardStats {
+            id,
+            stage: runner.stage,
+            latency: runner.latency,
+            __non_exhaustive: (),
+        })
+        .collect::<Vec<_>>();
+    shards.sort_by_key(|shard| shard.id.0);
+    shards
+}
+
+/// Returns the mean latency of the shards that have one, or None if no shard has a latency yet
+pub fn average_latency(shards: &[ShardStats]) -> Option<std::time::Duration> {
+    let latencies = shards
+        .iter()
+        .filter_map(|shard| shard.latency)
+        .collect::<Vec<_>>();
+    let count = u32::try_from(latencies.len()).ok().filter(|&n| n > 0)?;
+    Some(latencies.into_iter().sum::<std::time::Duration>() / count)
+}
+
+#[cfg(test)]
+#[test]
+fn test_average_latency() {
+    let shard = |id, latency_ms: Option<u64>| ShardStats {
+        id: serenity::ShardId(id),
+        stage: serenity::ConnectionStage::Connected,
+        latency: latency_ms.map(std::time::Duration::from_millis),
+        __non_exhaustive: (),
+    };
+    assert_eq!(average_latency(&[]), None);
+    assert_eq!(
+        average_latency(&[shard(0, Some(40)), shard(1, None), shard(2, Some(60))]),
+        Some(std::time::Duration::from_millis(50))
+    );
+}
@@ -47,6 +47,11 @@ pub struct ApplicationContext<'a, U, E> {
     pub data: &'a U,
     /// Custom user data carried across a single command invocation
     pub invocation_data: &'a tokio::sync::Mutex<Box<dyn std::any::Any + Send + Sync>>,
+    /// Messages sent in response to this command invocation, see [`crate::Context::replies`]
+    pub replies: &'a crate::ReplyRegistry,
+    /// When this command invocation was received and how long it ran, see
+    /// [`crate::Context::elapsed`]
+    pub timing: &'a crate::InvocationTiming,
     // #[non_exhaustive] forbids struct update syntax for ?? reason
     #[doc(hidden)]
     pub __non_exhaustive: (),
@@ -73,9 +78,11 @@ impl<U, E> ApplicationContext<'_, U, E> {
                 serenity::CreateInteractionResponseMessage::new().ephemeral(ephemeral),
             );
 
-            self.interaction
-                .create_response(self.serenity_context, response)
-                .await?;
+            crate::with_retry(self.framework.options.reply_retry.as_ref(), || {
+                self.interaction
+                    .create_response(self.serenity_context, response.clone())
+            })
+            .await?;
 
             self.has_sent_initial_response
                 .store(true, std::sync::atomic::Ordering::SeqCst);
@@ -114,6 +121,17 @@ impl<U, E> Clone for ContextMenuCommandAction<U, E> {
     }
 }
 
+/// The value that Discord sends to the bot when a [`CommandParameterChoice`] is picked
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CommandParameterChoiceValue {
+    /// An integer value. Registers the parameter as an integer option
+    ///
+    /// Limited to `i32` because that's what serenity's command builder accepts.
+    Integer(i32),
+    /// A floating point value. Registers the parameter as a number option
+    Number(f64),
+}
+
 /// A single drop-down choice in a slash command choice parameter
 #[derive(Debug, Clone)]
 pub struct CommandParameterChoice {
@@ -121,10 +139,25 @@ pub struct CommandParameterChoice {
     pub name: String,
     /// Localized labels with locale string as the key (slash-only)
     pub localizations: std::collections::HashMap<String, String>,
+    /// The value that Discord sends when this choice is picked. If `None`, the index of this choice
+    /// in the list of choices is sent
+    pub value: Option<CommandParameterChoiceValue>,
     #[doc(hidden)]
     pub __non_exhaustive: (),
 }
 
+impl CommandParameterChoice {
+    /// Creates a choice with the given label and no localizations
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            localizations: Default::default(),
+            value: None,
+            __non_exhaustive: (),
+        }
+    }
+}
+
 /// A single parameter of a [`crate::Command`]
 #[derive(Clone, derivative::Derivative)]
 #[derivative(Debug(bound = ""))]
@@ -145,6 +178,14 @@ pub struct CommandParameter<U, E> {
     pub channel_types: Option<Vec<serenity::ChannelType>>,
     /// If this parameter is a choice parameter, this is the fixed list of options
     pub choices: Vec<CommandParameterChoice>,
+    /// Callback that supplies the list of options at registration time, for choices that aren't
+    /// known at compile time (e.g. loaded from a config file)
+    ///
+    /// Unlike [`Self::choices`], these are registered as string choices, so the command receives
+    /// the name of the selected choice as a string. In prefix commands, input that doesn't match
+    /// any choice name is rejected with [`crate::InvalidChoice`].
+    #[derivative(Debug = "ignore")]
+    pub dynamic_choices: Option<fn() -> Vec<CommandParameterChoice>>,
     /// Closure that sets this parameter's type and min/max value in the given builder
     ///
     /// For example a u32 [`CommandParameter`] would store this as the [`Self::type_setter`]:
@@ -202,8 +243,25 @@ impl<U, E> CommandParameter<U, E> {
             builder = builder.channel_types(channel_types);
         }
         for (i, choice) in self.choices.iter().enumerate() {
-            builder =
-                builder.add_int_choice_localized(&choice.name, i as _, choice.localizations.iter());
+            let locales = choice.localizations.iter();
+            builder = match choice.value {
+                None => builder.add_int_choice_localized(&choice.name, i as _, locales),
+                Some(CommandParameterChoiceValue::Integer(value)) => {
+                    builder.add_int_choice_localized(&choice.name, value, locales)
+                }
+                Some(CommandParameterChoiceValue::Number(value)) => {
+                    builder.add_number_choice_localized(&choice.name, value, locales)
+                }
+            };
+        }
+        if let Some(dynamic_choices) = self.dynamic_choices {
+            for choice in dynamic_choices() {
+                builder = builder.add_string_choice_localized(
+                    &choice.name,
+                    &choice.name,
+                    choice.localizations.iter(),
+                );
+            }
         }
 
         Some((self.type_setter?)(builder))
@@ -0,0 +1,46 @@
+//! Timestamps of a single command invocation
+
+use std::time::{Duration, Instant};
+
+/// Records when a command invocation was received and how long the command itself ran, see
+/// [`crate::Context::elapsed`] and [`crate::Context::execution_time`].
+///
+/// A new instance is created by the framework for every invocation.
+#[derive(Debug)]
+pub struct InvocationTiming {
+    /// When the framework received the gateway event
+    received_at: Instant,
+    /// When the command action started and, once it returned, how long it ran
+    execution: parking_lot::Mutex<(Option<Instant>, Option<Duration>)>,
+}
+
+impl InvocationTiming {
+    /// Creates a record for an event received at the given instant
+    pub fn new(received_at: Instant) -> Self {
+        Self {
+            received_at,
+            execution: parking_lot::Mutex::new((None, None)),
+        }
+    }
+
+    /// When the framework received the gateway event that triggered this invocation
+    pub fn received_at(&self) -> Instant {
+        self.received_at
+    }
+
+    /// How long the command action ran, once it has returned
+    pub fn execution_time(&self) -> Option<Duration> {
+        self.execution.lock().1
+    }
+
+    /// Marks the start of the command action
+    pub(crate) fn start_execution(&self) {
+        *self.execution.lock() = (Some(Instant::now()), None);
+    }
+
+    /// Marks the end of the command action
+    pub(crate) fn finish_execution(&self) {
+        let mut execution = self.execution.lock();
+        execution.1 = execution.0.map(|start| start.elapsed());
+    }
+}
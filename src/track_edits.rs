@@ -47,26 +47,195 @@ fn update_message(message: &mut serenity::Message, update: serenity::MessageUpda
     // }
 }
 
-/// A single cached command invocation
-#[derive(Debug)]
-struct CachedInvocation {
+/// A single tracked command invocation, as stored in an [`EditTrackerStore`]
+#[derive(Clone, Debug)]
+pub struct TrackedInvocation {
     /// User message that triggered this command invocation
-    user_msg: serenity::Message,
+    pub user_msg: serenity::Message,
     /// Associated bot response of this command invocation
-    bot_response: Option<serenity::Message>,
+    pub bot_response: Option<serenity::Message>,
+    /// Further bot responses, like the continuation messages of a reply that was split into
+    /// multiple messages
+    pub additional_bot_responses: Vec<serenity::Message>,
     /// Whether the bot response should be deleted when the user deletes their message
-    track_deletion: bool,
+    pub track_deletion: bool,
+    /// Qualified name of the command that this message originally invoked
+    pub command: Option<String>,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+
+impl TrackedInvocation {
+    /// Creates a tracked invocation without any bot responses yet
+    pub fn new(user_msg: serenity::Message, track_deletion: bool) -> Self {
+        Self {
+            user_msg,
+            bot_response: None,
+            additional_bot_responses: Vec::new(),
+            track_deletion,
+            command: None,
+            __non_exhaustive: (),
+        }
+    }
+
+    /// Whether the user message was last created or edited longer than `max_duration` ago
+    pub fn is_expired(&self, max_duration: std::time::Duration) -> bool {
+        let last_update = self
+            .user_msg
+            .edited_timestamp
+            .unwrap_or(self.user_msg.timestamp);
+        let age = serenity::Timestamp::now().unix_timestamp() - last_update.unix_timestamp();
+        age >= max_duration.as_secs() as i64
+    }
+}
+
+/// Storage backend of an [`EditTracker`], keyed by the ID of the user message that invoked a
+/// command.
+///
+/// The default, [`InMemoryEditTrackerStore`], forgets everything on restart. Implement this trait
+/// to persist invocations elsewhere (e.g. in a database) and pass it to
+/// [`EditTracker::with_store`], so that edit tracking keeps working across restarts.
+///
+/// The edit tracker is accessed behind a lock, so implementations should be quick. A persistent
+/// store may want to keep an in-memory copy and write changes through in the background.
+pub trait EditTrackerStore: Send + Sync {
+    /// Stores the given invocation, replacing any invocation with the same user message ID
+    fn insert(&mut self, invocation: TrackedInvocation);
+
+    /// Returns the invocation with the given user message ID, if stored
+    fn lookup(&self, user_msg_id: serenity::MessageId) -> Option<TrackedInvocation>;
+
+    /// Removes and returns the invocation with the given user message ID, if stored
+    fn remove(&mut self, user_msg_id: serenity::MessageId) -> Option<TrackedInvocation>;
+
+    /// Removes all invocations that are expired according to [`TrackedInvocation::is_expired`]
+    fn expire(&mut self, max_duration: std::time::Duration);
+
+    /// Returns the number of stored invocations
+    fn len(&self) -> usize;
+
+    /// Returns whether no invocations are stored
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// The default [`EditTrackerStore`], which keeps invocations in memory.
+///
+/// By default, the number of stored invocations is only bounded by the edit tracker's duration.
+/// On busy bots, use [`Self::max_entries`] and [`Self::max_entries_per_channel`] to bound memory
+/// usage; when a limit is exceeded, the oldest invocations are evicted first.
+///
+/// ```rust
+/// let store = poise::InMemoryEditTrackerStore::default()
+///     .max_entries(10_000)
+///     .max_entries_per_channel(100);
+/// let edit_tracker =
+///     poise::EditTracker::with_store(std::time::Duration::from_secs(3600), store);
+/// ```
+#[derive(Debug, Default)]
+pub struct InMemoryEditTrackerStore {
+    /// Stored invocations by user message ID, oldest first
+    cache: indexmap::IndexMap<serenity::MessageId, TrackedInvocation>,
+    /// Maximum number of stored invocations
+    max_entries: Option<usize>,
+    /// Maximum number of stored invocations per channel
+    max_entries_per_channel: Option<usize>,
+}
+
+impl InMemoryEditTrackerStore {
+    /// Sets the maximum number of stored invocations
+    pub fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = Some(max_entries);
+        self
+    }
+
+    /// Sets the maximum number of stored invocations per channel
+    pub fn max_entries_per_channel(mut self, max_entries_per_channel: usize) -> Self {
+        self.max_entries_per_channel = Some(max_entries_per_channel);
+        self
+    }
+
+    /// Evicts the oldest invocations until the limits are satisfied, after an invocation was
+    /// added in the given channel
+    fn evict(&mut self, channel_id: serenity::ChannelId) {
+        if let Some(max_entries_per_channel) = self.max_entries_per_channel {
+            let in_channel = self
+                .cache
+                .values()
+                .filter(|invocation| invocation.user_msg.channel_id == channel_id)
+                .count();
+            let mut excess = in_channel.saturating_sub(max_entries_per_channel);
+            self.cache.retain(|_, invocation| {
+                if excess > 0 && invocation.user_msg.channel_id == channel_id {
+                    excess -= 1;
+                    return false;
+                }
+                true
+            });
+        }
+        if let Some(max_entries) = self.max_entries {
+            let excess = self.cache.len().saturating_sub(max_entries);
+            self.cache.drain(..excess);
+        }
+    }
+}
+
+impl EditTrackerStore for InMemoryEditTrackerStore {
+    fn insert(&mut self, invocation: TrackedInvocation) {
+        let channel_id = invocation.user_msg.channel_id;
+        // Replacing an invocation keeps its position
+        let (_, replaced) = self.cache.insert_full(invocation.user_msg.id, invocation);
+        if replaced.is_none() {
+            self.evict(channel_id);
+        }
+    }
+
+    fn lookup(&self, user_msg_id: serenity::MessageId) -> Option<TrackedInvocation> {
+        self.cache.get(&user_msg_id).cloned()
+    }
+
+    fn remove(&mut self, user_msg_id: serenity::MessageId) -> Option<TrackedInvocation> {
+        self.cache.shift_remove(&user_msg_id)
+    }
+
+    fn expire(&mut self, max_duration: std::time::Duration) {
+        self.cache
+            .retain(|_, invocation| !invocation.is_expired(max_duration));
+    }
+
+    fn len(&self) -> usize {
+        self.cache.len()
+    }
+}
+
+/// Usage statistics of an [`EditTracker`], returned by [`EditTracker::stats`]
+#[derive(Clone, Debug, Default)]
+pub struct EditTrackerStats {
+    /// Number of currently tracked invocations
+    pub entries: usize,
+    /// Number of message edits and deletions that concerned a tracked invocation
+    pub hits: u64,
+    /// Number of message edits and deletions that didn't concern a tracked invocation
+    pub misses: u64,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
 }
 
 /// Stores messages and the associated bot responses in order to implement poise's edit tracking
 /// feature.
-#[derive(Debug)]
+#[derive(derivative::Derivative)]
+#[derivative(Debug)]
 pub struct EditTracker {
     /// Duration after which cached messages can be purged
     max_duration: std::time::Duration,
-    /// Cache, which stores invocation messages, and the corresponding bot response message if any
-    // TODO: change to `OrderedMap<MessageId, (Message, Option<serenity::Message>)>`?
-    cache: Vec<CachedInvocation>,
+    /// Storage of invocation messages, and the corresponding bot response messages if any
+    #[derivative(Debug = "ignore")]
+    store: Box<dyn EditTrackerStore>,
+    /// Number of message updates and deletions of tracked invocations
+    hits: u64,
+    /// Number of message updates and deletions of untracked messages
+    misses: u64,
 }
 
 impl EditTracker {
@@ -76,9 +245,20 @@ impl EditTracker {
     /// is called. If you supply the created [`EditTracker`] to [`crate::Framework`], the framework
     /// will take care of that by calling [`Self::purge`] periodically.
     pub fn for_timespan(duration: std::time::Duration) -> std::sync::RwLock<Self> {
+        Self::with_store(duration, InMemoryEditTrackerStore::default())
+    }
+
+    /// Like [`Self::for_timespan`], but keeps the tracked messages in the given store instead of
+    /// in memory
+    pub fn with_store(
+        duration: std::time::Duration,
+        store: impl EditTrackerStore + 'static,
+    ) -> std::sync::RwLock<Self> {
         std::sync::RwLock::new(Self {
             max_duration: duration,
-            cache: Vec::new(),
+            store: Box::new(store),
+            hits: 0,
+            misses: 0,
         })
     }
 
@@ -91,12 +271,10 @@ impl EditTracker {
         user_msg_update: &serenity::MessageUpdateEvent,
         ignore_edits_if_not_yet_responded: bool,
     ) -> Option<(serenity::Message, bool)> {
-        match self
-            .cache
-            .iter_mut()
-            .find(|invocation| invocation.user_msg.id == user_msg_update.id)
-        {
-            Some(invocation) => {
+        let invocation = self.store.lookup(user_msg_update.id);
+        self.record_lookup(invocation.is_some());
+        match invocation {
+            Some(mut invocation) => {
                 if ignore_edits_if_not_yet_responded && invocation.bot_response.is_none() {
                     return None;
                 }
@@ -111,7 +289,9 @@ impl EditTracker {
                 }
 
                 update_message(&mut invocation.user_msg, user_msg_update.clone());
-                Some((invocation.user_msg.clone(), true))
+                let user_msg = invocation.user_msg.clone();
+                self.store.insert(invocation);
+                Some((user_msg, true))
             }
             None => {
                 if ignore_edits_if_not_yet_responded {
@@ -125,47 +305,67 @@ impl EditTracker {
     }
 
     /// Removes this command invocation from the cache and returns the associated bot response,
-    /// if the command invocation is cached, and it has an associated bot response, and the command
-    /// is marked track_deletion
+    /// if the command invocation is cached, and the command is marked track_deletion
+    ///
+    /// Responses added with [`Self::add_bot_response`] are forgotten; use
+    /// [`Self::process_message_delete_all`] to get them too.
     pub fn process_message_delete(
         &mut self,
         deleted_message_id: serenity::MessageId,
     ) -> Option<serenity::Message> {
-        let invocation = self.cache.remove(
-            self.cache
-                .iter()
-                .position(|invocation| invocation.user_msg.id == deleted_message_id)?,
-        );
-        if invocation.track_deletion {
-            invocation.bot_response
-        } else {
-            None
+        self.process_message_delete_all(deleted_message_id)
+            .into_iter()
+            .next()
+    }
+
+    /// Like [`Self::process_message_delete`], but returns all associated bot responses, including
+    /// those added with [`Self::add_bot_response`]
+    pub fn process_message_delete_all(
+        &mut self,
+        deleted_message_id: serenity::MessageId,
+    ) -> Vec<serenity::Message> {
+        let invocation = self.store.remove(deleted_message_id);
+        self.record_lookup(invocation.is_some());
+        match invocation {
+            Some(invocation) if invocation.track_deletion => invocation
+                .bot_response
+                .into_iter()
+                .chain(invocation.additional_bot_responses)
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Counts a hit or miss for [`Self::stats`]
+    fn record_lookup(&mut self, hit: bool) {
+        match hit {
+            true => self.hits += 1,
+            false => self.misses += 1,
+        }
+    }
+
+    /// Returns the number of tracked invocations and how often message edits and deletions
+    /// concerned a tracked invocation, to help tune the edit tracker's limits
+    pub fn stats(&self) -> EditTrackerStats {
+        EditTrackerStats {
+            entries: self.store.len(),
+            hits: self.hits,
+            misses: self.misses,
+            __non_exhaustive: (),
         }
     }
 
     /// Forget all of the messages that are older than the specified duration.
     pub fn purge(&mut self) {
-        let max_duration = self.max_duration;
-        self.cache.retain(|invocation| {
-            let last_update = invocation
-                .user_msg
-                .edited_timestamp
-                .unwrap_or(invocation.user_msg.timestamp);
-            let age = serenity::Timestamp::now().unix_timestamp() - last_update.unix_timestamp();
-            age < max_duration.as_secs() as i64
-        });
+        self.store.expire(self.max_duration);
     }
 
     /// Given a message by a user, find the corresponding bot response, if one exists and is cached.
-    pub fn find_bot_response(
-        &self,
-        user_msg_id: serenity::MessageId,
-    ) -> Option<&serenity::Message> {
-        let invocation = self
-            .cache
-            .iter()
-            .find(|invocation| invocation.user_msg.id == user_msg_id)?;
-        invocation.bot_response.as_ref()
+    ///
+    /// Returns a copy, because an [`EditTrackerStore`] doesn't need to keep the invocations in
+    /// memory.
+    pub fn find_bot_response(&self, user_msg_id: serenity::MessageId) -> Option<serenity::Message> {
+        self.store.lookup(user_msg_id)?.bot_response
     }
 
     /// Notify the [`EditTracker`] that the given user message should be associated with the given
@@ -176,18 +376,61 @@ impl EditTracker {
         bot_response: serenity::Message,
         track_deletion: bool,
     ) {
-        if let Some(invocation) = self
-            .cache
-            .iter_mut()
-            .find(|invocation| invocation.user_msg.id == user_msg.id)
-        {
-            invocation.bot_response = Some(bot_response);
-        } else {
-            self.cache.push(CachedInvocation {
-                user_msg: user_msg.clone(),
-                bot_response: Some(bot_response),
-                track_deletion,
-            });
+        let mut invocation = self
+            .store
+            .lookup(user_msg.id)
+            .unwrap_or_else(|| TrackedInvocation::new(user_msg.clone(), track_deletion));
+        invocation.bot_response = Some(bot_response);
+        self.store.insert(invocation);
+    }
+
+    /// Notify the [`EditTracker`] that the given bot response belongs to the given user message, in
+    /// addition to the response set with [`Self::set_bot_response`]. If there's no such response
+    /// yet, this is the same as [`Self::set_bot_response`]
+    pub fn add_bot_response(
+        &mut self,
+        user_msg: &serenity::Message,
+        bot_response: serenity::Message,
+        track_deletion: bool,
+    ) {
+        match self.store.lookup(user_msg.id) {
+            Some(mut invocation) if invocation.bot_response.is_some() => {
+                invocation.additional_bot_responses.push(bot_response);
+                self.store.insert(invocation);
+            }
+            _ => self.set_bot_response(user_msg, bot_response, track_deletion),
+        }
+    }
+
+    /// Removes and returns the bot responses added with [`Self::add_bot_response`] for the given
+    /// user message
+    pub fn take_additional_bot_responses(
+        &mut self,
+        user_msg_id: serenity::MessageId,
+    ) -> Vec<serenity::Message> {
+        let Some(mut invocation) = self.store.lookup(user_msg_id) else {
+            return Vec::new();
+        };
+        let responses = std::mem::take(&mut invocation.additional_bot_responses);
+        self.store.insert(invocation);
+        responses
+    }
+
+    /// Returns the qualified name of the command that the given user message originally invoked,
+    /// if it's tracked
+    pub fn original_command(&self, user_msg_id: serenity::MessageId) -> Option<String> {
+        self.store.lookup(user_msg_id)?.command
+    }
+
+    /// Notify the [`EditTracker`] that the given user message invoked the command with the given
+    /// qualified name. Has no effect if a command was already stored for this message, so that
+    /// edits which invoke a different command keep the original one
+    pub fn set_original_command(&mut self, user_msg_id: serenity::MessageId, command: &str) {
+        if let Some(mut invocation) = self.store.lookup(user_msg_id) {
+            if invocation.command.is_none() {
+                invocation.command = Some(command.to_owned());
+                self.store.insert(invocation);
+            }
         }
     }
 
@@ -195,16 +438,9 @@ impl EditTracker {
     /// invocation message (e.g. removing embeds), we don't accidentally treat it as an
     /// `execute_untracked_edits` situation and start an infinite loop
     pub fn track_command(&mut self, user_msg: &serenity::Message, track_deletion: bool) {
-        if !self
-            .cache
-            .iter()
-            .any(|invocation| invocation.user_msg.id == user_msg.id)
-        {
-            self.cache.push(CachedInvocation {
-                user_msg: user_msg.clone(),
-                bot_response: None,
-                track_deletion,
-            });
+        if self.store.lookup(user_msg.id).is_none() {
+            self.store
+                .insert(TrackedInvocation::new(user_msg.clone(), track_deletion));
         }
     }
 }
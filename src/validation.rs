@@ -0,0 +1,438 @@
+//! Runtime checks for parameter attributes like `#[regex = "..."]` or `#[min = ...]`, which are
+//! validated after the argument has been parsed
+
+/// Error thrown when a parameter annotated with `#[regex = "..."]` doesn't match the pattern
+#[derive(Default, Debug, Clone)]
+pub struct PatternMismatch {
+    /// The regular expression that the input failed to match
+    pub pattern: &'static str,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+impl std::fmt::Display for PatternMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Input doesn't have the required format (must match `{}`)",
+            self.pattern
+        )
+    }
+}
+impl std::error::Error for PatternMismatch {}
+
+/// Lazily compiled regular expression backing the `#[regex = "..."]` parameter attribute. The
+/// command macro puts one of these in a `static` per annotated parameter.
+#[doc(hidden)]
+pub struct LazyPattern {
+    /// Source of the regular expression, already checked for validity by the command macro
+    pattern: &'static str,
+    /// Compiled on first use
+    regex: std::sync::OnceLock<regex::Regex>,
+}
+
+impl LazyPattern {
+    pub const fn new(pattern: &'static str) -> Self {
+        Self {
+            pattern,
+            regex: std::sync::OnceLock::new(),
+        }
+    }
+
+    pub fn validate(
+        &self,
+        value: &str,
+    ) -> Result<(), (Box<dyn std::error::Error + Send + Sync>, Option<String>)> {
+        let regex = self.regex.get_or_init(|| {
+            regex::Regex::new(self.pattern).expect("pattern was validated by the command macro")
+        });
+        if regex.is_match(value) {
+            Ok(())
+        } else {
+            let error = PatternMismatch {
+                pattern: self.pattern,
+                __non_exhaustive: (),
+            };
+            Err((error.into(), Some(value.to_owned())))
+        }
+    }
+}
+
+/// Error thrown when a number parameter is outside the range given by `#[min]` and `#[max]`
+///
+/// Discord enforces these bounds by itself for slash commands, so this error only occurs in prefix
+/// commands.
+#[derive(Default, Debug, Clone)]
+pub struct NumberOutOfRange {
+    /// Minimum value allowed, if any
+    pub min: Option<f64>,
+    /// Maximum value allowed, if any
+    pub max: Option<f64>,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+impl std::fmt::Display for NumberOutOfRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => write!(f, "Number must be between {} and {}", min, max),
+            (Some(min), None) => write!(f, "Number must be at least {}", min),
+            (None, Some(max)) => write!(f, "Number must be at most {}", max),
+            (None, None) => f.write_str("Number is out of range"),
+        }
+    }
+}
+impl std::error::Error for NumberOutOfRange {}
+
+/// Error thrown when a string parameter is shorter or longer than allowed by `#[min_length]` and
+/// `#[max_length]`
+///
+/// Discord enforces these bounds by itself for slash commands, so this error only occurs in prefix
+/// commands.
+#[derive(Default, Debug, Clone)]
+pub struct LengthOutOfRange {
+    /// Minimum number of characters allowed, if any
+    pub min_length: Option<usize>,
+    /// Maximum number of characters allowed, if any
+    pub max_length: Option<usize>,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+impl std::fmt::Display for LengthOutOfRange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (self.min_length, self.max_length) {
+            (Some(min), Some(max)) => {
+                write!(
+                    f,
+                    "Text must be between {} and {} characters long",
+                    min, max
+                )
+            }
+            (Some(min), None) => write!(f, "Text must be at least {} characters long", min),
+            (None, Some(max)) => write!(f, "Text must be at most {} characters long", max),
+            (None, None) => f.write_str("Text has an invalid length"),
+        }
+    }
+}
+impl std::error::Error for LengthOutOfRange {}
+
+/// Bound given by `#[min]` or `#[max]`, as written in the attribute
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy)]
+pub enum NumberBound {
+    /// Integer literal. Compared exactly with integer parameters, even above 2^53
+    Int(i128),
+    /// Float literal
+    Float(f64),
+}
+
+impl NumberBound {
+    /// Approximate value for [`NumberOutOfRange`]
+    fn to_f64(self) -> f64 {
+        match self {
+            Self::Int(bound) => bound as f64,
+            Self::Float(bound) => bound,
+        }
+    }
+}
+
+/// Number types that can be checked against `#[min]` and `#[max]`
+#[doc(hidden)]
+pub trait RangeCheckedNumber: Copy + std::fmt::Display {
+    /// Compares the value with the bound in the value's own type where possible, so that large
+    /// integers aren't rounded. None if the value is NaN
+    fn compare_to(self, bound: NumberBound) -> Option<std::cmp::Ordering>;
+}
+
+/// Implements [`RangeCheckedNumber`] for integer types, which fit into i128 losslessly
+macro_rules! impl_range_checked_integer {
+    ($($t:ty)*) => { $(
+        impl RangeCheckedNumber for $t {
+            fn compare_to(self, bound: NumberBound) -> Option<std::cmp::Ordering> {
+                match bound {
+                    NumberBound::Int(bound) => Some((self as i128).cmp(&bound)),
+                    NumberBound::Float(bound) => (self as f64).partial_cmp(&bound),
+                }
+            }
+        }
+    )* };
+}
+impl_range_checked_integer!(u8 u16 u32 u64 usize i8 i16 i32 i64 i128 isize);
+
+impl RangeCheckedNumber for u128 {
+    fn compare_to(self, bound: NumberBound) -> Option<std::cmp::Ordering> {
+        match (i128::try_from(self), bound) {
+            (Ok(value), bound) => value.compare_to(bound),
+            // Above i128::MAX, so above every integer bound
+            (Err(_), NumberBound::Int(_)) => Some(std::cmp::Ordering::Greater),
+            (Err(_), NumberBound::Float(bound)) => (self as f64).partial_cmp(&bound),
+        }
+    }
+}
+
+/// Implements [`RangeCheckedNumber`] for float types
+macro_rules! impl_range_checked_float {
+    ($($t:ty)*) => { $(
+        impl RangeCheckedNumber for $t {
+            fn compare_to(self, bound: NumberBound) -> Option<std::cmp::Ordering> {
+                (self as f64).partial_cmp(&bound.to_f64())
+            }
+        }
+    )* };
+}
+impl_range_checked_float!(f32 f64);
+
+/// Enforces `#[min]` and `#[max]` on a parsed prefix command parameter
+#[doc(hidden)]
+pub fn validate_number_range(
+    value: impl RangeCheckedNumber,
+    min: Option<NumberBound>,
+    max: Option<NumberBound>,
+) -> Result<(), (Box<dyn std::error::Error + Send + Sync>, Option<String>)> {
+    use std::cmp::Ordering;
+
+    // NaN isn't comparable, so it's rejected by any bound
+    let below_min = min.is_some_and(|min| {
+        !matches!(
+            value.compare_to(min),
+            Some(Ordering::Greater | Ordering::Equal)
+        )
+    });
+    let above_max = max.is_some_and(|max| {
+        !matches!(
+            value.compare_to(max),
+            Some(Ordering::Less | Ordering::Equal)
+        )
+    });
+    if below_min || above_max {
+        let error = NumberOutOfRange {
+            min: min.map(NumberBound::to_f64),
+            max: max.map(NumberBound::to_f64),
+            __non_exhaustive: (),
+        };
+        return Err((error.into(), Some(value.to_string())));
+    }
+    Ok(())
+}
+
+/// Enforces `#[min_length]` and `#[max_length]` on a parsed prefix command parameter
+#[doc(hidden)]
+pub fn validate_length_range(
+    value: &str,
+    min_length: Option<usize>,
+    max_length: Option<usize>,
+) -> Result<(), (Box<dyn std::error::Error + Send + Sync>, Option<String>)> {
+    // Discord counts characters, not bytes
+    let length = value.chars().count();
+    if min_length.is_some_and(|min| length < min) || max_length.is_some_and(|max| length > max) {
+        let error = LengthOutOfRange {
+            min_length,
+            max_length,
+            __non_exhaustive: (),
+        };
+        return Err((error.into(), Some(value.to_owned())));
+    }
+    Ok(())
+}
+
+/// Checks that a prefix command argument is the name of one of the choices supplied by a
+/// `#[choices_fn]` callback. Discord takes care of this for slash commands.
+#[doc(hidden)]
+pub fn validate_dynamic_choice(
+    value: &str,
+    choices: fn() -> Vec<crate::CommandParameterChoice>,
+) -> Result<(), (Box<dyn std::error::Error + Send + Sync>, Option<String>)> {
+    if choices().iter().any(|choice| choice.name == value) {
+        Ok(())
+    } else {
+        Err((
+            crate::InvalidChoice::default().into(),
+            Some(value.to_owned()),
+        ))
+    }
+}
+
+/// Error thrown when the `#[validate = "..."]` callback of a parameter rejects the parsed value
+#[derive(Default, Debug, Clone)]
+pub struct ValidationFailed {
+    /// User-facing message returned by the validator
+    pub message: String,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+impl std::fmt::Display for ValidationFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+impl std::error::Error for ValidationFailed {}
+
+/// Awaits the future returned by a `#[validate = "..."]` callback and converts a rejection into an
+/// argument parse error
+#[doc(hidden)]
+pub async fn run_validator<M: std::fmt::Display>(
+    validation: impl std::future::Future<Output = Result<(), M>>,
+) -> Result<(), (Box<dyn std::error::Error + Send + Sync>, Option<String>)> {
+    match validation.await {
+        Ok(()) => Ok(()),
+        Err(message) => {
+            let error = ValidationFailed {
+                message: message.to_string(),
+                __non_exhaustive: (),
+            };
+            Err((error.into(), None))
+        }
+    }
+}
+
+/// Error thrown when an attachment parameter annotated with `#[content_type = "..."]` receives a
+/// file of a different type
+#[derive(Default, Debug, Clone)]
+pub struct InvalidContentType {
+    /// The allowed content types, as written in the attribute (e.g. `image/*`)
+    pub expected: &'static str,
+    /// The content type of the received file, if Discord reported one
+    pub found: Option<String>,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+impl std::fmt::Display for InvalidContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.found {
+            Some(found) => write!(
+                f,
+                "Expected a file of type `{}`, but got `{}`",
+                self.expected, found
+            ),
+            None => write!(
+                f,
+                "Expected a file of type `{}`, but the file type is unknown",
+                self.expected
+            ),
+        }
+    }
+}
+impl std::error::Error for InvalidContentType {}
+
+/// Error thrown when an attachment parameter annotated with `#[max_size = "..."]` receives a file
+/// that's too large
+#[derive(Default, Debug, Clone)]
+pub struct AttachmentTooLarge {
+    /// Maximum allowed size in bytes
+    pub max_size: u64,
+    /// Size of the received file in bytes
+    pub size: u64,
+    #[doc(hidden)]
+    pub __non_exhaustive: (),
+}
+impl std::fmt::Display for AttachmentTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "File is too large ({}, but at most {} is allowed)",
+            format_size(self.size),
+            format_size(self.max_size)
+        )
+    }
+}
+impl std::error::Error for AttachmentTooLarge {}
+
+/// Formats a number of bytes with a binary unit, e.g. `8 MB` or `1.5 KB`
+fn format_size(bytes: u64) -> String {
+    let mut size = bytes as f64;
+    for unit in ["B", "KB", "MB"] {
+        if size < 1024.0 {
+            return format!("{} {}", (size * 10.0).round() / 10.0, unit);
+        }
+        size /= 1024.0;
+    }
+    format!("{} GB", (size * 10.0).round() / 10.0)
+}
+
+/// Checks whether a MIME type matches a comma-separated list of patterns like `image/*, video/mp4`
+fn content_type_matches(content_type: &str, patterns: &str) -> bool {
+    // Strip parameters like `; charset=utf-8`
+    let content_type = content_type.split(';').next().unwrap_or("").trim();
+    let (type_, subtype) = content_type.split_once('/').unwrap_or((content_type, ""));
+    patterns
+        .split(',')
+        .map(str::trim)
+        .any(|pattern| match pattern.split_once('/') {
+            Some((pattern_type, "*")) => {
+                pattern_type == "*" || pattern_type.eq_ignore_ascii_case(type_)
+            }
+            Some((pattern_type, pattern_subtype)) => {
+                pattern_type.eq_ignore_ascii_case(type_)
+                    && pattern_subtype.eq_ignore_ascii_case(subtype)
+            }
+            None => pattern == "*",
+        })
+}
+
+/// Enforces `#[content_type]` on a parsed attachment parameter
+#[doc(hidden)]
+pub fn validate_content_type(
+    attachment: &crate::serenity_prelude::Attachment,
+    expected: &'static str,
+) -> Result<(), (Box<dyn std::error::Error + Send + Sync>, Option<String>)> {
+    let found = attachment.content_type.as_deref();
+    if found.is_some_and(|found| content_type_matches(found, expected)) {
+        return Ok(());
+    }
+    let error = InvalidContentType {
+        expected,
+        found: found.map(|found| found.to_owned()),
+        __non_exhaustive: (),
+    };
+    Err((error.into(), Some(attachment.filename.clone())))
+}
+
+/// Enforces `#[max_size]` on a parsed attachment parameter
+#[doc(hidden)]
+pub fn validate_attachment_size(
+    attachment: &crate::serenity_prelude::Attachment,
+    max_size: u64,
+) -> Result<(), (Box<dyn std::error::Error + Send + Sync>, Option<String>)> {
+    if u64::from(attachment.size) <= max_size {
+        return Ok(());
+    }
+    let error = AttachmentTooLarge {
+        max_size,
+        size: attachment.size.into(),
+        __non_exhaustive: (),
+    };
+    Err((error.into(), Some(attachment.filename.clone())))
+}
+
+#[cfg(test)]
+#[test]
+fn test_content_type_matches() {
+    assert!(content_type_matches("image/png", "image/*"));
+    assert!(content_type_matches("IMAGE/PNG", "image/png"));
+    assert!(content_type_matches(
+        "text/plain; charset=utf-8",
+        "text/plain"
+    ));
+    assert!(content_type_matches("video/mp4", "image/*, video/mp4"));
+    assert!(content_type_matches("application/zip", "*/*"));
+    assert!(!content_type_matches("video/mp4", "image/*"));
+    assert!(!content_type_matches("image/png", "image/jpeg"));
+
+    assert_eq!(format_size(500), "500 B");
+    assert_eq!(format_size(1536), "1.5 KB");
+    assert_eq!(format_size(8 * 1024 * 1024), "8 MB");
+}
+
+#[cfg(test)]
+#[test]
+fn test_validate_number_range() {
+    let max = Some(NumberBound::Int(9_007_199_254_740_992)); // 2^53
+    assert!(validate_number_range(9_007_199_254_740_992_u64, None, max).is_ok());
+    // Equal to the bound when both are rounded to f64
+    assert!(validate_number_range(9_007_199_254_740_993_u64, None, max).is_err());
+    assert!(validate_number_range(u128::MAX, None, max).is_err());
+
+    let min = Some(NumberBound::Int(-5));
+    assert!(validate_number_range(-5_i64, min, None).is_ok());
+    assert!(validate_number_range(-6_i64, min, None).is_err());
+    assert!(validate_number_range(0.5_f32, Some(NumberBound::Float(0.5)), None).is_ok());
+    assert!(validate_number_range(f64::NAN, min, None).is_err());
+}